@@ -31,12 +31,27 @@ pub enum GameError {
     #[error("insufficient funds")]
     InsufficientFunds,
 
+    #[error("buy in must be at least {minimum}")]
+    BuyInTooSmall { minimum: u64 },
+
+    #[error("buy in must be at most {maximum}")]
+    BuyInTooLarge { maximum: u64 },
+
     #[error("no winner")]
     NoWinner,
 
     #[error("no cards left")]
     NoCardsLeft,
 
+    #[error("hand not found")]
+    HandNotFound,
+
+    #[error("replay mismatch: {reason}")]
+    ReplayMismatch { reason: String },
+
+    #[error("chips not conserved: {chips_before} before the hand, {chips_after} after")]
+    ChipsNotConserved { chips_before: u64, chips_after: u64 },
+
     #[error("Could not calculate rake")]
     CouldNotCalculateRake,
 
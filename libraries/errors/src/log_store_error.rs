@@ -13,4 +13,7 @@ pub enum LogStoreError {
 
     #[error("Canister call failed: {0}")]
     CanisterCallError(String),
+
+    #[error("Hand not found")]
+    HandNotFound,
 }
@@ -30,6 +30,35 @@ pub async fn update_table_player_count_wrapper(
     }
 }
 
+pub async fn return_to_fast_fold_pool_wrapper(
+    backend_principal: Principal,
+    users_canister_principal: Principal,
+    user_principal: Principal,
+    stack: u64,
+) -> Result<(), TableIndexError> {
+    let call_result =
+        ic_cdk::call::Call::unbounded_wait(backend_principal, "return_to_fast_fold_pool")
+            .with_args(&(users_canister_principal, user_principal, stack))
+            .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error returning user to fast-fold pool: {:?}", err);
+                Err(TableIndexError::CanisterCallError(format!(
+                    "Failed to decode return_to_fast_fold_pool response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in return_to_fast_fold_pool call: {:?}", err);
+            Err(TableIndexError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
 pub async fn get_rake_stats(table_id: Principal) -> Result<RakeStats, TableError> {
     let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "get_rake_stats").await;
 
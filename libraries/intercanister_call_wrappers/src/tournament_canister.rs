@@ -4,7 +4,7 @@ use errors::{
     tournament_index_error::TournamentIndexError,
 };
 use ic_cdk::management_canister::{
-    CanisterSettings, CanisterStatusArgs, UpdateSettingsArgs, canister_status, update_settings,
+    canister_status, update_settings, CanisterSettings, CanisterStatusArgs, UpdateSettingsArgs,
 };
 use table::poker::game::{table_functions::table::TableConfig, types::PublicTable};
 use tournaments::tournaments::{
@@ -373,12 +373,43 @@ pub async fn update_tournament_state_icc_wrapper(
     }
 }
 
+pub async fn fund_tournament_tickets_wrapper(
+    tournament_index: Principal,
+    amount: u64,
+) -> Result<(), TournamentIndexError> {
+    let call_result =
+        ic_cdk::call::Call::unbounded_wait(tournament_index, "fund_tournament_tickets")
+            .with_arg(amount)
+            .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error funding tournament tickets: {:?}", err);
+                Err(TournamentIndexError::CanisterCallFailed(format!(
+                    "Failed to decode fund_tournament_tickets response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in fund_tournament_tickets call: {:?}", err);
+            Err(TournamentIndexError::CanisterCallFailed(format!(
+                "{:?}",
+                err
+            )))
+        }
+    }
+}
+
 pub async fn update_blinds(table_id: Principal, new_level: &BlindLevel) -> Result<(), TableError> {
     let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "update_blinds")
         .with_args(&(
             new_level.small_blind,
             new_level.big_blind,
             new_level.ante_type.clone(),
+            new_level.time_bank_seconds,
         ))
         .await;
 
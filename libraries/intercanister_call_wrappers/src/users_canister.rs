@@ -1,6 +1,9 @@
 use candid::Principal;
 use errors::user_error::UserError;
-use user::user::{User, UserAvatar};
+use user::{
+    ticket::{Ticket, TicketScope},
+    user::{User, UserAvatar},
+};
 
 pub async fn create_user_wrapper(
     user_canister: Principal,
@@ -360,3 +363,87 @@ pub async fn clear_pure_poker_experience_points_wrapper(
         }
     }
 }
+
+pub async fn issue_ticket_wrapper(
+    users_canister_id: Principal,
+    user_id: Principal,
+    scope: TicketScope,
+    value: u64,
+    expires_at: Option<u64>,
+) -> Result<Ticket, UserError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(users_canister_id, "issue_ticket")
+        .with_args(&(user_id, scope, value, expires_at))
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error decoding issue_ticket response: {:?}", err);
+                Err(UserError::CanisterCallFailed(format!(
+                    "Failed to decode issue_ticket response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in issue_ticket call: {:?}", err);
+            Err(UserError::CanisterCallFailed(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn redeem_ticket_wrapper(
+    users_canister_id: Principal,
+    user_id: Principal,
+    tournament_id: Principal,
+    buy_in: u64,
+) -> Result<Option<Ticket>, UserError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(users_canister_id, "redeem_ticket")
+        .with_args(&(user_id, tournament_id, buy_in))
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error decoding redeem_ticket response: {:?}", err);
+                Err(UserError::CanisterCallFailed(format!(
+                    "Failed to decode redeem_ticket response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in redeem_ticket call: {:?}", err);
+            Err(UserError::CanisterCallFailed(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn refund_ticket_wrapper(
+    users_canister_id: Principal,
+    user_id: Principal,
+    ticket_id: u64,
+) -> Result<Ticket, UserError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(users_canister_id, "refund_ticket")
+        .with_args(&(user_id, ticket_id))
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error decoding refund_ticket response: {:?}", err);
+                Err(UserError::CanisterCallFailed(format!(
+                    "Failed to decode refund_ticket response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in refund_ticket call: {:?}", err);
+            Err(UserError::CanisterCallFailed(format!("{:?}", err)))
+        }
+    }
+}
@@ -74,6 +74,27 @@ impl Deck {
     pub fn iter(&self) -> Iter<Card> {
         self.cards.iter()
     }
+
+    /// Create the 36 card short deck (sixes through aces)
+    /// used for short-deck Hold'em.
+    ///
+    /// ```
+    /// use table::poker::core::Deck;
+    ///
+    /// assert_eq!(36, Deck::short_deck().len());
+    /// ```
+    pub fn short_deck() -> Self {
+        let mut cards: HashSet<Card> = HashSet::new();
+        for v in Value::values().iter().filter(|v| **v >= Value::Six) {
+            for s in &Suit::suits() {
+                cards.insert(Card {
+                    value: *v,
+                    suit: *s,
+                });
+            }
+        }
+        Self { cards }
+    }
 }
 
 /// Turn a deck into an iterator
@@ -133,4 +154,18 @@ mod tests {
         assert!(!d.contains(&c));
         assert!(!d.remove(&c));
     }
+
+    #[test]
+    fn test_short_deck_has_no_low_cards() {
+        let d = Deck::short_deck();
+        assert_eq!(36, d.len());
+        assert!(!d.contains(&Card {
+            value: Value::Five,
+            suit: Suit::Heart,
+        }));
+        assert!(d.contains(&Card {
+            value: Value::Six,
+            suit: Suit::Heart,
+        }));
+    }
 }
@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use candid::CandidType;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::poker::core::card::Card;
+use crate::poker::core::card_iter::CardIter;
+use crate::poker::core::deck::Deck;
+use crate::poker::core::error::RSPokerError;
+use crate::poker::core::flat_deck::random_index;
+use crate::poker::core::rank::{rank_omaha, Rank, Rankable};
+
+/// Above this many runouts the equities are estimated from
+/// random runouts instead of dealing every one of them.
+pub const MAX_EXACT_RUNOUTS: u64 = 50_000;
+
+/// The number of community cards on a complete board.
+const BOARD_SIZE: usize = 5;
+
+/// How the hands are made and ranked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, CandidType, Serialize, Deserialize)]
+pub enum EquityRules {
+    /// Any five of the hole cards and the board play, 52 card deck.
+    #[default]
+    Holdem,
+    /// Hold'em with the 36 card deck, where a flush beats a full house.
+    ShortDeck,
+    /// Exactly two hole cards and three board cards play, 52 card deck.
+    Omaha,
+}
+
+impl EquityRules {
+    fn rank(&self, hole_cards: &[Card], board: &[Card]) -> Rank {
+        match self {
+            EquityRules::Omaha => rank_omaha(hole_cards, board),
+            EquityRules::Holdem | EquityRules::ShortDeck => {
+                let mut cards = hole_cards.to_vec();
+                cards.extend_from_slice(board);
+                if *self == EquityRules::ShortDeck {
+                    cards.rank_short_deck()
+                } else {
+                    cards.rank()
+                }
+            }
+        }
+    }
+
+    fn cmp(&self, a: &Rank, b: &Rank) -> Ordering {
+        match self {
+            EquityRules::ShortDeck => a.cmp_short_deck(b),
+            EquityRules::Holdem | EquityRules::Omaha => a.cmp(b),
+        }
+    }
+
+    fn deck(&self) -> Deck {
+        match self {
+            EquityRules::ShortDeck => Deck::short_deck(),
+            EquityRules::Holdem | EquityRules::Omaha => Deck::default(),
+        }
+    }
+}
+
+/// The equities of a set of hands.
+#[derive(Debug, Clone, PartialEq, CandidType, Serialize, Deserialize)]
+pub struct Equities {
+    /// The share of the pot each hand wins on average, in the order
+    /// the hands were given. A tie splits the share between the hands.
+    pub equities: Vec<f64>,
+    /// Whether every runout was dealt. Otherwise the equities are
+    /// estimated from random runouts.
+    pub is_exact: bool,
+    /// The number of runouts the equities were worked out from.
+    pub runouts: u64,
+}
+
+/// The number of ways to pick `k` out of `n`.
+fn combinations(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1u64, |acc, i| acc * (n - i) as u64 / (i as u64 + 1))
+}
+
+/// Adds the share of the pot every hand wins on one complete board.
+fn add_shares(rules: EquityRules, hands: &[Vec<Card>], board: &[Card], shares: &mut [f64]) {
+    let ranks: Vec<Rank> = hands.iter().map(|hand| rules.rank(hand, board)).collect();
+    let Some(best) = ranks.iter().max_by(|a, b| rules.cmp(a, b)).copied() else {
+        return;
+    };
+    let winners: Vec<usize> = ranks
+        .iter()
+        .enumerate()
+        .filter(|(_, rank)| rules.cmp(rank, &best).is_eq())
+        .map(|(i, _)| i)
+        .collect();
+    let share = 1.0 / winners.len() as f64;
+    for i in winners {
+        shares[i] += share;
+    }
+}
+
+/// Works out how often each hand wins once the board is complete.
+///
+/// Every runout is dealt when there are at most [`MAX_EXACT_RUNOUTS`]
+/// of them. Otherwise `samples` random runouts are dealt, drawn with
+/// ChaCha20 seeded with `seed`, so the same inputs always give the
+/// same estimate.
+///
+/// # Examples
+/// ```
+/// use table::poker::core::{calculate_equities, EquityRules, Hand};
+///
+/// let aces = Hand::new_from_str("AsAh").unwrap()[..].to_vec();
+/// let kings = Hand::new_from_str("KsKh").unwrap()[..].to_vec();
+/// let board = Hand::new_from_str("2c7d9h").unwrap()[..].to_vec();
+///
+/// let equities =
+///     calculate_equities(&[aces, kings], &board, EquityRules::Holdem, 0, &[0; 32]).unwrap();
+/// assert!(equities.is_exact);
+/// assert!(equities.equities[0] > 0.9);
+/// ```
+///
+/// # Errors
+///
+/// - [`RSPokerError::DuplicateCardInHand`] if a card is used twice
+/// - [`RSPokerError::Other`] if there are fewer than two hands, a hand
+///   has the wrong number of cards, the board has more than five cards
+///   or a card isn't in the deck
+pub fn calculate_equities(
+    hands: &[Vec<Card>],
+    board: &[Card],
+    rules: EquityRules,
+    samples: u64,
+    seed: &[u8; 32],
+) -> Result<Equities, RSPokerError> {
+    if hands.len() < 2 {
+        return Err(RSPokerError::Other(
+            "At least two hands are needed".to_string(),
+        ));
+    }
+    if board.len() > BOARD_SIZE {
+        return Err(RSPokerError::Other(
+            "The board has more than five cards".to_string(),
+        ));
+    }
+    let is_hand_size_valid = |hand: &Vec<Card>| match rules {
+        EquityRules::Omaha => hand.len() >= 4,
+        EquityRules::Holdem | EquityRules::ShortDeck => hand.len() == 2,
+    };
+    if !hands.iter().all(is_hand_size_valid) {
+        return Err(RSPokerError::Other(
+            "A hand has the wrong number of cards".to_string(),
+        ));
+    }
+
+    let mut deck = rules.deck();
+    let mut used = HashSet::new();
+    for card in hands.iter().flatten().chain(board.iter()) {
+        if !used.insert(*card) {
+            return Err(RSPokerError::DuplicateCardInHand(*card));
+        }
+        if !deck.remove(card) {
+            return Err(RSPokerError::Other(format!(
+                "Card {} isn't in the deck",
+                card
+            )));
+        }
+    }
+    // The deck doesn't keep an order, sort it so a seed always
+    // deals the same runouts
+    let mut remaining: Vec<Card> = deck.into_iter().collect();
+    remaining.sort();
+
+    let missing = BOARD_SIZE - board.len();
+    let mut shares = vec![0.0; hands.len()];
+    let mut full_board = board.to_vec();
+    let all_runouts = combinations(remaining.len(), missing);
+
+    let (is_exact, runouts) = if missing == 0 {
+        add_shares(rules, hands, board, &mut shares);
+        (true, 1)
+    } else if all_runouts <= MAX_EXACT_RUNOUTS {
+        for runout in CardIter::new(&remaining, missing) {
+            full_board.truncate(board.len());
+            full_board.extend(runout);
+            add_shares(rules, hands, &full_board, &mut shares);
+        }
+        (true, all_runouts)
+    } else {
+        let mut rng = ChaCha20Rng::from_seed(*seed);
+        for _ in 0..samples {
+            // Only the first cards of the deck need to be shuffled
+            for i in 0..missing {
+                let j = i + random_index(&mut rng, remaining.len() - i);
+                remaining.swap(i, j);
+            }
+            full_board.truncate(board.len());
+            full_board.extend_from_slice(&remaining[..missing]);
+            add_shares(rules, hands, &full_board, &mut shares);
+        }
+        (false, samples)
+    };
+
+    let equities = if runouts == 0 {
+        shares
+    } else {
+        shares
+            .into_iter()
+            .map(|share| share / runouts as f64)
+            .collect()
+    };
+    Ok(Equities {
+        equities,
+        is_exact,
+        runouts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::core::hand::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        Hand::new_from_str(s).unwrap()[..].to_vec()
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(990, combinations(45, 2));
+        assert_eq!(1_712_304, combinations(48, 5));
+        assert_eq!(1, combinations(44, 0));
+    }
+
+    #[test]
+    fn test_complete_board() {
+        let equities = calculate_equities(
+            &[cards("AsAh"), cards("KsKh")],
+            &cards("2c7d9hJsQd"),
+            EquityRules::Holdem,
+            0,
+            &[0; 32],
+        )
+        .unwrap();
+        assert_eq!(vec![1.0, 0.0], equities.equities);
+        assert!(equities.is_exact);
+        assert_eq!(1, equities.runouts);
+    }
+
+    #[test]
+    fn test_tie_splits() {
+        let equities = calculate_equities(
+            &[cards("2s3h"), cards("2d3c")],
+            &cards("AsKsQsJsTs"),
+            EquityRules::Holdem,
+            0,
+            &[0; 32],
+        )
+        .unwrap();
+        assert_eq!(vec![0.5, 0.5], equities.equities);
+    }
+
+    #[test]
+    fn test_exact_on_the_turn() {
+        // Only the two eights left in the deck win for the underdog
+        let equities = calculate_equities(
+            &[cards("AsAh"), cards("8s8h")],
+            &cards("2c7d9hKc"),
+            EquityRules::Holdem,
+            0,
+            &[0; 32],
+        )
+        .unwrap();
+        assert!(equities.is_exact);
+        assert_eq!(44, equities.runouts);
+        assert_eq!(vec![42.0 / 44.0, 2.0 / 44.0], equities.equities);
+    }
+
+    #[test]
+    fn test_sampled_preflop() {
+        let run = |seed: [u8; 32]| {
+            calculate_equities(
+                &[cards("AsAh"), cards("KsKh")],
+                &[],
+                EquityRules::Holdem,
+                2_000,
+                &seed,
+            )
+            .unwrap()
+        };
+        let equities = run([1; 32]);
+        assert!(!equities.is_exact);
+        assert_eq!(2_000, equities.runouts);
+        // Aces are about an 82% favourite
+        assert!((equities.equities[0] - 0.82).abs() < 0.04);
+        assert!((equities.equities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(equities, run([1; 32]));
+    }
+
+    #[test]
+    fn test_omaha_uses_two_hole_cards() {
+        // Four spades in hand don't make a flush with one on the board
+        let equities = calculate_equities(
+            &[cards("AsKsQsJs"), cards("2d2h3c4c")],
+            &cards("5s8d9c3h2c"),
+            EquityRules::Omaha,
+            0,
+            &[0; 32],
+        )
+        .unwrap();
+        assert_eq!(vec![0.0, 1.0], equities.equities);
+    }
+
+    #[test]
+    fn test_invalid_hands() {
+        assert!(matches!(
+            calculate_equities(
+                &[cards("AsAh"), cards("AsKh")],
+                &[],
+                EquityRules::Holdem,
+                0,
+                &[0; 32],
+            ),
+            Err(RSPokerError::DuplicateCardInHand(_))
+        ));
+        assert!(
+            calculate_equities(&[cards("AsAh")], &[], EquityRules::Holdem, 0, &[0; 32]).is_err()
+        );
+        assert!(calculate_equities(
+            &[cards("AsAh"), cards("2s2h")],
+            &[],
+            EquityRules::ShortDeck,
+            0,
+            &[0; 32],
+        )
+        .is_err());
+    }
+}
@@ -3,7 +3,31 @@ use crate::poker::core::deck::Deck;
 use std::ops::{Index, Range, RangeFrom, RangeFull, RangeTo};
 
 use candid::CandidType;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Derive the 32 byte shuffle seed from raw random bytes
+/// (the `raw_rand` VRF output on the IC).
+pub fn shuffle_seed(rand_bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(rand_bytes).into()
+}
+
+/// Draw a uniformly distributed index below `bound`.
+///
+/// Values from the top of the range that would make some indices
+/// more likely than others are rejected, so there's no modulo bias.
+pub(crate) fn random_index(rng: &mut ChaCha20Rng, bound: usize) -> usize {
+    let bound = bound as u64;
+    let zone = u64::MAX - (u64::MAX % bound);
+    loop {
+        let value = rng.next_u64();
+        if value < zone {
+            return (value % bound) as usize;
+        }
+    }
+}
 
 /// `FlatDeck` is a deck of cards that allows easy
 /// indexing into the cards. It does not provide
@@ -29,14 +53,33 @@ impl FlatDeck {
     /// Randomly shuffle the flat deck.
     /// This will ensure the there's no order to the deck.
     pub fn shuffle(&mut self, rand_bytes: Vec<u8>) {
-        // Perform the Fisher-Yates shuffle using the random bytes
-        let mut n = self.cards.len();
-        for i in 0..n - 1 {
-            let rand_index = (rand_bytes[i % rand_bytes.len()] as usize) % n;
-            self.cards.swap(i, rand_index);
-            // Adjust n to prevent the same bytes from influencing too many swaps
-            n = n.saturating_sub(1);
+        self.shuffle_with_seed(&shuffle_seed(&rand_bytes));
+    }
+
+    /// Shuffle the deck with an unbiased Fisher-Yates shuffle driven by
+    /// ChaCha20 seeded with `seed`. The same seed and starting order
+    /// always give the same deck.
+    pub fn shuffle_with_seed(&mut self, seed: &[u8; 32]) {
+        let mut rng = ChaCha20Rng::from_seed(*seed);
+        for i in (1..self.cards.len()).rev() {
+            let j = random_index(&mut rng, i + 1);
+            self.cards.swap(i, j);
+        }
+    }
+
+    /// SHA-256 commitment to the seed and the current order of the deck,
+    /// as a hex string.
+    pub fn commitment(&self, seed: &[u8; 32]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(seed);
+        for card in &self.cards {
+            hasher.update([card.value as u8, card.suit as u8]);
         }
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
     }
 
     /// Deal a card if there is one there to deal.
@@ -99,9 +142,21 @@ impl From<Deck> for FlatDeck {
 
 impl FlatDeck {
     pub fn new(bytes: Vec<u8>) -> Self {
-        let cards: Vec<Card> = Deck::default().into_iter().collect();
-        let mut fdeck = Self { cards };
-        fdeck.shuffle(bytes);
+        Self::from_seed(&shuffle_seed(&bytes), Deck::default())
+    }
+
+    /// Create a shuffled 36 card short deck (sixes through aces).
+    pub fn new_short_deck(bytes: Vec<u8>) -> Self {
+        Self::from_seed(&shuffle_seed(&bytes), Deck::short_deck())
+    }
+
+    /// Flatten the deck into its sorted order and shuffle it with the seed.
+    ///
+    /// This is what the table uses to build the deck for every hand,
+    /// so anyone holding a revealed seed can recompute the deck.
+    pub fn from_seed(seed: &[u8; 32], deck: Deck) -> Self {
+        let mut fdeck: FlatDeck = deck.into();
+        fdeck.shuffle_with_seed(seed);
 
         fdeck
     }
@@ -124,6 +179,45 @@ mod tests {
         assert_eq!(52, fd.len());
     }
 
+    #[test]
+    fn test_short_deck_new() {
+        let fd: FlatDeck = FlatDeck::new_short_deck(vec![1, 2, 3, 4, 5]);
+        assert_eq!(36, fd.len());
+    }
+
+    #[test]
+    fn test_same_seed_same_deck() {
+        let seed = shuffle_seed(&[7; 32]);
+        let first = FlatDeck::from_seed(&seed, Deck::default());
+        let second = FlatDeck::from_seed(&seed, Deck::default());
+        assert_eq!(first, second);
+        assert_eq!(first.commitment(&seed), second.commitment(&seed));
+
+        let other = FlatDeck::from_seed(&shuffle_seed(&[8; 32]), Deck::default());
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let deck = FlatDeck::from_seed(&shuffle_seed(&[1, 2, 3]), Deck::default());
+        let mut cards = deck[..].to_vec();
+        cards.sort();
+        cards.dedup();
+        assert_eq!(52, cards.len());
+    }
+
+    #[test]
+    fn test_commitment_binds_seed_and_order() {
+        let seed = shuffle_seed(&[1, 2, 3]);
+        let mut deck = FlatDeck::from_seed(&seed, Deck::default());
+        let commitment = deck.commitment(&seed);
+        assert_eq!(64, commitment.len());
+        assert_ne!(commitment, deck.commitment(&shuffle_seed(&[4, 5, 6])));
+
+        deck.deal();
+        assert_ne!(commitment, deck.commitment(&seed));
+    }
+
     #[test]
     fn test_from_vec() {
         let c = Card {
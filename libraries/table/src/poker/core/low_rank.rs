@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::poker::core::card::{Card, Value};
+use crate::poker::core::card_iter::CardIter;
+
+/// The highest card value that can still qualify for the low half of the pot.
+const LOW_QUALIFIER: u32 = 8;
+
+/// The rank of a qualifying eight-or-better low hand (A-5 lowball).
+///
+/// Straights and flushes don't count against a low and aces play low.
+/// The inner u32 packs the five card values from highest to lowest,
+/// so a smaller value is a better low. The `Ord` implementation is
+/// reversed so that, like `Rank`, the better hand compares greater.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Copy, CandidType, Deserialize, Serialize)]
+pub struct LowRank(pub u32);
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The value of a card when playing for low. Aces are one.
+fn low_value(card: &Card) -> u32 {
+    match card.value {
+        Value::Ace => 1,
+        value => value as u32 + 2,
+    }
+}
+
+/// Find the best eight-or-better low that can be made from any five
+/// of the given cards.
+///
+/// Returns None if there aren't five distinct cards of eight or lower.
+///
+/// # Examples
+/// ```
+/// use table::poker::core::{rank_low, Hand};
+///
+/// let wheel = Hand::new_from_str("As2d3c4h5s").unwrap();
+/// let eight_low = Hand::new_from_str("As2d3c4h8s").unwrap();
+/// assert!(rank_low(&wheel[..]) > rank_low(&eight_low[..]));
+///
+/// let no_low = Hand::new_from_str("As2d3c4h9s").unwrap();
+/// assert_eq!(rank_low(&no_low[..]), None);
+/// ```
+pub fn rank_low(cards: &[Card]) -> Option<LowRank> {
+    let mut value_set: u32 = 0;
+    for card in cards {
+        let value = low_value(card);
+        if value <= LOW_QUALIFIER {
+            value_set |= 1 << value;
+        }
+    }
+    if value_set.count_ones() < 5 {
+        return None;
+    }
+
+    // Keeping the five lowest distinct values always gives the best low.
+    let mut values = Vec::with_capacity(5);
+    for value in 1..=LOW_QUALIFIER {
+        if value_set & (1 << value) != 0 {
+            values.push(value);
+            if values.len() == 5 {
+                break;
+            }
+        }
+    }
+
+    let rank = values
+        .iter()
+        .rev()
+        .fold(0, |rank, value| (rank << 4) | value);
+    Some(LowRank(rank))
+}
+
+/// Find the best eight-or-better Omaha low. Like the high hand it has
+/// to use exactly two of the hole cards and three of the community cards.
+///
+/// Returns None if no qualifying low can be made.
+pub fn rank_omaha_low(hole_cards: &[Card], community_cards: &[Card]) -> Option<LowRank> {
+    if community_cards.len() < 3 {
+        return None;
+    }
+    let mut best: Option<LowRank> = None;
+    for hole in CardIter::new(hole_cards, 2) {
+        for board in CardIter::new(community_cards, 3) {
+            let mut cards = hole.clone();
+            cards.extend(board);
+            if let Some(rank) = rank_low(&cards) {
+                best = Some(best.map_or(rank, |b| b.max(rank)));
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::core::hand::*;
+
+    #[test]
+    fn test_wheel_is_best_low() {
+        let wheel = Hand::new_from_str("As2d3c4h5s").unwrap();
+        let six_low = Hand::new_from_str("As2d3c4h6s").unwrap();
+        assert!(rank_low(&wheel[..]).unwrap() > rank_low(&six_low[..]).unwrap());
+    }
+
+    #[test]
+    fn test_low_compares_highest_card_first() {
+        // 7-5-4-3-2 beats 7-6-3-2-A
+        let better = Hand::new_from_str("7s5d4c3h2s").unwrap();
+        let worse = Hand::new_from_str("7s6d3c2hAs").unwrap();
+        assert!(rank_low(&better[..]).unwrap() > rank_low(&worse[..]).unwrap());
+    }
+
+    #[test]
+    fn test_low_ignores_pairs_and_high_cards() {
+        let hand = Hand::new_from_str("AsAd2c3h4s5dKc").unwrap();
+        let wheel = Hand::new_from_str("As2d3c4h5s").unwrap();
+        assert_eq!(rank_low(&hand[..]), rank_low(&wheel[..]));
+    }
+
+    #[test]
+    fn test_no_qualifying_low() {
+        let hand = Hand::new_from_str("As2d3c9hTs").unwrap();
+        assert_eq!(rank_low(&hand[..]), None);
+        let paired = Hand::new_from_str("As2d3c3h2sKdQc").unwrap();
+        assert_eq!(rank_low(&paired[..]), None);
+    }
+
+    #[test]
+    fn test_omaha_low_uses_two_hole_cards() {
+        // Only one low card in hand, so no low even though the board has four.
+        let hole = Hand::new_from_str("AsKdQcJh").unwrap();
+        let board = Hand::new_from_str("2d3c4h5sTd").unwrap();
+        assert_eq!(rank_omaha_low(&hole[..], &board[..]), None);
+
+        let hole = Hand::new_from_str("As2dQcJh").unwrap();
+        let board = Hand::new_from_str("3c4h5sKdTd").unwrap();
+        let wheel = Hand::new_from_str("As2d3c4h5s").unwrap();
+        assert_eq!(rank_omaha_low(&hole[..], &board[..]), rank_low(&wheel[..]));
+    }
+}
@@ -26,12 +26,22 @@ pub use self::deck::Deck;
 /// Flattened deck
 mod flat_deck;
 /// Export the trait and the result.
-pub use self::flat_deck::FlatDeck;
+pub use self::flat_deck::{shuffle_seed, FlatDeck};
 
 /// 5 Card hand ranking code.
 mod rank;
 /// Export the trait and the results.
-pub use self::rank::{Rank, Rankable};
+pub use self::rank::{rank_omaha, Rank, Rankable};
+
+/// Eight-or-better low hand ranking code.
+mod low_rank;
+/// Export the low rank and the evaluators.
+pub use self::low_rank::{rank_low, rank_omaha_low, LowRank};
+
+/// All-in equities from every runout or from random runouts.
+mod equity;
+/// Export the calculator and the results.
+pub use self::equity::{calculate_equities, Equities, EquityRules, MAX_EXACT_RUNOUTS};
 
 // u16 backed player set.
 mod player_bit_set;
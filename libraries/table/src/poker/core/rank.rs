@@ -1,7 +1,10 @@
+use std::cmp::Ordering;
+
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
 use crate::poker::core::card::Card;
+use crate::poker::core::card_iter::CardIter;
 use crate::poker::core::hand::Hand;
 
 /// All the different possible hand ranks.
@@ -33,15 +36,51 @@ pub enum Rank {
     StraightFlush(u32),
 }
 
+impl Rank {
+    /// The strength of the rank's category and of the hand within it
+    /// when playing short deck, where a flush beats a full house.
+    fn short_deck_strength(&self) -> (u8, u32) {
+        match *self {
+            Rank::HighCard(v) => (0, v),
+            Rank::OnePair(v) => (1, v),
+            Rank::TwoPair(v) => (2, v),
+            Rank::ThreeOfAKind(v) => (3, v),
+            Rank::Straight(v) => (4, v),
+            Rank::FullHouse(v) => (5, v),
+            Rank::Flush(v) => (6, v),
+            Rank::FourOfAKind(v) => (7, v),
+            Rank::StraightFlush(v) => (8, v),
+        }
+    }
+
+    /// Compare two ranks using the short deck hand order.
+    ///
+    /// # Examples
+    /// ```
+    /// use table::poker::core::Rank;
+    ///
+    /// assert!(Rank::Flush(0) < Rank::FullHouse(0));
+    /// assert!(Rank::Flush(0).cmp_short_deck(&Rank::FullHouse(0)).is_gt());
+    /// ```
+    pub fn cmp_short_deck(&self, other: &Rank) -> Ordering {
+        self.short_deck_strength().cmp(&other.short_deck_strength())
+    }
+}
+
 /// Bit mask for the wheel (Ace, two, three, four, five)
 const WHEEL: u32 = 0b1_0000_0000_1111;
+/// Bit mask for the short deck wheel (Ace, six, seven, eight, nine)
+const SHORT_DECK_WHEEL: u32 = 0b1_0000_1111_0000;
 /// Given a bitset of hand ranks. This method
 /// will determine if there's a straight, and will give the
 /// rank. Wheel is the lowest, broadway is the highest value.
 ///
+/// `wheel` is the mask of the lowest straight where the ace plays low,
+/// which differs between the full and the short deck.
+///
 /// Returns None if the hand ranks represented don't correspond
 /// to a straight.
-fn rank_straight(value_set: u32) -> Option<u32> {
+fn rank_straight(value_set: u32, wheel: u32) -> Option<u32> {
     // Example of something with a straight:
     //       0000111111100
     //       0001111111000
@@ -70,7 +109,7 @@ fn rank_straight(value_set: u32) -> Option<u32> {
     // If this isn't all zeros then we found a straight
     if idx < 32 {
         Some(32 - 4 - idx)
-    } else if value_set & WHEEL == WHEEL {
+    } else if value_set & wheel == wheel {
         // Check to see if this is the wheel. It's pretty unlikely.
         Some(0)
     } else {
@@ -97,6 +136,85 @@ fn keep_n(rank: u32, to_keep: u32) -> u32 {
 fn find_flush(suit_value_sets: &[u32]) -> Option<usize> {
     suit_value_sets.iter().position(|sv| sv.count_ones() >= 5)
 }
+/// Rank five or more cards to find the best 5 card hand.
+/// `wheel` is the mask of the lowest straight, see `rank_straight`.
+fn rank_cards(cards: &[Card], wheel: u32) -> Rank {
+    let mut value_to_count: [u8; 13] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let mut count_to_value: [u32; 5] = [0, 0, 0, 0, 0];
+    let mut suit_value_sets: [u32; 4] = [0, 0, 0, 0];
+    let mut value_set: u32 = 0;
+
+    for c in cards {
+        let v = c.value as u8;
+        let s = c.suit as u8;
+        value_set |= 1 << v;
+        value_to_count[v as usize] += 1;
+        suit_value_sets[s as usize] |= 1 << v;
+    }
+
+    // Now rotate the value to count map.
+    for (value, &count) in value_to_count.iter().enumerate() {
+        count_to_value[count as usize] |= 1 << value;
+    }
+
+    // Find out if there's a flush
+    let flush: Option<usize> = find_flush(&suit_value_sets);
+
+    // If this is a flush then it could be a straight flush
+    // or a flush. So check only once.
+    if let Some(flush_idx) = flush {
+        // If we can find a straight in the flush then it's a straight flush
+        if let Some(rank) = rank_straight(suit_value_sets[flush_idx], wheel) {
+            Rank::StraightFlush(rank)
+        } else {
+            // Else it's just a normal flush
+            let rank = keep_n(suit_value_sets[flush_idx], 5);
+            Rank::Flush(rank)
+        }
+    } else if count_to_value[4] != 0 {
+        // Four of a kind.
+        let high = keep_highest(value_set ^ count_to_value[4]);
+        Rank::FourOfAKind((count_to_value[4] << 13) | high)
+    } else if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
+        // There are two sets. So the best we can make is a full house.
+        let set = keep_highest(count_to_value[3]);
+        let pair = count_to_value[3] ^ set;
+        Rank::FullHouse((set << 13) | pair)
+    } else if count_to_value[3] != 0 && count_to_value[2] != 0 {
+        // there is a pair and a set.
+        let set = count_to_value[3];
+        let pair = keep_highest(count_to_value[2]);
+        Rank::FullHouse((set << 13) | pair)
+    } else if let Some(s_rank) = rank_straight(value_set, wheel) {
+        // If there's a straight return it now.
+        Rank::Straight(s_rank)
+    } else if count_to_value[3] != 0 {
+        // if there is a set then we need to keep 2 cards that
+        // aren't in the set.
+        let low = keep_n(value_set ^ count_to_value[3], 2);
+        Rank::ThreeOfAKind((count_to_value[3] << 13) | low)
+    } else if count_to_value[2].count_ones() >= 2 {
+        // Two pair
+        //
+        // That can be because we have 3 pairs and a high card.
+        // Or we could have two pair and two high cards.
+        let pairs = keep_n(count_to_value[2], 2);
+        let low = keep_highest(value_set ^ pairs);
+        Rank::TwoPair((pairs << 13) | low)
+    } else if count_to_value[2] == 0 {
+        // This means that there's no pair
+        // no sets, no straights, no flushes, so only a
+        // high card.
+        Rank::HighCard(keep_n(value_set, 5))
+    } else {
+        // Otherwise there's only one pair.
+        let pair = count_to_value[2];
+        // Keep the highest three cards not in the pair.
+        let low = keep_n(value_set ^ count_to_value[2], 3);
+        Rank::OnePair((pair << 13) | low)
+    }
+}
+
 /// Can this turn into a hand rank? There are default implementations for
 /// `Hand` and `Vec<Card>`.
 pub trait Rankable {
@@ -119,80 +237,23 @@ pub trait Rankable {
     /// assert!(Rank::TwoPair(u32::max_value()) >= rank);
     /// ```
     fn rank(&self) -> Rank {
-        let mut value_to_count: [u8; 13] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-        let mut count_to_value: [u32; 5] = [0, 0, 0, 0, 0];
-        let mut suit_value_sets: [u32; 4] = [0, 0, 0, 0];
-        let mut value_set: u32 = 0;
-
-        for c in self.cards() {
-            let v = c.value as u8;
-            let s = c.suit as u8;
-            value_set |= 1 << v;
-            value_to_count[v as usize] += 1;
-            suit_value_sets[s as usize] |= 1 << v;
-        }
-
-        // Now rotate the value to count map.
-        for (value, &count) in value_to_count.iter().enumerate() {
-            count_to_value[count as usize] |= 1 << value;
-        }
+        rank_cards(self.cards(), WHEEL)
+    }
 
-        // Find out if there's a flush
-        let flush: Option<usize> = find_flush(&suit_value_sets);
-
-        // If this is a flush then it could be a straight flush
-        // or a flush. So check only once.
-        if let Some(flush_idx) = flush {
-            // If we can find a straight in the flush then it's a straight flush
-            if let Some(rank) = rank_straight(suit_value_sets[flush_idx]) {
-                Rank::StraightFlush(rank)
-            } else {
-                // Else it's just a normal flush
-                let rank = keep_n(suit_value_sets[flush_idx], 5);
-                Rank::Flush(rank)
-            }
-        } else if count_to_value[4] != 0 {
-            // Four of a kind.
-            let high = keep_highest(value_set ^ count_to_value[4]);
-            Rank::FourOfAKind((count_to_value[4] << 13) | high)
-        } else if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
-            // There are two sets. So the best we can make is a full house.
-            let set = keep_highest(count_to_value[3]);
-            let pair = count_to_value[3] ^ set;
-            Rank::FullHouse((set << 13) | pair)
-        } else if count_to_value[3] != 0 && count_to_value[2] != 0 {
-            // there is a pair and a set.
-            let set = count_to_value[3];
-            let pair = keep_highest(count_to_value[2]);
-            Rank::FullHouse((set << 13) | pair)
-        } else if let Some(s_rank) = rank_straight(value_set) {
-            // If there's a straight return it now.
-            Rank::Straight(s_rank)
-        } else if count_to_value[3] != 0 {
-            // if there is a set then we need to keep 2 cards that
-            // aren't in the set.
-            let low = keep_n(value_set ^ count_to_value[3], 2);
-            Rank::ThreeOfAKind((count_to_value[3] << 13) | low)
-        } else if count_to_value[2].count_ones() >= 2 {
-            // Two pair
-            //
-            // That can be because we have 3 pairs and a high card.
-            // Or we could have two pair and two high cards.
-            let pairs = keep_n(count_to_value[2], 2);
-            let low = keep_highest(value_set ^ pairs);
-            Rank::TwoPair((pairs << 13) | low)
-        } else if count_to_value[2] == 0 {
-            // This means that there's no pair
-            // no sets, no straights, no flushes, so only a
-            // high card.
-            Rank::HighCard(keep_n(value_set, 5))
-        } else {
-            // Otherwise there's only one pair.
-            let pair = count_to_value[2];
-            // Keep the highest three cards not in the pair.
-            let low = keep_n(value_set ^ count_to_value[2], 3);
-            Rank::OnePair((pair << 13) | low)
-        }
+    /// Rank the cards to find the best 5 card short-deck hand.
+    /// The ace can also complete a six to nine straight (A-6-7-8-9).
+    /// Use `Rank::cmp_short_deck` to compare the results, in short deck
+    /// a flush beats a full house.
+    ///
+    /// # Examples
+    /// ```
+    /// use table::poker::core::{Hand, Rank, Rankable};
+    ///
+    /// let hand = Hand::new_from_str("As6d7c8h9sKd").unwrap();
+    /// assert_eq!(Rank::Straight(0), hand.rank_short_deck());
+    /// ```
+    fn rank_short_deck(&self) -> Rank {
+        rank_cards(self.cards(), SHORT_DECK_WHEEL)
     }
 
     /// Rank this hand. It doesn't do any caching so it's left up to the user
@@ -238,7 +299,7 @@ pub trait Rankable {
                 // Need to check for all of them.
                 let suit_count = suit_set.count_ones();
                 let is_flush = suit_count == 1;
-                match (rank_straight(value_set), is_flush) {
+                match (rank_straight(value_set, WHEEL), is_flush) {
                     // This is the most likely outcome.
                     // Not a flush and not a straight.
                     (None, false) => Rank::HighCard(value_set),
@@ -300,6 +361,42 @@ impl Rankable for Vec<Card> {
     }
 }
 
+/// Rank an Omaha hand. The best hand has to use exactly two of the
+/// hole cards and exactly three of the community cards.
+///
+/// If fewer than three community cards have been dealt the best two
+/// hole cards are ranked together with whatever is on the board.
+///
+/// # Examples
+/// ```
+/// use table::poker::core::{rank_omaha, Hand, Rank};
+///
+/// // Four spades in hand and one on the board is not a flush in Omaha.
+/// let hole = Hand::new_from_str("AsKsQsJs").unwrap();
+/// let board = Hand::new_from_str("2s7d8c9hTd").unwrap();
+/// let rank = rank_omaha(&hole[..], &board[..]);
+/// assert!(rank < Rank::Flush(0));
+/// ```
+pub fn rank_omaha(hole_cards: &[Card], community_cards: &[Card]) -> Rank {
+    let mut best: Option<Rank> = None;
+    for hole in CardIter::new(hole_cards, 2) {
+        if community_cards.len() < 3 {
+            let mut cards = hole.clone();
+            cards.extend_from_slice(community_cards);
+            let rank = cards.rank();
+            best = Some(best.map_or(rank, |b| b.max(rank)));
+            continue;
+        }
+        for board in CardIter::new(community_cards, 3) {
+            let mut cards = hole.clone();
+            cards.extend(board);
+            let rank = cards.rank_five();
+            best = Some(best.map_or(rank, |b| b.max(rank)));
+        }
+    }
+    best.unwrap_or(Rank::HighCard(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -499,4 +596,62 @@ mod tests {
         let low_rank = 1 << Value::King as u32;
         assert_eq!(Rank::TwoPair(pair_rank | low_rank), h.rank());
     }
+
+    #[test]
+    fn test_omaha_must_use_two_hole_cards() {
+        // Four spades in hand and a single spade on the board.
+        let hole = Hand::new_from_str("AsKsQsJs").unwrap();
+        let board = Hand::new_from_str("2s7d8c9hTd").unwrap();
+        let rank = rank_omaha(&hole[..], &board[..]);
+        // K-Q with 8-9-T makes the best straight (queen high).
+        assert_eq!(Rank::Straight(Value::Queen as u32 - 3), rank);
+    }
+
+    #[test]
+    fn test_omaha_must_use_three_board_cards() {
+        // Quads on the board can't be played, the best is nines full of aces.
+        let hole = Hand::new_from_str("AhAd2c3c").unwrap();
+        let board = Hand::new_from_str("9s9h9d9cTd").unwrap();
+        let rank = rank_omaha(&hole[..], &board[..]);
+        assert!(matches!(rank, Rank::FullHouse(_)));
+    }
+
+    #[test]
+    fn test_omaha_five_card_hand() {
+        let hole = Hand::new_from_str("AhAd7c8c2s").unwrap();
+        let board = Hand::new_from_str("AsKcQc3c4d").unwrap();
+        let rank = rank_omaha(&hole[..], &board[..]);
+        // 7c-8c with Kc-Qc-3c makes a flush which beats the set of aces.
+        assert!(matches!(rank, Rank::Flush(_)));
+    }
+
+    #[test]
+    fn test_short_deck_wheel() {
+        let hand = Hand::new_from_str("As6d7c8h9s").unwrap();
+        assert_eq!(Rank::Straight(0), hand.rank_short_deck());
+        // A-6-7-8-9 isn't a straight with the full deck.
+        assert!(matches!(hand.rank(), Rank::HighCard(_)));
+    }
+
+    #[test]
+    fn test_short_deck_wheel_is_lowest_straight() {
+        let wheel = Hand::new_from_str("As6d7c8h9s").unwrap();
+        let six_to_ten = Hand::new_from_str("6s7d8c9hTs").unwrap();
+        assert!(wheel.rank_short_deck() < six_to_ten.rank_short_deck());
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house() {
+        let flush = Hand::new_from_str("6h8hTh9hAhKsKd")
+            .unwrap()
+            .rank_short_deck();
+        let full_house = Hand::new_from_str("KhKcKs7d7cAsQd")
+            .unwrap()
+            .rank_short_deck();
+        assert!(matches!(flush, Rank::Flush(_)));
+        assert!(matches!(full_house, Rank::FullHouse(_)));
+        assert!(flush.cmp_short_deck(&full_house).is_gt());
+        assert!(full_house.cmp_short_deck(&flush).is_lt());
+        assert!(flush.cmp_short_deck(&Rank::FourOfAKind(0)).is_lt());
+    }
 }
@@ -0,0 +1,956 @@
+//! Converts the action logs of a hand into hand history files that
+//! tracking software can import, either in the Open Hand History
+//! JSON format or as PokerStars text.
+
+use std::{cmp::Reverse, collections::HashMap, fmt::Write};
+
+use candid::{CandidType, Principal};
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+
+use crate::poker::core::Card;
+
+use super::{
+    table_functions::{
+        action_log::{ActionLog, ActionType, HandPlayer},
+        types::DealStage,
+    },
+    types::GameType,
+};
+
+const SITE_NAME: &str = "ZkPoker";
+const OPEN_HAND_HISTORY_VERSION: &str = "1.4.6";
+
+/// The formats a hand history can be exported in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub enum HandHistoryFormat {
+    /// The Open Hand History JSON format.
+    OpenHandHistory,
+    /// The PokerStars text format.
+    PokerStars,
+}
+
+/// The logs of a single hand, starting with the
+/// [`ActionType::HandStarted`] log.
+#[derive(Debug, Clone)]
+pub struct HandHistory {
+    pub table_id: Principal,
+    pub logs: Vec<ActionLog>,
+}
+
+/// Groups the logs of a table by hand. Logs written
+/// before the first hand started are skipped.
+pub fn group_logs_by_hand(table_id: Principal, logs: &[ActionLog]) -> Vec<HandHistory> {
+    let mut hands: Vec<HandHistory> = Vec::new();
+    for log in logs {
+        if matches!(log.action_type, ActionType::HandStarted { .. }) {
+            hands.push(HandHistory {
+                table_id,
+                logs: Vec::new(),
+            });
+        }
+        if let Some(hand) = hands.last_mut() {
+            hand.logs.push(log.clone());
+        }
+    }
+    hands
+}
+
+/// Finds a hand in the logs of a table and exports it.
+///
+/// # Errors
+///
+/// - [`GameError::HandNotFound`] if there are no logs for the hand
+/// - [`GameError::Other`] if the hand can't be exported
+pub fn export_hand(
+    table_id: Principal,
+    logs: &[ActionLog],
+    hand_id: u64,
+    format: HandHistoryFormat,
+) -> Result<String, TracedError<GameError>> {
+    group_logs_by_hand(table_id, logs)
+        .into_iter()
+        .find(|hand| hand.hand_id() == Some(hand_id))
+        .ok_or_else(|| trace_err!(TracedError::new(GameError::HandNotFound)))?
+        .export(format)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// The community cards dealt on the street.
+    fn board_range(&self) -> std::ops::Range<usize> {
+        match self {
+            Street::Preflop => 0..0,
+            Street::Flop => 0..3,
+            Street::Turn => 3..4,
+            Street::River => 4..5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandAction {
+    Ante,
+    DeadBlind,
+    SmallBlind,
+    BigBlind,
+    Straddle,
+    Fold,
+    Check,
+    Call,
+    Bet,
+    /// A raise by `by` over the highest bet, to `to` in total on the street.
+    Raise {
+        by: u64,
+        to: u64,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct LoggedAction {
+    user_principal: Principal,
+    action: HandAction,
+    /// The chips the player put in with the action.
+    amount: u64,
+    is_all_in: bool,
+}
+
+#[derive(Debug, Clone)]
+struct StreetActions {
+    street: Street,
+    actions: Vec<LoggedAction>,
+}
+
+/// A hand rebuilt from its logs.
+#[derive(Debug, Clone)]
+struct ParsedHand {
+    hand_id: u64,
+    timestamp: u64,
+    table_name: String,
+    game_type: GameType,
+    is_hi_lo: bool,
+    max_seats: u8,
+    small_blind: u64,
+    big_blind: u64,
+    dealer_seat: u8,
+    players: Vec<HandPlayer>,
+    streets: Vec<StreetActions>,
+    board: Vec<Card>,
+    extra_boards: Vec<Vec<Card>>,
+    shown_cards: Vec<(Principal, Vec<Card>)>,
+    winnings: Vec<(Principal, u64)>,
+    /// Bets no other player called, returned to the player who made them.
+    uncalled_bets: Vec<(Principal, u64)>,
+    total_pot: u64,
+    rake: u64,
+}
+
+impl HandHistory {
+    /// The hand number, the table's `round_ticker` when it was dealt.
+    pub fn hand_id(&self) -> Option<u64> {
+        self.logs.iter().find_map(|log| match log.action_type {
+            ActionType::HandStarted { hand_id, .. } => Some(hand_id),
+            _ => None,
+        })
+    }
+
+    /// Exports the hand in the given format.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the hand didn't start or finish in the logs
+    pub fn export(&self, format: HandHistoryFormat) -> Result<String, TracedError<GameError>> {
+        match format {
+            HandHistoryFormat::OpenHandHistory => self.to_open_hand_history(),
+            HandHistoryFormat::PokerStars => self.to_poker_stars(),
+        }
+    }
+
+    /// Exports the hand in the Open Hand History JSON format.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the hand didn't start or finish in the logs
+    pub fn to_open_hand_history(&self) -> Result<String, TracedError<GameError>> {
+        let hand = self
+            .parse()
+            .map_err(|e| trace_err!(e, "Failed to parse hand logs."))?;
+
+        let player_id = |user_principal: &Principal| {
+            hand.players
+                .iter()
+                .find(|player| player.user_principal == *user_principal)
+                .map_or(0, |player| player.seat as u32 + 1)
+        };
+
+        let mut action_number = 0;
+        let mut rounds = Vec::new();
+        for (id, street) in hand.streets.iter().enumerate() {
+            let mut actions = Vec::new();
+            for action in &street.actions {
+                action_number += 1;
+                actions.push(OhhAction {
+                    action_number,
+                    player_id: player_id(&action.user_principal),
+                    action: match action.action {
+                        HandAction::Ante => "Post Ante",
+                        HandAction::DeadBlind => "Post Dead",
+                        HandAction::SmallBlind => "Post SB",
+                        HandAction::BigBlind => "Post BB",
+                        HandAction::Straddle => "Straddle",
+                        HandAction::Fold => "Fold",
+                        HandAction::Check => "Check",
+                        HandAction::Call => "Call",
+                        HandAction::Bet => "Bet",
+                        HandAction::Raise { .. } => "Raise",
+                    }
+                    .to_string(),
+                    amount: to_units(action.amount),
+                    is_allin: action.is_all_in,
+                    cards: None,
+                });
+            }
+            rounds.push(OhhRound {
+                id: id as u32,
+                street: format!("{:?}", street.street),
+                cards: card_strings(&hand.board[street.street.board_range()]),
+                actions,
+            });
+        }
+
+        if !hand.shown_cards.is_empty() {
+            let actions = hand
+                .shown_cards
+                .iter()
+                .map(|(user_principal, cards)| {
+                    action_number += 1;
+                    OhhAction {
+                        action_number,
+                        player_id: player_id(user_principal),
+                        action: "Shows Cards".to_string(),
+                        amount: 0.0,
+                        is_allin: false,
+                        cards: Some(card_strings(cards)),
+                    }
+                })
+                .collect();
+            rounds.push(OhhRound {
+                id: rounds.len() as u32,
+                street: "Showdown".to_string(),
+                cards: Vec::new(),
+                actions,
+            });
+        }
+
+        let ohh = OhhHand {
+            spec_version: OPEN_HAND_HISTORY_VERSION.to_string(),
+            site_name: SITE_NAME.to_string(),
+            network_name: SITE_NAME.to_string(),
+            internal_version: self.table_id.to_text(),
+            tournament: false,
+            game_number: hand.hand_id.to_string(),
+            start_date_utc: format_iso_date(hand.timestamp),
+            table_name: hand.table_name.clone(),
+            table_size: hand.max_seats,
+            game_type: if !hand.game_type.is_omaha() {
+                "Holdem"
+            } else if hand.is_hi_lo {
+                "OmahaHiLo"
+            } else {
+                "Omaha"
+            }
+            .to_string(),
+            bet_limit: OhhBetLimit {
+                bet_type: if hand.game_type.is_pot_limit() {
+                    "PL"
+                } else if hand.game_type.is_limit() {
+                    "FL"
+                } else {
+                    "NL"
+                }
+                .to_string(),
+                bet_cap: 0.0,
+            },
+            dealer_seat: hand.dealer_seat as u32 + 1,
+            small_blind_amount: to_units(hand.small_blind),
+            big_blind_amount: to_units(hand.big_blind),
+            ante_amount: to_units(
+                hand.streets
+                    .iter()
+                    .flat_map(|street| street.actions.iter())
+                    .find(|action| action.action == HandAction::Ante)
+                    .map_or(0, |action| action.amount),
+            ),
+            players: hand
+                .players
+                .iter()
+                .map(|player| OhhPlayer {
+                    id: player.seat as u32 + 1,
+                    seat: player.seat as u32 + 1,
+                    name: player.user_principal.to_text(),
+                    display: player.user_name.clone(),
+                    starting_stack: to_units(player.stack),
+                    is_sitting_out: player.is_sitting_out,
+                })
+                .collect(),
+            rounds,
+            pots: vec![OhhPot {
+                number: 0,
+                amount: to_units(hand.total_pot),
+                rake: to_units(hand.rake),
+                player_wins: hand
+                    .winnings
+                    .iter()
+                    .map(|(user_principal, amount)| OhhPlayerWin {
+                        player_id: player_id(user_principal),
+                        win_amount: to_units(hand.collected(user_principal, *amount)),
+                        contributed_rake: 0.0,
+                    })
+                    .collect(),
+            }],
+        };
+
+        serde_json::to_string_pretty(&OpenHandHistory { ohh }).map_err(|e| {
+            trace_err!(TracedError::new(GameError::Other(format!(
+                "Failed to serialize hand history: {}",
+                e
+            ))))
+        })
+    }
+
+    /// Exports the hand as PokerStars text.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the hand didn't start or finish in the logs
+    pub fn to_poker_stars(&self) -> Result<String, TracedError<GameError>> {
+        let hand = self
+            .parse()
+            .map_err(|e| trace_err!(e, "Failed to parse hand logs."))?;
+        let name = |user_principal: &Principal| {
+            hand.players
+                .iter()
+                .find(|player| player.user_principal == *user_principal)
+                .map_or_else(
+                    || user_principal.to_text(),
+                    |player| player.user_name.clone(),
+                )
+        };
+
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "PokerStars Hand #{}: {} ({}/{}) - {}",
+            hand.hand_id,
+            poker_stars_game_name(&hand.game_type, hand.is_hi_lo),
+            format_amount(hand.small_blind),
+            format_amount(hand.big_blind),
+            format_poker_stars_date(hand.timestamp)
+        );
+        let _ = writeln!(
+            text,
+            "Table '{}' {}-max Seat #{} is the button",
+            hand.table_name,
+            hand.max_seats,
+            hand.dealer_seat as u32 + 1
+        );
+        for player in &hand.players {
+            let _ = writeln!(
+                text,
+                "Seat {}: {} ({} in chips){}",
+                player.seat as u32 + 1,
+                player.user_name,
+                format_amount(player.stack),
+                if player.is_sitting_out {
+                    " is sitting out"
+                } else {
+                    ""
+                }
+            );
+        }
+
+        for street in &hand.streets {
+            match street.street {
+                Street::Preflop => {}
+                Street::Flop => {
+                    let _ = writeln!(text, "*** FLOP *** [{}]", join_cards(&hand.board[0..3]));
+                }
+                Street::Turn => {
+                    let _ = writeln!(
+                        text,
+                        "*** TURN *** [{}] [{}]",
+                        join_cards(&hand.board[0..3]),
+                        join_cards(&hand.board[3..4])
+                    );
+                }
+                Street::River => {
+                    let _ = writeln!(
+                        text,
+                        "*** RIVER *** [{}] [{}]",
+                        join_cards(&hand.board[0..4]),
+                        join_cards(&hand.board[4..5])
+                    );
+                }
+            }
+            let mut hole_cards_written = street.street != Street::Preflop;
+            for action in &street.actions {
+                let is_forced = matches!(
+                    action.action,
+                    HandAction::Ante
+                        | HandAction::DeadBlind
+                        | HandAction::SmallBlind
+                        | HandAction::BigBlind
+                        | HandAction::Straddle
+                );
+                if !is_forced && !hole_cards_written {
+                    let _ = writeln!(text, "*** HOLE CARDS ***");
+                    hole_cards_written = true;
+                }
+                let all_in = if action.is_all_in {
+                    " and is all-in"
+                } else {
+                    ""
+                };
+                let description = match action.action {
+                    HandAction::Ante => format!("posts the ante {}", format_amount(action.amount)),
+                    HandAction::DeadBlind => {
+                        format!("posts dead blind {}", format_amount(action.amount))
+                    }
+                    HandAction::SmallBlind => {
+                        format!("posts small blind {}", format_amount(action.amount))
+                    }
+                    HandAction::BigBlind => {
+                        format!("posts big blind {}", format_amount(action.amount))
+                    }
+                    HandAction::Straddle => {
+                        format!("posts straddle {}", format_amount(action.amount))
+                    }
+                    HandAction::Fold => "folds".to_string(),
+                    HandAction::Check => "checks".to_string(),
+                    HandAction::Call => format!("calls {}", format_amount(action.amount)),
+                    HandAction::Bet => format!("bets {}", format_amount(action.amount)),
+                    HandAction::Raise { by, to } => {
+                        format!("raises {} to {}", format_amount(by), format_amount(to))
+                    }
+                };
+                let _ = writeln!(
+                    text,
+                    "{}: {}{}",
+                    name(&action.user_principal),
+                    description,
+                    all_in
+                );
+            }
+            if !hole_cards_written {
+                let _ = writeln!(text, "*** HOLE CARDS ***");
+            }
+        }
+
+        for (user_principal, amount) in &hand.uncalled_bets {
+            let _ = writeln!(
+                text,
+                "Uncalled bet ({}) returned to {}",
+                format_amount(*amount),
+                name(user_principal)
+            );
+        }
+
+        if !hand.shown_cards.is_empty() {
+            let _ = writeln!(text, "*** SHOW DOWN ***");
+            for (user_principal, cards) in &hand.shown_cards {
+                let _ = writeln!(
+                    text,
+                    "{}: shows [{}]",
+                    name(user_principal),
+                    join_cards(cards)
+                );
+            }
+        }
+        for (user_principal, amount) in &hand.winnings {
+            let collected = hand.collected(user_principal, *amount);
+            if collected > 0 {
+                let _ = writeln!(
+                    text,
+                    "{} collected {} from pot",
+                    name(user_principal),
+                    format_amount(collected)
+                );
+            }
+        }
+
+        let _ = writeln!(text, "*** SUMMARY ***");
+        let _ = writeln!(
+            text,
+            "Total pot {} | Rake {}",
+            format_amount(hand.total_pot),
+            format_amount(hand.rake)
+        );
+        if !hand.board.is_empty() {
+            if hand.extra_boards.is_empty() {
+                let _ = writeln!(text, "Board [{}]", join_cards(&hand.board));
+            } else {
+                let _ = writeln!(text, "FIRST Board [{}]", join_cards(&hand.board));
+                for (run, board) in hand.extra_boards.iter().enumerate() {
+                    let _ = writeln!(text, "{} Board [{}]", ordinal(run + 2), join_cards(board));
+                }
+            }
+        }
+        for player in &hand.players {
+            let _ = writeln!(
+                text,
+                "Seat {}: {}{}",
+                player.seat as u32 + 1,
+                player.user_name,
+                hand.summary(player)
+            );
+        }
+
+        Ok(text)
+    }
+
+    /// Rebuilds the hand from its logs, working out how many chips
+    /// each action put in from the stacks at the start of the hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the hand didn't start or finish in the logs
+    fn parse(&self) -> Result<ParsedHand, TracedError<GameError>> {
+        let mut hand = self
+            .logs
+            .iter()
+            .find_map(|log| match &log.action_type {
+                ActionType::HandStarted {
+                    hand_id,
+                    table_name,
+                    game_type,
+                    is_hi_lo,
+                    max_seats,
+                    small_blind,
+                    big_blind,
+                    dealer_seat,
+                    players,
+                    ..
+                } => Some(ParsedHand {
+                    hand_id: *hand_id,
+                    timestamp: log.timestamp,
+                    table_name: table_name.clone(),
+                    game_type: game_type.clone(),
+                    is_hi_lo: *is_hi_lo,
+                    max_seats: *max_seats,
+                    small_blind: *small_blind,
+                    big_blind: *big_blind,
+                    dealer_seat: *dealer_seat,
+                    players: players.clone(),
+                    streets: vec![StreetActions {
+                        street: Street::Preflop,
+                        actions: Vec::new(),
+                    }],
+                    board: Vec::new(),
+                    extra_boards: Vec::new(),
+                    shown_cards: Vec::new(),
+                    winnings: Vec::new(),
+                    uncalled_bets: Vec::new(),
+                    total_pot: 0,
+                    rake: 0,
+                }),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                trace_err!(TracedError::new(GameError::Other(
+                    "The hand has no start log".to_string()
+                )))
+            })?;
+
+        let finished = self.logs.iter().find_map(|log| match &log.action_type {
+            ActionType::HandFinished {
+                board,
+                shown_cards,
+                winnings,
+                rake,
+            } => Some((board, shown_cards, winnings, *rake)),
+            _ => None,
+        });
+        let (board, shown_cards, winnings, rake) = finished.ok_or_else(|| {
+            trace_err!(TracedError::new(GameError::Other(
+                "The hand has not finished".to_string()
+            )))
+        })?;
+        hand.board = board.clone();
+        hand.shown_cards = shown_cards.clone();
+        hand.winnings = winnings.clone();
+        hand.rake = rake;
+
+        let mut stacks: HashMap<Principal, u64> = hand
+            .players
+            .iter()
+            .map(|player| (player.user_principal, player.stack))
+            .collect();
+        let mut street_bets: HashMap<Principal, u64> = HashMap::new();
+        let mut total_bets: HashMap<Principal, u64> = HashMap::new();
+        let mut highest_bet: u64 = 0;
+
+        for log in &self.logs {
+            let street = match log.action_type {
+                ActionType::Stage {
+                    stage: DealStage::Flop,
+                } => Some(Street::Flop),
+                ActionType::Stage {
+                    stage: DealStage::Turn,
+                } => Some(Street::Turn),
+                ActionType::Stage {
+                    stage: DealStage::River,
+                } => Some(Street::River),
+                _ => None,
+            };
+            // The showdown logs the stage it was reached at, so a
+            // stage only starts a street if its cards were dealt.
+            if let Some(street) = street {
+                if hand.board.len() >= street.board_range().end {
+                    hand.close_street(&street_bets);
+                    hand.streets.push(StreetActions {
+                        street,
+                        actions: Vec::new(),
+                    });
+                    street_bets.clear();
+                    highest_bet = 0;
+                }
+                continue;
+            }
+            if let ActionType::BoardRun { cards, .. } = &log.action_type {
+                hand.extra_boards.push(cards.clone());
+                continue;
+            }
+
+            let Some(user_principal) = log.user_principal else {
+                continue;
+            };
+            let stack = stacks.get(&user_principal).copied().unwrap_or(0);
+            let street_bet = street_bets.get(&user_principal).copied().unwrap_or(0);
+            let (action, amount, is_all_in) = match log.action_type {
+                ActionType::Ante { amount } => (HandAction::Ante, amount.min(stack), false),
+                ActionType::DeadBlind { amount } => {
+                    (HandAction::DeadBlind, amount.min(stack), false)
+                }
+                ActionType::SmallBlind => {
+                    (HandAction::SmallBlind, hand.small_blind.min(stack), false)
+                }
+                ActionType::BigBlind => (HandAction::BigBlind, hand.big_blind.min(stack), false),
+                ActionType::Straddle { amount } => (HandAction::Straddle, amount.min(stack), false),
+                ActionType::Fold => (HandAction::Fold, 0, false),
+                ActionType::Check => (HandAction::Check, 0, false),
+                ActionType::Call => (
+                    HandAction::Call,
+                    highest_bet.saturating_sub(street_bet).min(stack),
+                    false,
+                ),
+                ActionType::Bet { amount } | ActionType::Raise { amount } => {
+                    let added = amount.saturating_sub(street_bet).min(stack);
+                    (
+                        betting_action(highest_bet, street_bet + added),
+                        added,
+                        added == stack,
+                    )
+                }
+                // Going all in is sometimes logged twice.
+                ActionType::AllIn { .. } if stack == 0 => continue,
+                ActionType::AllIn { .. } => {
+                    let action = if street_bet + stack > highest_bet {
+                        betting_action(highest_bet, street_bet + stack)
+                    } else {
+                        HandAction::Call
+                    };
+                    (action, stack, true)
+                }
+                _ => continue,
+            };
+
+            stacks.insert(user_principal, stack - amount);
+            *total_bets.entry(user_principal).or_insert(0) += amount;
+            if !matches!(action, HandAction::Ante | HandAction::DeadBlind) {
+                street_bets.insert(user_principal, street_bet + amount);
+                highest_bet = highest_bet.max(street_bet + amount);
+            }
+            if let Some(street) = hand.streets.last_mut() {
+                street.actions.push(LoggedAction {
+                    user_principal,
+                    action,
+                    amount,
+                    is_all_in,
+                });
+            }
+        }
+        hand.close_street(&street_bets);
+
+        let returned: u64 = hand.uncalled_bets.iter().map(|(_, amount)| amount).sum();
+        hand.total_pot = total_bets.values().sum::<u64>().saturating_sub(returned);
+        Ok(hand)
+    }
+}
+
+impl ParsedHand {
+    /// Records the part of the biggest bet of the street that nobody called.
+    fn close_street(&mut self, street_bets: &HashMap<Principal, u64>) {
+        let mut bets = street_bets.iter().collect::<Vec<_>>();
+        bets.sort_by_key(|(_, amount)| Reverse(**amount));
+        if let Some((user_principal, highest)) = bets.first() {
+            let called = bets.get(1).map_or(0, |(_, amount)| **amount);
+            if **highest > called {
+                self.uncalled_bets
+                    .push((**user_principal, **highest - called));
+            }
+        }
+    }
+
+    /// The chips a player won from the pot, leaving out their uncalled bets.
+    fn collected(&self, user_principal: &Principal, amount: u64) -> u64 {
+        let returned: u64 = self
+            .uncalled_bets
+            .iter()
+            .filter(|(principal, _)| principal == user_principal)
+            .map(|(_, amount)| amount)
+            .sum();
+        amount.saturating_sub(returned)
+    }
+
+    /// The end of a player's line in the summary of a PokerStars hand history.
+    fn summary(&self, player: &HandPlayer) -> String {
+        let mut summary = String::new();
+        if player.seat == self.dealer_seat {
+            summary.push_str(" (button)");
+        }
+
+        let folded_on = self.streets.iter().find_map(|street| {
+            street
+                .actions
+                .iter()
+                .any(|action| {
+                    action.user_principal == player.user_principal
+                        && action.action == HandAction::Fold
+                })
+                .then_some(street.street)
+        });
+        let won = self
+            .winnings
+            .iter()
+            .find(|(principal, _)| *principal == player.user_principal)
+            .map_or(0, |(_, amount)| {
+                self.collected(&player.user_principal, *amount)
+            });
+        let shown = self
+            .shown_cards
+            .iter()
+            .find(|(principal, _)| *principal == player.user_principal);
+
+        match (folded_on, shown) {
+            (Some(Street::Preflop), _) => summary.push_str(" folded before Flop"),
+            (Some(street), _) => {
+                let _ = write!(summary, " folded on the {:?}", street);
+            }
+            (None, Some((_, cards))) => {
+                let _ = write!(summary, " showed [{}]", join_cards(cards));
+                if won > 0 {
+                    let _ = write!(summary, " and won ({})", format_amount(won));
+                } else {
+                    summary.push_str(" and lost");
+                }
+            }
+            (None, None) if won > 0 => {
+                let _ = write!(summary, " collected ({})", format_amount(won));
+            }
+            (None, None) => {}
+        }
+        summary
+    }
+}
+
+/// Whether putting the player's bet on the street up to `to` is a bet or a raise.
+fn betting_action(highest_bet: u64, to: u64) -> HandAction {
+    if highest_bet == 0 {
+        HandAction::Bet
+    } else {
+        HandAction::Raise {
+            by: to.saturating_sub(highest_bet),
+            to,
+        }
+    }
+}
+
+fn poker_stars_game_name(game_type: &GameType, is_hi_lo: bool) -> String {
+    let game = if !game_type.is_omaha() {
+        "Hold'em"
+    } else if is_hi_lo {
+        "Omaha Hi/Lo"
+    } else {
+        "Omaha"
+    };
+    let limit = if game_type.is_pot_limit() {
+        "Pot Limit"
+    } else if game_type.is_limit() {
+        "Limit"
+    } else {
+        "No Limit"
+    };
+    format!("{} {}", game, limit)
+}
+
+/// Converts an amount in e8s to whole units.
+fn to_units(amount: u64) -> f64 {
+    amount as f64 / 1e8
+}
+
+/// Formats an amount in e8s with at least two decimals.
+fn format_amount(amount: u64) -> String {
+    let whole = amount / 100_000_000;
+    let fraction = format!("{:08}", amount % 100_000_000);
+    let fraction = fraction.trim_end_matches('0');
+    format!("{}.{:0<2}", whole, fraction)
+}
+
+fn card_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn join_cards(cards: &[Card]) -> String {
+    card_strings(cards).join(" ")
+}
+
+fn ordinal(run: usize) -> &'static str {
+    match run {
+        2 => "SECOND",
+        3 => "THIRD",
+        4 => "FOURTH",
+        _ => "NEXT",
+    }
+}
+
+/// Splits a timestamp in nanoseconds into the UTC date and time.
+fn utc_date_time(timestamp: u64) -> (i64, u32, u32, u64, u64, u64) {
+    let seconds = timestamp / 1_000_000_000;
+    let days = (seconds / 86_400) as i64;
+    let seconds_of_day = seconds % 86_400;
+
+    // Converts days since the Unix epoch to a civil date.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = (if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+    )
+}
+
+fn format_iso_date(timestamp: u64) -> String {
+    let (year, month, day, hour, minute, second) = utc_date_time(timestamp);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, hour, minute, second
+    )
+}
+
+fn format_poker_stars_date(timestamp: u64) -> String {
+    let (year, month, day, hour, minute, second) = utc_date_time(timestamp);
+    format!(
+        "{:04}/{:02}/{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, hour, minute, second
+    )
+}
+
+#[derive(Serialize)]
+struct OpenHandHistory {
+    ohh: OhhHand,
+}
+
+#[derive(Serialize)]
+struct OhhHand {
+    spec_version: String,
+    site_name: String,
+    network_name: String,
+    internal_version: String,
+    tournament: bool,
+    game_number: String,
+    start_date_utc: String,
+    table_name: String,
+    table_size: u8,
+    game_type: String,
+    bet_limit: OhhBetLimit,
+    dealer_seat: u32,
+    small_blind_amount: f64,
+    big_blind_amount: f64,
+    ante_amount: f64,
+    players: Vec<OhhPlayer>,
+    rounds: Vec<OhhRound>,
+    pots: Vec<OhhPot>,
+}
+
+#[derive(Serialize)]
+struct OhhBetLimit {
+    bet_type: String,
+    bet_cap: f64,
+}
+
+#[derive(Serialize)]
+struct OhhPlayer {
+    id: u32,
+    seat: u32,
+    name: String,
+    display: String,
+    starting_stack: f64,
+    is_sitting_out: bool,
+}
+
+#[derive(Serialize)]
+struct OhhRound {
+    id: u32,
+    street: String,
+    cards: Vec<String>,
+    actions: Vec<OhhAction>,
+}
+
+#[derive(Serialize)]
+struct OhhAction {
+    action_number: u32,
+    player_id: u32,
+    action: String,
+    amount: f64,
+    is_allin: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cards: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct OhhPot {
+    number: u32,
+    amount: f64,
+    rake: f64,
+    player_wins: Vec<OhhPlayerWin>,
+}
+
+#[derive(Serialize)]
+struct OhhPlayerWin {
+    player_id: u32,
+    win_amount: f64,
+    contributed_rake: f64,
+}
@@ -1,3 +1,5 @@
+pub mod hand_history;
+pub mod replay;
 pub mod table_functions;
 pub mod types;
 pub mod users;
@@ -0,0 +1,385 @@
+//! Replays a logged hand through the [`Table`] methods to rebuild
+//! the state of the table at every point of the hand. Every log the
+//! replay produces is checked against the recorded one, so the chip
+//! movements and the winners have to match what was logged.
+//!
+//! Replays deal with a fresh table and are meant to be run off-chain,
+//! for dispute resolution, hand replayers and regression tests.
+
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use user::user::User;
+
+use super::{
+    hand_history::HandHistory,
+    table_functions::{
+        action_log::{ActionLog, ActionType, HandPlayer},
+        provably_fair::HandVerification,
+        table::{Table, TableConfig},
+        types::BetType,
+    },
+    types::PublicTable,
+};
+
+/// The input a player gave the table, worked out from a log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReplayAction {
+    Fold,
+    Check,
+    Call,
+    /// A bet or raise to `amount` in total on the street.
+    Raise(u64),
+}
+
+/// Steps through a logged hand one player action at a time.
+///
+/// The iterator yields the table after the cards are dealt
+/// and then after every action, until the hand is over or a
+/// replayed log doesn't match the recorded one.
+#[derive(Debug, Clone)]
+pub struct HandReplay {
+    table: Table,
+    /// The recorded logs up to and including [`ActionType::HandFinished`].
+    logs: Vec<ActionLog>,
+    players: Vec<HandPlayer>,
+    has_yielded_deal: bool,
+    has_failed: bool,
+}
+
+impl HandReplay {
+    /// Deals the hand again with the seed it was shuffled with.
+    ///
+    /// The logs don't record everything about the table, so the rest
+    /// of it is taken from `config`, like the antes and the rake.
+    ///
+    /// # Parameters
+    ///
+    /// - `config` - The config of the table the hand was played at.
+    /// - `hand` - The logs of the hand.
+    /// - `verification` - The revealed seed of the hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::HandNotFound`] if the logs don't start with a hand
+    /// - [`GameError::ReplayMismatch`] if the seed doesn't belong to the hand
+    ///   or the cards and blinds don't match the logs
+    pub fn new(
+        config: TableConfig,
+        hand: &HandHistory,
+        verification: &HandVerification,
+    ) -> Result<HandReplay, TracedError<GameError>> {
+        let Some(ActionType::HandStarted {
+            hand_id,
+            table_name,
+            game_type,
+            is_hi_lo,
+            max_seats,
+            small_blind,
+            big_blind,
+            dealer_seat,
+            players,
+            blind_seats,
+            missed_blinds,
+        }) = hand.logs.first().map(|log| log.action_type.clone())
+        else {
+            return Err(trace_err!(TracedError::new(GameError::HandNotFound)));
+        };
+
+        if verification.hand_id != hand_id || !verification.verify() {
+            return Err(mismatch(format!(
+                "The seed of hand {} can't be verified for hand {}",
+                verification.hand_id, hand_id
+            )));
+        }
+        let seed: [u8; 32] = verification.seed.clone().try_into().map_err(|_| {
+            trace_err!(TracedError::new(GameError::Other(
+                "Seed must be 32 bytes".to_string()
+            )))
+        })?;
+
+        let config = TableConfig {
+            name: table_name,
+            game_type,
+            seats: max_seats,
+            is_hi_lo: Some(is_hi_lo),
+            is_short_deck: Some(verification.is_short_deck),
+            ..config
+        };
+        let mut table = Table::new(hand.table_id, config, Vec::new());
+        table.small_blind = small_blind;
+        table.big_blind = big_blind;
+
+        // Players held out until the big blind reaches them are logged
+        // as sitting out, the dead button holds them out again.
+        let missed_blinds = missed_blinds.unwrap_or_default();
+        for player in &players {
+            let is_owing = missed_blinds
+                .iter()
+                .any(|(user_principal, _, _)| *user_principal == player.user_principal);
+            let user = User::new(
+                player.user_principal,
+                Principal::anonymous(),
+                player.user_name.clone(),
+                player.stack,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            table
+                .add_user(user, player.seat, player.is_sitting_out && !is_owing)
+                .map_err(|e| trace_err!(e, "Failed to seat player in replay."))?;
+        }
+        for (user_principal, owed, blind_choice) in missed_blinds {
+            let user_table_data = table
+                .get_user_table_data_mut(user_principal)
+                .map_err(|e| trace_err!(e, "Failed to restore missed blinds in replay."))?;
+            user_table_data.missed_blinds = Some(owed);
+            user_table_data.blind_choice = blind_choice;
+        }
+        table.blind_seats = blind_seats.map(|(small_blind_seat, big_blind_seat)| {
+            (small_blind_seat as usize, big_blind_seat as usize)
+        });
+        for log in &hand.logs {
+            if let (ActionType::Straddle { .. }, Some(user_principal)) =
+                (&log.action_type, log.user_principal)
+            {
+                table
+                    .set_straddle_next_hand(user_principal, true)
+                    .map_err(|e| trace_err!(e, "Failed to replay straddle."))?;
+            }
+        }
+        table.dealer_position = dealer_seat as usize;
+        table.round_ticker = hand_id.saturating_sub(1);
+
+        table
+            .start_betting_round_with_seed(seed)
+            .map_err(|e| trace_err!(e, "Failed to deal hand in replay."))?;
+
+        let logs = match hand
+            .logs
+            .iter()
+            .position(|log| matches!(log.action_type, ActionType::HandFinished { .. }))
+        {
+            Some(position) => hand.logs[..=position].to_vec(),
+            None => hand.logs.clone(),
+        };
+        let replay = HandReplay {
+            table,
+            logs,
+            players,
+            has_yielded_deal: false,
+            has_failed: false,
+        };
+        check_logs(&replay.table.action_logs, &replay.logs)?;
+        Ok(replay)
+    }
+
+    /// The table as it is at this point of the replay.
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Whether every recorded log has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.table.action_logs.len() >= self.logs.len()
+    }
+
+    /// Replays the next player action.
+    ///
+    /// # Returns
+    ///
+    /// The table after the action, or `None` once every log is replayed.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ReplayMismatch`] if the logs the action produces
+    ///   don't match the recorded ones or chips went missing
+    pub fn step(&mut self) -> Result<Option<PublicTable>, TracedError<GameError>> {
+        let index = self.table.action_logs.len();
+        let Some(log) = self.logs.get(index) else {
+            return Ok(None);
+        };
+        let user_principal = log.user_principal.ok_or_else(|| {
+            mismatch(format!(
+                "Log {} ({:?}) should be a player action",
+                index, log.action_type
+            ))
+        })?;
+
+        // Some logs could come from more than one input, like an all in
+        // which is a call or a raise. Only one of them reproduces the logs.
+        let mut result = Err(mismatch(format!(
+            "Log {} ({:?}) isn't a player action",
+            index, log.action_type
+        )));
+        for action in self.candidate_actions(index) {
+            let mut table = self.table.clone();
+            let replayed = replay_action(&mut table, user_principal, action, &self.logs)
+                .and_then(|_| check_logs(&table.action_logs, &self.logs));
+            result = replayed.map(|_| table);
+            if result.is_ok() {
+                break;
+            }
+        }
+        self.table = result?;
+        self.check_chips()?;
+        Ok(Some(PublicTable::from(&self.table)))
+    }
+
+    /// Replays the rest of the hand.
+    ///
+    /// # Returns
+    ///
+    /// The table once the hand is over.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ReplayMismatch`] if the hand didn't play out as logged
+    pub fn replay_to_end(mut self) -> Result<Table, TracedError<GameError>> {
+        while self.step()?.is_some() {}
+        Ok(self.table)
+    }
+
+    /// The inputs the log at `index` could have come from.
+    fn candidate_actions(&self, index: usize) -> Vec<ReplayAction> {
+        let log = &self.logs[index];
+        match log.action_type {
+            ActionType::Fold => vec![ReplayAction::Fold],
+            ActionType::Check => vec![ReplayAction::Check],
+            ActionType::Call => vec![ReplayAction::Call],
+            ActionType::Bet { amount } | ActionType::Raise { amount } => {
+                vec![ReplayAction::Raise(amount)]
+            }
+            ActionType::AllIn { amount } => {
+                let mut actions = vec![ReplayAction::Call, ReplayAction::Raise(amount)];
+                // An all in raise may be logged with the chips put in first
+                // and then with the total bet.
+                if let Some(ActionLog {
+                    action_type: ActionType::AllIn { amount: total },
+                    user_principal,
+                    ..
+                }) = self.logs.get(index + 1)
+                {
+                    if *user_principal == log.user_principal {
+                        actions.push(ReplayAction::Raise(*total));
+                    }
+                }
+                actions
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Checks that no chips were created or lost once the hand is over.
+    /// The chips the players started with are either back in front of
+    /// them or were taken as rake.
+    fn check_chips(&self) -> Result<(), TracedError<GameError>> {
+        let Some(ActionLog {
+            action_type: ActionType::HandFinished { rake, .. },
+            ..
+        }) = self.table.action_logs.last()
+        else {
+            return Ok(());
+        };
+        let started_with: u64 = self.players.iter().map(|player| player.stack).sum();
+        let balances: u64 = self
+            .players
+            .iter()
+            .filter_map(|player| self.table.users.get(&player.user_principal))
+            .map(|user| user.balance)
+            .sum();
+
+        if balances + rake != started_with {
+            return Err(mismatch(format!(
+                "The players started with {} chips but ended with {} and {} rake",
+                started_with, balances, rake
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for HandReplay {
+    type Item = Result<PublicTable, TracedError<GameError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.has_failed {
+            return None;
+        }
+        if !self.has_yielded_deal {
+            self.has_yielded_deal = true;
+            return Some(Ok(PublicTable::from(&self.table)));
+        }
+        let step = self.step().transpose();
+        self.has_failed = matches!(step, Some(Err(_)));
+        step
+    }
+}
+
+/// Gives the table the input a player gave it. A pending offer to run
+/// the board more than once is settled with the number of runs logged.
+fn replay_action(
+    table: &mut Table,
+    user_principal: Principal,
+    action: ReplayAction,
+    logs: &[ActionLog],
+) -> Result<(), TracedError<GameError>> {
+    match action {
+        ReplayAction::Fold => {
+            if table.is_players_turn(user_principal) {
+                table.user_fold(user_principal, false)
+            } else {
+                table.user_pre_fold(user_principal)
+            }
+        }
+        ReplayAction::Check => table.user_check(user_principal, false),
+        ReplayAction::Call => table.bet(user_principal, BetType::Called),
+        ReplayAction::Raise(amount) => table.bet(user_principal, BetType::Raised(amount)),
+    }
+    .map_err(|e| trace_err!(e, "Failed to replay player action."))?;
+
+    if let Some(offer) = table.board_runs_offer.clone() {
+        let runs = 1 + logs
+            .iter()
+            .filter(|log| matches!(log.action_type, ActionType::BoardRun { .. }))
+            .count() as u8;
+        for user_principal in offer.user_principals {
+            table
+                .set_board_runs(user_principal, runs)
+                .map_err(|e| trace_err!(e, "Failed to replay board runs."))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the logs the replay produced against the recorded ones.
+/// Timestamps are ignored.
+fn check_logs(
+    replayed: &[ActionLog],
+    recorded: &[ActionLog],
+) -> Result<(), TracedError<GameError>> {
+    if replayed.len() > recorded.len() {
+        return Err(mismatch(format!(
+            "The replay produced {} logs but only {} were recorded",
+            replayed.len(),
+            recorded.len()
+        )));
+    }
+    for (index, (replayed, recorded)) in replayed.iter().zip(recorded).enumerate() {
+        if replayed.user_principal != recorded.user_principal
+            || replayed.action_type != recorded.action_type
+        {
+            return Err(mismatch(format!(
+                "Log {} was replayed as {:?} but {:?} was recorded",
+                index, replayed.action_type, recorded.action_type
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn mismatch(reason: String) -> TracedError<GameError> {
+    trace_err!(TracedError::new(GameError::ReplayMismatch { reason }))
+}
@@ -1,9 +1,14 @@
+use std::collections::HashMap;
+
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
-use crate::poker::core::Card;
+use crate::poker::{core::Card, game::types::GameType};
 
-use super::{table::Table, types::DealStage};
+use super::{
+    table::Table,
+    types::{BlindChoice, DealStage, MissedBlinds, PlayerAction, SeatStatus},
+};
 
 /// All the different loggable actions a user can take.
 #[derive(Debug, Clone, Serialize, Deserialize, CandidType, Eq, PartialEq)]
@@ -27,12 +32,19 @@ pub enum ActionType {
     },
     PlayersHandsRankedMainPot {
         hands: Vec<(String, Vec<Card>, u64)>,
+        /// The low half of the pot on hi/lo tables.
+        low_hands: Option<Vec<(String, Vec<Card>, u64)>>,
     },
     PlayersHandsRankedSidePot {
         hands: Vec<(String, Vec<Card>, u64)>,
+        /// The low half of the pot on hi/lo tables.
+        low_hands: Option<Vec<(String, Vec<Card>, u64)>>,
     },
     BigBlind,
     SmallBlind,
+    Straddle {
+        amount: u64,
+    },
     Kicked {
         reason: String,
     },
@@ -40,6 +52,70 @@ pub enum ActionType {
         stage: DealStage,
     },
     SidePotCreated,
+    /// An extra board dealt when players agreed to run it more than once.
+    /// The first run is the regular community cards.
+    BoardRun {
+        run: u8,
+        cards: Vec<Card>,
+    },
+    Ante {
+        amount: u64,
+    },
+    /// A small blind a returning player owed, posted as dead money.
+    DeadBlind {
+        amount: u64,
+    },
+    /// The table and the players at the start of a hand,
+    /// so the hand can be exported without the table.
+    HandStarted {
+        hand_id: u64,
+        table_name: String,
+        game_type: GameType,
+        is_hi_lo: bool,
+        max_seats: u8,
+        small_blind: u64,
+        big_blind: u64,
+        dealer_seat: u8,
+        players: Vec<HandPlayer>,
+        /// The seats of the blinds of the last hand, which the
+        /// dead button places the blinds of this hand from.
+        blind_seats: Option<(u8, u8)>,
+        /// The blinds owed by the players who weren't sitting out
+        /// before the blinds were placed, and how they chose to settle them.
+        missed_blinds: Option<Vec<(Principal, MissedBlinds, Option<BlindChoice>)>>,
+    },
+    /// The board, the cards shown and what every player
+    /// won once the pots have been handed out.
+    HandFinished {
+        board: Vec<Card>,
+        shown_cards: Vec<(Principal, Vec<Card>)>,
+        winnings: Vec<(Principal, u64)>,
+        rake: u64,
+    },
+    /// The chips at the table after a hand, including the rake,
+    /// didn't add up to the chips at the table when it started.
+    ChipAuditFailed {
+        chips_before: u64,
+        chips_after: u64,
+    },
+    /// The share of the pot each player still in the hand wins on
+    /// average over the rest of the board, in basis points.
+    /// Logged once the players are all in and their hands are shown.
+    AllInEquity {
+        equities: Vec<(Principal, u16)>,
+    },
+}
+
+/// A player dealt into a hand.
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, Eq, PartialEq)]
+pub struct HandPlayer {
+    pub seat: u8,
+    pub user_principal: Principal,
+    pub user_name: String,
+    /// The player's chips before the blinds and antes.
+    pub stack: u64,
+    /// Sitting out players keep their seat but aren't dealt in.
+    pub is_sitting_out: bool,
 }
 
 /// A log of an action that a user has taken.
@@ -78,4 +154,102 @@ impl Table {
 
         self.action_logs.push(action_log);
     }
+
+    /// Logs the start of a hand with the stakes and the players'
+    /// stacks. Should be called before the blinds are posted.
+    ///
+    /// # Parameters
+    ///
+    /// - `blind_seats` - The seats of the blinds of the last hand.
+    /// - `missed_blinds` - The blinds the players owed before the
+    ///   blinds of this hand were placed.
+    pub fn log_hand_started(
+        &mut self,
+        blind_seats: Option<(usize, usize)>,
+        missed_blinds: Vec<(Principal, MissedBlinds, Option<BlindChoice>)>,
+    ) {
+        let players = self
+            .seats
+            .iter()
+            .enumerate()
+            .filter_map(|(seat, status)| match status {
+                SeatStatus::Occupied(principal) => {
+                    self.users.get(principal).map(|user| HandPlayer {
+                        seat: seat as u8,
+                        user_principal: *principal,
+                        user_name: user.user_name.clone(),
+                        stack: user.balance,
+                        is_sitting_out: self.user_table_data.get(principal).is_some_and(|data| {
+                            matches!(
+                                data.player_action,
+                                PlayerAction::SittingOut | PlayerAction::Joining
+                            )
+                        }),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.log_action(
+            None,
+            ActionType::HandStarted {
+                hand_id: self.round_ticker,
+                table_name: self.config.name.clone(),
+                game_type: self.config.game_type.clone(),
+                is_hi_lo: self.config.is_hi_lo.unwrap_or(false),
+                max_seats: self.config.seats,
+                small_blind: self.small_blind,
+                big_blind: self.big_blind,
+                dealer_seat: self.dealer_position as u8,
+                players,
+                blind_seats: blind_seats.map(|(small_blind_seat, big_blind_seat)| {
+                    (small_blind_seat as u8, big_blind_seat as u8)
+                }),
+                missed_blinds: Some(missed_blinds),
+            },
+        );
+    }
+
+    /// Logs the end of a hand. Hole cards are only shown when more
+    /// than one player was left in the hand.
+    ///
+    /// # Parameters
+    ///
+    /// - `winnings` - What each player won from the pots.
+    /// - `rake` - The rake taken from the pots.
+    pub fn log_hand_finished(&mut self, winnings: &HashMap<Principal, u64>, rake: u64) {
+        let shown_cards = if self.all_players_folded() {
+            Vec::new()
+        } else {
+            self.get_players_in_hand()
+                .into_iter()
+                .filter_map(|principal| {
+                    self.user_table_data
+                        .get(&principal)
+                        .map(|data| (principal, data.cards.clone()))
+                })
+                .collect()
+        };
+        let winnings = self
+            .seats
+            .iter()
+            .filter_map(|status| match status {
+                SeatStatus::Occupied(principal) => {
+                    winnings.get(principal).map(|amount| (*principal, *amount))
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.log_action(
+            None,
+            ActionType::HandFinished {
+                board: self.community_cards.clone(),
+                shown_cards,
+                winnings,
+                rake,
+            },
+        );
+    }
 }
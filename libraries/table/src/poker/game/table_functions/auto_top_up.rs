@@ -0,0 +1,196 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use crate::poker::game::types::QueueItem;
+
+use super::{
+    table::{Table, TableType},
+    types::{AutoTopUp, PlayerAction, SeatStatus},
+};
+
+impl Table {
+    /// Sets or clears the auto top up settings of a user.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    /// - `auto_top_up` - The settings, `None` turns auto top ups off.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the table isn't a cash table or
+    ///   the target isn't above the threshold
+    /// - [`GameError::BuyInTooSmall`] if the target is below the min buy in
+    /// - [`GameError::BuyInTooLarge`] if the target is above the max buy in
+    /// - [`GameError::Other`] if the user isn't at the table
+    pub fn set_auto_top_up(
+        &mut self,
+        user_principal: Principal,
+        auto_top_up: Option<AutoTopUp>,
+    ) -> Result<(), TracedError<GameError>> {
+        if let Some(auto_top_up) = auto_top_up {
+            if !matches!(self.config.table_type, None | Some(TableType::Cash)) {
+                return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "Auto top ups are only available at cash tables".to_string(),
+                })));
+            }
+            if auto_top_up.target_big_blinds <= auto_top_up.threshold_big_blinds {
+                return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "The top up target has to be above the threshold".to_string(),
+                })));
+            }
+            let target = auto_top_up.target_big_blinds as u64 * self.big_blind;
+            let minimum = self.min_buy_in();
+            if target < minimum {
+                return Err(trace_err!(TracedError::new(GameError::BuyInTooSmall {
+                    minimum
+                })));
+            }
+            if let Some(maximum) = self.max_buy_in().filter(|maximum| target > *maximum) {
+                return Err(trace_err!(TracedError::new(GameError::BuyInTooLarge {
+                    maximum
+                })));
+            }
+        }
+
+        let user_table_data = self
+            .get_user_table_data_mut(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?;
+        user_table_data.auto_top_up = auto_top_up;
+        Ok(())
+    }
+
+    /// Gets the amount the user's settings top their stack up by
+    /// before the next hand. A user who busted only buys back in
+    /// with auto rebuy on.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    pub fn auto_top_up_amount(&self, user_principal: Principal) -> Option<u64> {
+        let auto_top_up = self.user_table_data.get(&user_principal)?.auto_top_up?;
+        let balance = self.users.get(&user_principal)?.balance;
+
+        let should_top_up = if balance < self.big_blind {
+            auto_top_up.auto_rebuy
+        } else {
+            balance < auto_top_up.threshold_big_blinds as u64 * self.big_blind
+        };
+        let mut target = auto_top_up.target_big_blinds as u64 * self.big_blind;
+        if let Some(maximum) = self.max_buy_in() {
+            target = target.min(maximum);
+        }
+        (should_top_up && target > balance).then(|| target - balance)
+    }
+
+    /// Queues deposits for the players whose stacks need topping up.
+    /// Players sitting out are left alone. Should be called once the
+    /// pots of a hand have been handed out.
+    pub fn queue_auto_top_ups(&mut self) {
+        for seat in self.seats.clone() {
+            let SeatStatus::Occupied(user_principal) = seat else {
+                continue;
+            };
+            let Some(user_table_data) = self.user_table_data.get(&user_principal) else {
+                continue;
+            };
+            if user_table_data.player_action == PlayerAction::SittingOut {
+                continue;
+            }
+            let Some(amount) = self.auto_top_up_amount(user_principal) else {
+                continue;
+            };
+            let Some(user) = self.users.get(&user_principal) else {
+                continue;
+            };
+            self.queue.push(QueueItem::Deposit(
+                user_principal,
+                user.users_canister_id,
+                amount,
+            ));
+        }
+    }
+
+    /// Sits out a busted player whose auto rebuy hasn't landed yet,
+    /// or whose top up failed, instead of kicking them.
+    ///
+    /// # Returns
+    ///
+    /// Whether the player is waiting for a rebuy.
+    pub fn sit_out_for_rebuy(&mut self, user_principal: Principal) -> bool {
+        let is_busted = self
+            .users
+            .get(&user_principal)
+            .is_some_and(|user| user.balance < self.big_blind);
+        let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) else {
+            return false;
+        };
+        let is_rebuying = user_table_data.awaiting_rebuy == Some(true)
+            || user_table_data
+                .auto_top_up
+                .is_some_and(|auto| auto.auto_rebuy);
+        if !is_busted || !is_rebuying {
+            return false;
+        }
+        user_table_data.player_action = PlayerAction::SittingOut;
+        user_table_data.awaiting_rebuy = Some(true);
+        true
+    }
+
+    /// Sits a player back in once their auto rebuy landed.
+    ///
+    /// # Returns
+    ///
+    /// Whether the player was sat back in.
+    pub fn finish_rebuy(&mut self, user_principal: Principal) -> bool {
+        let has_chips = self
+            .users
+            .get(&user_principal)
+            .is_some_and(|user| user.balance >= self.big_blind);
+        let is_game_ongoing = self.is_game_ongoing();
+        let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) else {
+            return false;
+        };
+        if user_table_data.awaiting_rebuy != Some(true) || !has_chips {
+            return false;
+        }
+        user_table_data.awaiting_rebuy = None;
+        if is_game_ongoing {
+            self.queue.push(QueueItem::SittingIn(user_principal, false));
+        } else {
+            user_table_data.player_action = PlayerAction::None;
+        }
+        true
+    }
+
+    /// Turns off the auto top ups of a player whose deposit failed,
+    /// for example because their allowance ran out. A player who
+    /// busted is sat out and keeps their seat until they buy back in.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user isn't at the table
+    pub fn handle_failed_top_up(
+        &mut self,
+        user_principal: Principal,
+    ) -> Result<(), TracedError<GameError>> {
+        let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) else {
+            return Err(trace_err!(TracedError::new(GameError::PlayerNotFound)));
+        };
+        if user_table_data.auto_top_up.take().is_none() {
+            return Ok(());
+        }
+        let is_sitting_out = user_table_data.player_action == PlayerAction::SittingOut;
+        let is_busted = self
+            .users
+            .get(&user_principal)
+            .is_some_and(|user| user.balance < self.big_blind);
+        user_table_data.awaiting_rebuy = is_busted.then_some(true);
+
+        if is_busted && !is_sitting_out {
+            self.user_sitting_out(user_principal, false)
+                .map_err(|e| trace_err!(e, "Failed to sit out user after failed top up"))?;
+        }
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use errors::{game_error::GameError, trace_err, traced_error::TracedError};
 use crate::poker::game::types::GameType;
 
 use super::{
+    action_log::ActionType,
     table::Table,
     types::{BetType, DealStage},
 };
@@ -34,7 +35,7 @@ impl Table {
             self.clear_turn_timer();
         }
 
-        if bet_type != BetType::SmallBlind && bet_type != BetType::BigBlind {
+        if !bet_type.is_forced() {
             self.get_user_table_data_mut(user_principal)
                 .map_err(|e| {
                     trace_err!(
@@ -46,7 +47,9 @@ impl Table {
         }
 
         match bet_type {
-            BetType::BigBlind | BetType::SmallBlind => self.raise(user_principal, bet_type, 0, 0),
+            BetType::BigBlind | BetType::SmallBlind | BetType::Straddle => {
+                self.raise(user_principal, bet_type, 0, 0)
+            }
             BetType::Ante(amount) => {
                 self.check_user_balance(amount, user_principal)?;
                 self.users
@@ -54,6 +57,7 @@ impl Table {
                     .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
                     .balance -= amount;
                 self.pot += amount;
+                self.log_action(Some(user_principal), ActionType::Ante { amount });
                 Ok(())
             }
             BetType::Raised(amount) => {
@@ -62,7 +66,7 @@ impl Table {
                         reason: format!("Bet must be at least {}", self.big_blind as f64 / 1e8),
                     })));
                 }
-                if let GameType::PotLimit(_) = self.config.game_type {
+                if self.config.game_type.is_pot_limit() {
                     if amount > self.get_pot()
                         && bet_type != BetType::BigBlind
                         && bet_type != BetType::SmallBlind
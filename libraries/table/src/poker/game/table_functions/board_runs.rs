@@ -0,0 +1,259 @@
+use candid::{CandidType, Principal};
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+
+use crate::poker::core::Card;
+
+use super::{
+    action_log::ActionType,
+    table::{Table, TableType},
+    types::{DealStage, PlayerAction, SeatStatus},
+};
+
+/// An offer to run the rest of the board more than once, made when
+/// the players left in the hand are all in before the river.
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct BoardRunsOffer {
+    /// The players who have to agree on the number of runs.
+    pub user_principals: Vec<Principal>,
+    /// The number of runs each player has chosen so far.
+    pub choices: Vec<(Principal, u8)>,
+    /// The most runs the players can choose.
+    pub max_runs: u8,
+}
+
+impl BoardRunsOffer {
+    /// The number of runs every player agreed to. Players
+    /// who haven't chosen only agree to a single run.
+    pub fn agreed_runs(&self) -> u8 {
+        self.user_principals
+            .iter()
+            .map(|user_principal| {
+                self.choices
+                    .iter()
+                    .find(|(principal, _)| principal == user_principal)
+                    .map_or(1, |(_, runs)| *runs)
+            })
+            .min()
+            .unwrap_or(1)
+    }
+
+    /// Has every player made their choice?
+    pub fn is_complete(&self) -> bool {
+        self.user_principals.iter().all(|user_principal| {
+            self.choices
+                .iter()
+                .any(|(principal, _)| principal == user_principal)
+        })
+    }
+}
+
+/// Splits a pot evenly across the boards. Chips that can't
+/// be split evenly go to the first board.
+pub fn split_across_boards(amount: u64, boards: usize) -> Vec<u64> {
+    let boards = boards.max(1) as u64;
+    let share = amount / boards;
+    (0..boards)
+        .map(|board| {
+            if board == 0 {
+                share + amount % boards
+            } else {
+                share
+            }
+        })
+        .collect()
+}
+
+impl Table {
+    /// Whether the board can be run more than once at this table.
+    /// Only cash tables support it.
+    pub fn allows_board_runs(&self) -> bool {
+        self.config.max_board_runs.unwrap_or(1) > 1
+            && matches!(self.config.table_type, None | Some(TableType::Cash))
+    }
+
+    /// Every board of the hand, the community cards followed
+    /// by the extra boards if the board was run more than once.
+    pub fn boards(&self) -> Vec<Vec<Card>> {
+        std::iter::once(self.community_cards.clone())
+            .chain(self.extra_boards.iter().cloned())
+            .collect()
+    }
+
+    /// Offers the players still in the hand to run the rest of the board
+    /// more than once. Should be called once no more betting is possible.
+    ///
+    /// # Returns
+    ///
+    /// Whether an offer was made. If not, the board is run once as usual.
+    pub fn offer_board_runs(&mut self) -> bool {
+        if !self.allows_board_runs() || self.board_runs_offer.is_some() {
+            return false;
+        }
+
+        // The cards needed for a run including the burn cards.
+        let cards_per_run = match self.deal_stage {
+            DealStage::Flop => 8,
+            DealStage::Turn => 4,
+            DealStage::River => 2,
+            _ => return false,
+        };
+
+        let user_principals = self.get_players_in_hand();
+        if user_principals.len() < 2 {
+            return false;
+        }
+
+        let max_runs = (self.deck.len() / cards_per_run)
+            .min(self.config.max_board_runs.unwrap_or(1) as usize) as u8;
+        if max_runs < 2 {
+            return false;
+        }
+
+        self.board_runs_offer = Some(BoardRunsOffer {
+            user_principals,
+            choices: Vec::new(),
+            max_runs,
+        });
+
+        #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+        {
+            self.clear_turn_timer();
+            self.start_board_runs_timer(self.config.timer_duration.into());
+        }
+        true
+    }
+
+    /// Sets the number of times the user wants the rest of the board to
+    /// be run. Once every player has chosen, the board is run the lowest
+    /// number of times chosen.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if there is no offer to run the board more than once
+    /// - [`GameError::ActionNotAllowed`] if the user isn't part of the offer
+    /// - [`GameError::ActionNotAllowed`] if the number of runs is zero or above the maximum
+    pub fn set_board_runs(
+        &mut self,
+        user_principal: Principal,
+        runs: u8,
+    ) -> Result<(), TracedError<GameError>> {
+        let offer = self.board_runs_offer.as_mut().ok_or_else(|| {
+            trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "The board is not being run more than once".to_string(),
+            }))
+        })?;
+        if !offer.user_principals.contains(&user_principal) {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "User is not all in".to_string(),
+            })));
+        }
+        if runs == 0 || runs > offer.max_runs {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: format!(
+                    "The board can be run between 1 and {} times",
+                    offer.max_runs
+                ),
+            })));
+        }
+
+        offer
+            .choices
+            .retain(|(principal, _)| *principal != user_principal);
+        offer.choices.push((user_principal, runs));
+
+        if offer.is_complete() {
+            self.resolve_board_runs()
+                .map_err(|e| trace_err!(e, "Failed to resolve board runs."))?;
+        }
+        Ok(())
+    }
+
+    /// Runs the rest of the board as many times as the players agreed to
+    /// and shows down. Called once every player has chosen or when the
+    /// time to choose runs out.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if there is no offer to run the board more than once
+    /// - [`GameError::NoCardsLeft`] if there are no cards left in the deck
+    pub fn resolve_board_runs(&mut self) -> Result<(), TracedError<GameError>> {
+        let offer = self.board_runs_offer.take().ok_or_else(|| {
+            trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "The board is not being run more than once".to_string(),
+            }))
+        })?;
+        let runs = offer.agreed_runs();
+        let dealt_cards = self.community_cards.len();
+
+        while self.deal_stage != DealStage::Showdown {
+            self.deal_cards(true)
+                .map_err(|e| trace_err!(e, "Error dealing cards in resolve board runs."))?;
+            self.annotate_all_in_equities();
+        }
+        for run in 2..=runs {
+            let board = self
+                .deal_extra_board(dealt_cards)
+                .map_err(|e| trace_err!(e, "Error dealing extra board."))?;
+            self.log_action(
+                None,
+                ActionType::BoardRun {
+                    run,
+                    cards: board.clone(),
+                },
+            );
+            self.extra_boards.push(board);
+        }
+
+        self.showdown()
+            .map_err(|e| trace_err!(e, "Failed to show down after board runs."))?;
+        Ok(())
+    }
+
+    /// Deals another run of the board. The community cards dealt before
+    /// the players were all in are shared by every run.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::NoCardsLeft`] if there are no cards left in the deck
+    fn deal_extra_board(
+        &mut self,
+        dealt_cards: usize,
+    ) -> Result<Vec<Card>, TracedError<GameError>> {
+        let mut board = self.community_cards[..dealt_cards].to_vec();
+        for position in dealt_cards..5 {
+            // A card is burned before the flop, the turn and the river.
+            if position == 0 || position >= 3 {
+                self.deck
+                    .deal()
+                    .ok_or_else(|| trace_err!(TracedError::new(GameError::NoCardsLeft)))?;
+            }
+            board.push(
+                self.deck
+                    .deal()
+                    .ok_or_else(|| trace_err!(TracedError::new(GameError::NoCardsLeft)))?,
+            );
+        }
+        Ok(board)
+    }
+
+    /// Gets the players who haven't folded and are still in the hand.
+    pub(crate) fn get_players_in_hand(&self) -> Vec<Principal> {
+        self.seats
+            .iter()
+            .filter_map(|seat| match seat {
+                SeatStatus::Occupied(principal) => self
+                    .user_table_data
+                    .get(principal)
+                    .filter(|data| {
+                        !matches!(
+                            data.player_action,
+                            PlayerAction::Folded | PlayerAction::SittingOut | PlayerAction::Joining
+                        )
+                    })
+                    .map(|_| *principal),
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,336 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use user::user::User;
+
+use crate::poker::{
+    core::{rank_omaha, Card, CardIter, Rank, Rankable, Value},
+    game::utils::rank_hand,
+};
+
+use super::{
+    legal_actions::LegalActions,
+    table::Table,
+    types::{BetType, BotPersonality, CurrencyType, PlayerAction},
+};
+
+/// The seconds a bot waits before it acts.
+pub const BOT_THINK_SECONDS: u64 = 2;
+
+/// What a bot knows about the table when it is its turn.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BotView {
+    /// How strong the bot's hand is, from 0 for the weakest to 1 for
+    /// the strongest.
+    pub hand_strength: f64,
+    /// The share of the pot the call would be once it is made, 0 if
+    /// there is nothing to call.
+    pub pot_odds: f64,
+    /// The actions the bot may take.
+    pub legal_actions: LegalActions,
+    /// The total a raise of a big blind goes to, within the legal raise sizes.
+    pub small_raise: Option<u64>,
+    /// The total a pot sized raise goes to, within the legal raise sizes.
+    pub pot_raise: Option<u64>,
+}
+
+impl BotView {
+    /// Checks if possible, folds otherwise.
+    fn check_or_fold(&self) -> PlayerAction {
+        if self.legal_actions.can_check {
+            PlayerAction::Checked
+        } else {
+            PlayerAction::Folded
+        }
+    }
+
+    /// Checks if possible, calls otherwise.
+    fn check_or_call(&self) -> PlayerAction {
+        if self.legal_actions.can_check {
+            PlayerAction::Checked
+        } else {
+            PlayerAction::Called
+        }
+    }
+}
+
+/// Decides what a bot does on its turn.
+pub trait BotStrategy {
+    /// Picks the action for the bot. A raise that is out of the legal
+    /// range is clamped to it and an action that isn't allowed falls
+    /// back to a check, a call or a fold.
+    fn decide(&self, view: &BotView) -> PlayerAction;
+}
+
+/// Only plays strong hands and rarely puts in a raise.
+pub struct TightPassive;
+
+impl BotStrategy for TightPassive {
+    fn decide(&self, view: &BotView) -> PlayerAction {
+        match view.small_raise {
+            Some(small_raise) if view.hand_strength >= 0.8 => PlayerAction::Raised(small_raise),
+            _ if view.hand_strength >= 0.5 && view.hand_strength > view.pot_odds => {
+                view.check_or_call()
+            }
+            _ => view.check_or_fold(),
+        }
+    }
+}
+
+/// Plays a wide range of hands and bets them hard.
+pub struct LooseAggressive;
+
+impl BotStrategy for LooseAggressive {
+    fn decide(&self, view: &BotView) -> PlayerAction {
+        match (view.pot_raise, view.small_raise) {
+            (Some(pot_raise), _) if view.hand_strength >= 0.55 => PlayerAction::Raised(pot_raise),
+            // Takes a stab at the pot when nobody has bet
+            (_, Some(small_raise)) if view.legal_actions.can_check && view.hand_strength >= 0.3 => {
+                PlayerAction::Raised(small_raise)
+            }
+            _ if view.hand_strength >= 0.25 || view.hand_strength + 0.15 > view.pot_odds => {
+                view.check_or_call()
+            }
+            _ => view.check_or_fold(),
+        }
+    }
+}
+
+impl BotPersonality {
+    /// The strategy the personality plays with.
+    pub fn strategy(&self) -> Box<dyn BotStrategy> {
+        match self {
+            BotPersonality::TightPassive => Box::new(TightPassive),
+            BotPersonality::LooseAggressive => Box::new(LooseAggressive),
+        }
+    }
+}
+
+/// Scores two hole cards with the Chen formula, scaled so that aces
+/// score 1.
+fn starting_hand_strength(first: &Card, second: &Card) -> f64 {
+    let points = |value: Value| match value {
+        Value::Ace => 10.0,
+        Value::King => 8.0,
+        Value::Queen => 7.0,
+        Value::Jack => 6.0,
+        value => (value as u8 + 2) as f64 / 2.0,
+    };
+    let (high, low) = if first.value >= second.value {
+        (first.value, second.value)
+    } else {
+        (second.value, first.value)
+    };
+
+    let mut score = points(high);
+    if high == low {
+        score = (score * 2.0).max(5.0);
+    } else {
+        let gap = high as u8 - low as u8 - 1;
+        score -= match gap {
+            0 => 0.0,
+            1 => 1.0,
+            2 => 2.0,
+            3 => 4.0,
+            _ => 5.0,
+        };
+        if gap <= 1 && high < Value::Queen {
+            score += 1.0;
+        }
+    }
+    if first.suit == second.suit {
+        score += 2.0;
+    }
+    (score / 20.0).clamp(0.0, 1.0)
+}
+
+/// Scores a made hand by its category. In short deck a flush beats a
+/// full house.
+fn made_hand_strength(rank: Rank, is_short_deck: bool) -> f64 {
+    match rank {
+        Rank::HighCard(_) => 0.1,
+        Rank::OnePair(_) => 0.4,
+        Rank::TwoPair(_) => 0.6,
+        Rank::ThreeOfAKind(_) => 0.7,
+        Rank::Straight(_) => 0.8,
+        Rank::Flush(_) if is_short_deck => 0.92,
+        Rank::Flush(_) => 0.85,
+        Rank::FullHouse(_) if is_short_deck => 0.85,
+        Rank::FullHouse(_) => 0.92,
+        Rank::FourOfAKind(_) => 0.97,
+        Rank::StraightFlush(_) => 1.0,
+    }
+}
+
+impl Table {
+    /// Whether the user is a bot.
+    pub fn is_bot(&self, user_principal: Principal) -> bool {
+        self.bots.contains_key(&user_principal)
+    }
+
+    /// Whether everyone at the table is a bot. Bots don't play hands
+    /// among themselves.
+    pub fn has_only_bots(&self) -> bool {
+        self.users.users.keys().all(|user| self.is_bot(*user))
+    }
+
+    /// How strong the hand of the user is. Before the flop the best
+    /// two hole cards are scored, after it the best made hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    pub fn hand_strength(&self, user_principal: Principal) -> Result<f64, TracedError<GameError>> {
+        let cards = &self
+            .get_user_table_data(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data for hand strength."))?
+            .cards;
+
+        if self.community_cards.is_empty() {
+            return Ok(CardIter::new(cards, 2)
+                .map(|hole| starting_hand_strength(&hole[0], &hole[1]))
+                .fold(0.0, f64::max));
+        }
+        let rank = if self.config.game_type.is_omaha() {
+            rank_omaha(cards, &self.community_cards)
+        } else {
+            let mut all_cards = cards.clone();
+            all_cards.extend_from_slice(&self.community_cards);
+            if self.config.uses_short_deck() {
+                all_cards.rank_short_deck()
+            } else {
+                rank_hand(all_cards)
+            }
+        };
+        Ok(made_hand_strength(rank, self.config.uses_short_deck()))
+    }
+
+    /// Gets what the bot sees of the table on its turn.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user isn't at the table
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    pub fn bot_view(&self, user_principal: Principal) -> Result<BotView, TracedError<GameError>> {
+        let legal_actions = self
+            .legal_actions(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get legal actions for bot."))?;
+        let side_pots: u64 = self.side_pots.iter().map(|side_pot| side_pot.pot).sum();
+        let pot = self.pot + side_pots + self.get_pot();
+        let to_call = legal_actions.call_amount.unwrap_or(0);
+
+        let pot_odds = if to_call == 0 {
+            0.0
+        } else {
+            to_call as f64 / (pot + to_call) as f64
+        };
+        let raise_range = legal_actions.min_raise.zip(legal_actions.max_raise);
+        let small_raise =
+            raise_range.map(|(min, max)| (self.highest_bet + self.big_blind).clamp(min, max));
+        let pot_raise =
+            raise_range.map(|(min, max)| (self.highest_bet + pot + to_call).clamp(min, max));
+
+        Ok(BotView {
+            hand_strength: self.hand_strength(user_principal)?,
+            pot_odds,
+            legal_actions,
+            small_raise,
+            pot_raise,
+        })
+    }
+
+    /// Seats a bot in the first free seat.
+    ///
+    /// # Parameters
+    ///
+    /// - `personality` - The strategy the bot plays with.
+    /// - `balance` - The chips the bot sits down with.
+    ///
+    /// # Returns
+    ///
+    /// The principal of the bot.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the table plays for real money
+    /// - [`GameError::GameFull`] if there is no free seat
+    pub fn add_bot(
+        &mut self,
+        personality: BotPersonality,
+        balance: u64,
+    ) -> Result<Principal, TracedError<GameError>> {
+        if self.config.currency_type != CurrencyType::Fake {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Bots can only play at play money tables".to_string(),
+            })));
+        }
+        let seat = self
+            .get_free_seat_index()
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::GameFull)))?;
+
+        // Reserved class principals can never be the caller of a canister
+        // so nobody can act as the bot.
+        let id = self.id.as_slice();
+        let mut bytes = id[..id.len().min(27)].to_vec();
+        bytes.extend_from_slice(&[seat, 0x7f]);
+        let bot_principal = Principal::from_slice(&bytes);
+
+        let mut user = User::new(
+            bot_principal,
+            Principal::anonymous(),
+            format!("Bot {}", seat + 1),
+            balance,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        user.is_bot = Some(true);
+        self.add_user(user, seat, false)
+            .map_err(|e| trace_err!(e, "Failed to seat bot."))?;
+        self.bots.insert(bot_principal, personality);
+        Ok(bot_principal)
+    }
+
+    /// Takes the bot's turn with the action its strategy picks.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the bot.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the user isn't a bot or it
+    ///   isn't their turn
+    pub fn play_bot_turn(
+        &mut self,
+        user_principal: Principal,
+    ) -> Result<(), TracedError<GameError>> {
+        let Some(personality) = self.bots.get(&user_principal).copied() else {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "User is not a bot".to_string(),
+            })));
+        };
+        if !self.is_game_ongoing() || !self.is_players_turn(user_principal) {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "It is not the bot's turn".to_string(),
+            })));
+        }
+
+        let view = self.bot_view(user_principal)?;
+        let legal_actions = &view.legal_actions;
+        let raise_range = legal_actions.min_raise.zip(legal_actions.max_raise);
+        let result = match (personality.strategy().decide(&view), raise_range) {
+            (PlayerAction::Raised(amount), Some((min, max))) => {
+                self.bet(user_principal, BetType::Raised(amount.clamp(min, max)))
+            }
+            (PlayerAction::AllIn, Some((_, max))) => self.bet(user_principal, BetType::Raised(max)),
+            (PlayerAction::Folded | PlayerAction::Checked, _) if !legal_actions.can_check => {
+                self.user_fold(user_principal, false)
+            }
+            _ if legal_actions.can_check => self.user_check(user_principal, false),
+            _ => self.bet(user_principal, BetType::Called),
+        };
+        result.map_err(|e| trace_err!(e, "Failed to play bot turn."))
+    }
+}
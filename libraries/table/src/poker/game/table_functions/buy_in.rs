@@ -0,0 +1,172 @@
+use candid::{CandidType, Principal};
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+
+use super::table::{Table, TableType};
+
+/// A player who left a cash table with more than the max buy in.
+/// They can't come back with less until the rathole window is over.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct TableExit {
+    /// The stack the player left with.
+    pub stack: u64,
+    /// When the player left in nanoseconds.
+    pub left_at: u64,
+}
+
+impl Table {
+    /// Whether the buy in limits apply. Tournaments have a fixed buy in
+    /// and fast-fold buy ins are checked when joining the pool, as the
+    /// stack a player carries between pool tables can be anything.
+    fn has_buy_in_limits(&self) -> bool {
+        matches!(self.config.table_type, None | Some(TableType::Cash)) && !self.is_fast_fold()
+    }
+
+    /// The least a player can sit down with.
+    pub fn min_buy_in(&self) -> u64 {
+        self.config
+            .min_buy_in_big_blinds
+            .map_or(self.big_blind, |big_blinds| {
+                big_blinds as u64 * self.big_blind
+            })
+    }
+
+    /// The most a player can sit down with or top up to.
+    pub fn max_buy_in(&self) -> Option<u64> {
+        self.config
+            .max_buy_in_big_blinds
+            .map(|big_blinds| big_blinds as u64 * self.big_blind)
+    }
+
+    /// Gets the exit of a user who left within the rathole window.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    /// - `now` - The current time in nanoseconds.
+    pub fn recent_exit(&self, user_principal: Principal, now: u64) -> Option<TableExit> {
+        let window = self.rathole_window()?;
+        self.recent_exits
+            .get(&user_principal)
+            .filter(|exit| now.saturating_sub(exit.left_at) < window)
+            .copied()
+    }
+
+    /// The rathole window in nanoseconds.
+    fn rathole_window(&self) -> Option<u64> {
+        self.config
+            .rathole_window_seconds
+            .map(|seconds| seconds as u64 * 1_000_000_000)
+    }
+
+    /// Checks that a user can sit down with `amount`. A user returning
+    /// within the rathole window has to bring back at least the stack they
+    /// left with, even if that is more than the max buy in.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user joining.
+    /// - `amount` - The amount the user is buying in for.
+    /// - `now` - The current time in nanoseconds.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::BuyInTooSmall`] if the amount is below the min buy in
+    ///   or the stack the user left with
+    /// - [`GameError::BuyInTooLarge`] if the amount is above the max buy in
+    pub fn validate_buy_in(
+        &self,
+        user_principal: Principal,
+        amount: u64,
+        now: u64,
+    ) -> Result<(), TracedError<GameError>> {
+        if !self.has_buy_in_limits() {
+            return Ok(());
+        }
+        let exit_stack = self
+            .recent_exit(user_principal, now)
+            .map_or(0, |exit| exit.stack);
+
+        let minimum = self.min_buy_in().max(exit_stack);
+        if amount < minimum {
+            return Err(trace_err!(TracedError::new(GameError::BuyInTooSmall {
+                minimum
+            })));
+        }
+        if let Some(maximum) = self.max_buy_in().map(|maximum| maximum.max(exit_stack)) {
+            if amount > maximum {
+                return Err(trace_err!(TracedError::new(GameError::BuyInTooLarge {
+                    maximum
+                })));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that a top up doesn't take the user's stack over the max buy in.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user topping up.
+    /// - `amount` - The amount the user is adding to their stack.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user isn't at the table
+    /// - [`GameError::BuyInTooLarge`] if the stack would go over the max buy in
+    pub fn validate_top_up(
+        &self,
+        user_principal: Principal,
+        amount: u64,
+    ) -> Result<(), TracedError<GameError>> {
+        if !self.has_buy_in_limits() {
+            return Ok(());
+        }
+        let Some(maximum) = self.max_buy_in() else {
+            return Ok(());
+        };
+        let balance = self
+            .users
+            .get(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
+            .balance;
+        if balance + amount > maximum {
+            return Err(trace_err!(TracedError::new(GameError::BuyInTooLarge {
+                maximum
+            })));
+        }
+        Ok(())
+    }
+
+    /// Remembers the stack of a user leaving with more than the max buy in
+    /// so they can't rejoin with less within the rathole window. Exits
+    /// outside the window are forgotten.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user leaving.
+    /// - `stack` - The stack the user is leaving with.
+    /// - `now` - The current time in nanoseconds.
+    pub fn record_exit(&mut self, user_principal: Principal, stack: u64, now: u64) {
+        let Some(window) = self.rathole_window() else {
+            return;
+        };
+        self.recent_exits
+            .retain(|_, exit| now.saturating_sub(exit.left_at) < window);
+
+        if !self.has_buy_in_limits() {
+            return;
+        }
+        if self.max_buy_in().is_some_and(|maximum| stack > maximum) {
+            self.recent_exits.insert(
+                user_principal,
+                TableExit {
+                    stack,
+                    left_at: now,
+                },
+            );
+        } else {
+            self.recent_exits.remove(&user_principal);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use candid::{CandidType, Principal};
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::poker::core::Card;
+
+use super::{
+    table::Table,
+    types::{DealStage, UserTableData},
+};
+
+/// Derives the keys hole cards are encrypted under.
+///
+/// The cards of every hand are encrypted under a key derived for an
+/// identity made of the table, the player and the hand. The player fetches
+/// that key wrapped under the transport public key they registered, so
+/// only the holder of the matching secret key can unwrap it.
+pub trait CardKeyDerivation {
+    /// Derives the symmetric key for the given identity.
+    fn derive_key(&self, identity: &[u8]) -> [u8; 32];
+
+    /// Derives the key for the given identity and wraps it
+    /// under the transport public key.
+    fn encrypted_key(
+        &self,
+        identity: &[u8],
+        transport_public_key: &[u8; TRANSPORT_KEY_LEN],
+    ) -> Vec<u8>;
+}
+
+/// The length of an X25519 transport public key.
+pub const TRANSPORT_KEY_LEN: usize = 32;
+
+/// Derives card keys from a master key held in the table state and wraps
+/// them with ECIES over X25519.
+///
+/// A wrapped key is an ephemeral public key followed by the card key
+/// masked with a hash of the shared secret between the ephemeral key and
+/// the player's transport key. Observers of the table only ever see the
+/// transport public key, so they can't unwrap it. The master key itself
+/// lives in canister memory, so this doesn't protect the cards from
+/// anyone who can read that memory.
+pub struct EciesKeyDerivation {
+    master_key: [u8; 32],
+}
+
+impl EciesKeyDerivation {
+    pub fn new(master_key: [u8; 32]) -> Self {
+        EciesKeyDerivation { master_key }
+    }
+
+    /// The ephemeral secret a key is wrapped with. It's derived from the
+    /// master key so fetching the same key twice returns the same bytes.
+    fn ephemeral_secret(&self, identity: &[u8], transport_public_key: &[u8]) -> StaticSecret {
+        let mut hasher = Sha256::new();
+        hasher.update(b"card-key-ephemeral");
+        hasher.update(self.master_key);
+        hasher.update(identity);
+        hasher.update(transport_public_key);
+        let bytes: [u8; 32] = hasher.finalize().into();
+        StaticSecret::from(bytes)
+    }
+
+    /// Unwraps a key returned by [`CardKeyDerivation::encrypted_key`].
+    /// This is the step a client performs with its transport secret key.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the encrypted key isn't 64 bytes
+    #[cfg(test)]
+    pub fn decrypt_key(
+        encrypted_key: &[u8],
+        transport_secret_key: &StaticSecret,
+    ) -> Result<[u8; 32], TracedError<GameError>> {
+        if encrypted_key.len() != 2 * TRANSPORT_KEY_LEN {
+            return Err(trace_err!(TracedError::new(GameError::Other(
+                "Encrypted key must be 64 bytes".to_string()
+            ))));
+        }
+        let (ephemeral_public_key, masked_key) = encrypted_key.split_at(TRANSPORT_KEY_LEN);
+        let ephemeral_public_key: [u8; TRANSPORT_KEY_LEN] = ephemeral_public_key
+            .try_into()
+            .expect("Split at the transport key length");
+        let transport_public_key = PublicKey::from(transport_secret_key);
+        let shared_secret =
+            transport_secret_key.diffie_hellman(&PublicKey::from(ephemeral_public_key));
+        let mask = wrapping_mask(
+            shared_secret.as_bytes(),
+            &ephemeral_public_key,
+            transport_public_key.as_bytes(),
+        );
+
+        let mut key = [0; 32];
+        for ((byte, masked), mask) in key.iter_mut().zip(masked_key).zip(mask) {
+            *byte = masked ^ mask;
+        }
+        Ok(key)
+    }
+}
+
+impl CardKeyDerivation for EciesKeyDerivation {
+    fn derive_key(&self, identity: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"card-key");
+        hasher.update(self.master_key);
+        hasher.update(identity);
+        hasher.finalize().into()
+    }
+
+    fn encrypted_key(
+        &self,
+        identity: &[u8],
+        transport_public_key: &[u8; TRANSPORT_KEY_LEN],
+    ) -> Vec<u8> {
+        let ephemeral_secret = self.ephemeral_secret(identity, transport_public_key);
+        let ephemeral_public_key = PublicKey::from(&ephemeral_secret);
+        let shared_secret =
+            ephemeral_secret.diffie_hellman(&PublicKey::from(*transport_public_key));
+        let mask = wrapping_mask(
+            shared_secret.as_bytes(),
+            ephemeral_public_key.as_bytes(),
+            transport_public_key,
+        );
+
+        let mut encrypted_key = ephemeral_public_key.as_bytes().to_vec();
+        encrypted_key.extend(
+            self.derive_key(identity)
+                .iter()
+                .zip(mask)
+                .map(|(byte, mask)| byte ^ mask),
+        );
+        encrypted_key
+    }
+}
+
+/// Derives the mask a card key is wrapped with from the ECDH shared secret.
+/// Both public keys are bound in so a wrapped key can't be replayed under
+/// another transport key.
+fn wrapping_mask(
+    shared_secret: &[u8; 32],
+    ephemeral_public_key: &[u8; TRANSPORT_KEY_LEN],
+    transport_public_key: &[u8; TRANSPORT_KEY_LEN],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"card-key-wrap");
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_public_key);
+    hasher.update(transport_public_key);
+    hasher.finalize().into()
+}
+
+/// Derives the master key for a table's card encryption from random bytes.
+pub fn card_master_key(rand_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"card-master-key");
+    hasher.update(rand_bytes);
+    hasher.finalize().into()
+}
+
+/// The identity a player's hole cards for a hand are encrypted to.
+pub fn card_key_identity(table_id: Principal, user_principal: Principal, hand_id: u64) -> Vec<u8> {
+    let mut identity = Vec::new();
+    identity.extend_from_slice(table_id.as_slice());
+    identity.extend_from_slice(user_principal.as_slice());
+    identity.extend_from_slice(&hand_id.to_le_bytes());
+    identity
+}
+
+/// XORs the data with a SHA-256 keystream. Encryption and decryption
+/// are the same operation.
+fn apply_keystream(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    data.chunks(32)
+        .enumerate()
+        .flat_map(|(counter, chunk)| {
+            let mut hasher = Sha256::new();
+            hasher.update(key);
+            hasher.update((counter as u64).to_le_bytes());
+            let block: [u8; 32] = hasher.finalize().into();
+            chunk
+                .iter()
+                .zip(block)
+                .map(|(byte, mask)| byte ^ mask)
+                .collect::<Vec<u8>>()
+        })
+        .collect()
+}
+
+/// Encrypts hole cards under the given key.
+pub fn encrypt_cards(key: &[u8; 32], cards: &[Card]) -> Vec<u8> {
+    let plaintext: Vec<u8> = cards.iter().map(|card| u8::from(*card)).collect();
+    apply_keystream(key, &plaintext)
+}
+
+/// Decrypts hole cards encrypted with [`encrypt_cards`].
+///
+/// # Errors
+///
+/// - [`GameError::Other`] if the ciphertext doesn't decrypt to valid cards
+pub fn decrypt_cards(
+    key: &[u8; 32],
+    ciphertext: &[u8],
+) -> Result<Vec<Card>, TracedError<GameError>> {
+    apply_keystream(key, ciphertext)
+        .into_iter()
+        .map(|byte| {
+            if byte < 52 {
+                Ok(Card::from(byte))
+            } else {
+                Err(trace_err!(TracedError::new(GameError::Other(
+                    "Could not decrypt cards".to_string()
+                ))))
+            }
+        })
+        .collect()
+}
+
+/// A player's card key for a hand, wrapped under their transport key.
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct EncryptedCardKey {
+    /// The hand the key decrypts the cards of.
+    pub hand_id: u64,
+    pub encrypted_key: Vec<u8>,
+}
+
+impl Table {
+    /// Gets the key derivation used for the hole cards at this table.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the table has no card encryption key
+    pub fn card_key_derivation(&self) -> Result<EciesKeyDerivation, TracedError<GameError>> {
+        let master_key: [u8; 32] = self
+            .card_master_key
+            .clone()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| {
+                trace_err!(TracedError::new(GameError::Other(
+                    "Card encryption key not set".to_string()
+                )))
+            })?;
+        Ok(EciesKeyDerivation::new(master_key))
+    }
+
+    /// Registers the transport public key the user's card keys are
+    /// delivered under.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the key isn't an X25519 public key
+    /// - [`GameError::Other`] if the user isn't at the table
+    pub fn set_transport_public_key(
+        &mut self,
+        user_principal: Principal,
+        transport_public_key: Vec<u8>,
+    ) -> Result<(), TracedError<GameError>> {
+        if transport_public_key.len() != TRANSPORT_KEY_LEN {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Transport public key must be a 32 byte X25519 key".to_string(),
+            })));
+        }
+        let user_table_data = self
+            .get_user_table_data_mut(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?;
+        user_table_data.transport_public_key = Some(transport_public_key);
+        Ok(())
+    }
+
+    /// Encrypts the hole cards of every player that registered a
+    /// transport key. Should be called once the opening cards are dealt.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the table has no card encryption key
+    pub fn encrypt_hole_cards(&mut self) -> Result<(), TracedError<GameError>> {
+        let key_derivation = self
+            .card_key_derivation()
+            .map_err(|e| trace_err!(e, "Failed to get card key derivation"))?;
+        let table_id = self.id;
+        let hand_id = self.round_ticker;
+
+        for (user_principal, user_table_data) in self.user_table_data.iter_mut() {
+            if user_table_data.cards.is_empty() || user_table_data.transport_public_key.is_none() {
+                continue;
+            }
+            let identity = card_key_identity(table_id, *user_principal, hand_id);
+            let key = key_derivation.derive_key(&identity);
+            user_table_data.encrypted_cards = Some(encrypt_cards(&key, &user_table_data.cards));
+        }
+        Ok(())
+    }
+
+    /// Gets the user's card key for the current hand, wrapped under
+    /// their transport key.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the user hasn't registered a transport key
+    /// - [`GameError::Other`] if the user isn't at the table
+    /// - [`GameError::Other`] if the table has no card encryption key
+    pub fn get_encrypted_card_key(
+        &self,
+        user_principal: Principal,
+    ) -> Result<EncryptedCardKey, TracedError<GameError>> {
+        let transport_public_key: [u8; TRANSPORT_KEY_LEN] = self
+            .get_user_table_data(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?
+            .transport_public_key
+            .clone()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| {
+                trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "No transport public key registered".to_string(),
+                }))
+            })?;
+        let key_derivation = self
+            .card_key_derivation()
+            .map_err(|e| trace_err!(e, "Failed to get card key derivation"))?;
+
+        let identity = card_key_identity(self.id, user_principal, self.round_ticker);
+        Ok(EncryptedCardKey {
+            hand_id: self.round_ticker,
+            encrypted_key: key_derivation.encrypted_key(&identity, &transport_public_key),
+        })
+    }
+
+    /// The user table data as it may be published. With hole card
+    /// encryption enabled the plaintext cards are removed until showdown,
+    /// or until the player is all in and their hand is face up.
+    pub fn public_user_table_data(&self) -> HashMap<Principal, UserTableData> {
+        let mut user_table_data = self.user_table_data.clone();
+        if self.config.encrypts_hole_cards() && self.deal_stage != DealStage::Showdown {
+            for (user_principal, data) in user_table_data.iter_mut() {
+                if !self.is_hand_face_up(*user_principal) {
+                    data.cards.clear();
+                }
+            }
+        }
+        user_table_data
+    }
+}
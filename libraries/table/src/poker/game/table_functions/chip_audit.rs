@@ -0,0 +1,109 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use crate::poker::game::types::QueueItem;
+
+use super::{action_log::ActionType, table::Table, types::SeatStatus};
+
+impl Table {
+    /// Gets the chips in the stacks of the seated players.
+    fn seated_stacks(&self) -> u64 {
+        self.seats
+            .iter()
+            .filter_map(|seat| match seat {
+                SeatStatus::Occupied(principal) => self.users.get(principal),
+                _ => None,
+            })
+            .map(|user| user.balance)
+            .sum()
+    }
+
+    /// Snapshots the chips at the table so they can be audited once
+    /// the hand is over. Should be called once the pots of the last
+    /// hand are cleared and before the blinds are posted.
+    pub fn start_chip_audit(&mut self) {
+        self.hand_start_chips = Some(self.seated_stacks());
+    }
+
+    /// Counts chips deposited into a seated player's stack during the
+    /// hand into the chips the audit started with.
+    pub(crate) fn audit_deposit(&mut self, user_principal: Principal, amount: u64) {
+        if !self.is_seated(user_principal) {
+            return;
+        }
+        if let Some(chips) = self.hand_start_chips.as_mut() {
+            *chips += amount;
+        }
+    }
+
+    /// Takes chips that left a seated player's stack during the hand
+    /// out of the chips the audit started with.
+    pub(crate) fn audit_withdrawal(&mut self, user_principal: Principal, amount: u64) {
+        if !self.is_seated(user_principal) {
+            return;
+        }
+        if let Some(chips) = self.hand_start_chips.as_mut() {
+            *chips = chips.saturating_sub(amount);
+        }
+    }
+
+    fn is_seated(&self, user_principal: Principal) -> bool {
+        self.seats.iter().any(
+            |seat| matches!(seat, SeatStatus::Occupied(principal) if *principal == user_principal),
+        )
+    }
+
+    /// Checks that the chips at the table when the hand started are
+    /// all accounted for. Once the pots have been handed out every chip
+    /// is either back in a stack, uncalled bets included, or was raked.
+    ///
+    /// # Parameters
+    ///
+    /// - `rake` - The rake taken from the pots of the hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ChipsNotConserved`] if chips were created or lost
+    pub fn audit_chips(&self, rake: u64) -> Result<(), TracedError<GameError>> {
+        let Some(chips_before) = self.hand_start_chips else {
+            return Ok(());
+        };
+        let chips_after = self.seated_stacks() + rake;
+
+        if chips_after != chips_before {
+            return Err(trace_err!(TracedError::new(GameError::ChipsNotConserved {
+                chips_before,
+                chips_after,
+            })));
+        }
+        Ok(())
+    }
+
+    /// Audits the chips of the hand that just finished. A violation is
+    /// logged and the table is paused before the next hand is dealt, so
+    /// no more chips can go missing until someone has looked into it.
+    ///
+    /// # Parameters
+    ///
+    /// - `rake` - The rake taken from the pots of the hand.
+    pub fn finish_chip_audit(&mut self, rake: u64) {
+        if let Err(e) = self.audit_chips(rake) {
+            ic_cdk::println!("Chip audit failed on table {}: {:?}", self.id.to_text(), e);
+            if let GameError::ChipsNotConserved {
+                chips_before,
+                chips_after,
+            } = e.into_inner()
+            {
+                self.log_action(
+                    None,
+                    ActionType::ChipAuditFailed {
+                        chips_before,
+                        chips_after,
+                    },
+                );
+            }
+            self.append_to_queue(QueueItem::PauseTable);
+        }
+        self.hand_start_chips = None;
+    }
+}
@@ -34,6 +34,10 @@ impl Table {
             DealStage::Opening => {
                 self.deal_opening_cards()
                     .map_err(|e| trace_err!(e, "Failed to deal opening cards."))?;
+                if self.config.encrypts_hole_cards() {
+                    self.encrypt_hole_cards()
+                        .map_err(|e| trace_err!(e, "Failed to encrypt hole cards."))?;
+                }
                 return Ok(());
             }
             DealStage::Flop => {
@@ -66,14 +70,17 @@ impl Table {
         Ok(())
     }
 
-    /// Deals the opening (two) cards to all players
+    /// Deals the opening hole cards to all players
+    /// (two for Hold'em, four or five for Omaha)
     ///
     /// # Errors
     ///
     /// - [`GameError::NoCardsLeft`] if there are no cards left in the deck
     /// - [`GameError::Other`] if the user table data cannot be retrieved
     fn deal_opening_cards(&mut self) -> Result<(), TracedError<GameError>> {
-        for _ in 0..2 {
+        // Players waiting for the big blind are held as joining.
+        let holds_joining_players = self.tracks_missed_blinds();
+        for _ in 0..self.config.game_type.hole_cards_count() {
             for user_principal in self.seats.iter() {
                 if let SeatStatus::Occupied(user_principal) = user_principal {
                     let user_table_data =
@@ -84,7 +91,12 @@ impl Table {
                                     "Could not get users table data".to_string(),
                                 )))
                             })?;
-                    if user_table_data.player_action != PlayerAction::SittingOut {
+                    let is_held = match user_table_data.player_action {
+                        PlayerAction::SittingOut => true,
+                        PlayerAction::Joining => holds_joining_players,
+                        _ => false,
+                    };
+                    if !is_held {
                         user_table_data.cards.push(
                             self.deck.deal().ok_or_else(|| {
                                 trace_err!(TracedError::new(GameError::NoCardsLeft))
@@ -0,0 +1,72 @@
+use std::collections::{HashMap, HashSet};
+
+use candid::Principal;
+
+use super::table::Table;
+
+/// The players contesting a pot and the chips each winner took from it.
+pub(crate) type PotShares = (Vec<Principal>, HashMap<Principal, u64>);
+
+impl Table {
+    /// Records who knocked out the players left with less than a big
+    /// blind after a showdown, so a tournament can pay out their bounty.
+    ///
+    /// Every other player who won a share of a pot the knocked out player
+    /// was contesting is an eliminator, weighted by the chips they won
+    /// from those pots. With split pots or side pots there can be more
+    /// than one.
+    ///
+    /// # Parameters
+    ///
+    /// - `pot_shares` - Every pot of the hand with its contestants and winners.
+    pub(crate) fn record_eliminations(&mut self, pot_shares: &[PotShares]) {
+        self.eliminations.clear();
+
+        let contestants: HashSet<Principal> = pot_shares
+            .iter()
+            .flat_map(|(contestants, _)| contestants.iter().copied())
+            .collect();
+        for user_principal in contestants {
+            let is_knocked_out = self
+                .users
+                .get(&user_principal)
+                .is_some_and(|user| user.balance < self.big_blind);
+            if !is_knocked_out {
+                continue;
+            }
+
+            let mut eliminators: HashMap<Principal, u64> = HashMap::new();
+            for (contestants, shares) in pot_shares {
+                if !contestants.contains(&user_principal) {
+                    continue;
+                }
+                for (winner, share) in shares {
+                    if *winner != user_principal && *share > 0 {
+                        *eliminators.entry(*winner).or_insert(0) += share;
+                    }
+                }
+            }
+            if eliminators.is_empty() {
+                continue;
+            }
+
+            // Biggest share first so the order doesn't depend on the map
+            let mut eliminators: Vec<(Principal, u64)> = eliminators.into_iter().collect();
+            eliminators.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            self.eliminations.insert(user_principal, eliminators);
+        }
+    }
+
+    /// Gets the players who knocked out the user in the last showdown,
+    /// with the chips they won from the pots the user was in.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the knocked out user.
+    pub fn get_eliminators(&self, user_principal: Principal) -> Vec<(Principal, u64)> {
+        self.eliminations
+            .get(&user_principal)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
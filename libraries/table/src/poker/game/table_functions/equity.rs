@@ -0,0 +1,137 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use crate::poker::core::{calculate_equities, shuffle_seed, Card, Equities, EquityRules};
+
+use super::{
+    action_log::ActionType,
+    table::{Table, TableConfig},
+};
+
+/// The random runouts dealt when there are too many to deal every one.
+pub const ALL_IN_EQUITY_SAMPLES: u64 = 2_000;
+
+/// Equities are published in basis points, 10_000 being the whole pot.
+const BASIS_POINTS: f64 = 10_000.0;
+
+/// The seed for the random runouts of a set of hands, so the same
+/// hands and board always get the same equities.
+pub fn equity_seed(hands: &[Vec<Card>], board: &[Card]) -> [u8; 32] {
+    let bytes: Vec<u8> = hands
+        .iter()
+        .flatten()
+        .chain(board.iter())
+        .flat_map(|card| [card.value as u8, card.suit as u8])
+        .collect();
+    shuffle_seed(&bytes)
+}
+
+/// Works out the equities of the hands with [`ALL_IN_EQUITY_SAMPLES`]
+/// random runouts if there are too many to deal every one.
+///
+/// # Errors
+///
+/// - [`GameError::Other`] if the hands or the board are invalid
+pub fn equities_for(
+    hands: &[Vec<Card>],
+    board: &[Card],
+    rules: EquityRules,
+) -> Result<Equities, TracedError<GameError>> {
+    calculate_equities(
+        hands,
+        board,
+        rules,
+        ALL_IN_EQUITY_SAMPLES,
+        &equity_seed(hands, board),
+    )
+    .map_err(|e| trace_err!(TracedError::new(GameError::Other(e.to_string()))))
+}
+
+impl TableConfig {
+    /// How the hands at the table are made and ranked.
+    pub fn equity_rules(&self) -> EquityRules {
+        if self.game_type.is_omaha() {
+            EquityRules::Omaha
+        } else if self.uses_short_deck() {
+            EquityRules::ShortDeck
+        } else {
+            EquityRules::Holdem
+        }
+    }
+}
+
+impl Table {
+    /// Works out the equity of every player still in the hand.
+    ///
+    /// # Returns
+    ///
+    /// The equities in basis points, or `None` on hi/lo tables, on a
+    /// complete board or with fewer than two players in the hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the hands of the players are invalid
+    pub fn calculate_all_in_equities(
+        &self,
+    ) -> Result<Option<Vec<(Principal, u16)>>, TracedError<GameError>> {
+        if self.config.is_hi_lo.unwrap_or(false) || self.community_cards.len() >= 5 {
+            return Ok(None);
+        }
+
+        let (user_principals, hands): (Vec<Principal>, Vec<Vec<Card>>) = self
+            .get_players_in_hand()
+            .into_iter()
+            .filter_map(|user_principal| {
+                self.user_table_data
+                    .get(&user_principal)
+                    .map(|data| (user_principal, data.cards.clone()))
+            })
+            .unzip();
+        if hands.len() < 2 {
+            return Ok(None);
+        }
+
+        let equities = equities_for(&hands, &self.community_cards, self.config.equity_rules())
+            .map_err(|e| trace_err!(e, "Failed to calculate all in equities."))?;
+        Ok(Some(
+            user_principals
+                .into_iter()
+                .zip(equities.equities)
+                .map(|(user_principal, equity)| {
+                    (user_principal, (equity * BASIS_POINTS).round() as u16)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Annotates the hand with the equities of the players once no more
+    /// betting is possible, and again with every street dealt after that.
+    /// Their hands are shown to the table from then on. A failure is only
+    /// printed, the hand plays on with the last equities.
+    pub fn annotate_all_in_equities(&mut self) {
+        match self.calculate_all_in_equities() {
+            Ok(Some(equities)) => {
+                self.all_in_equities = Some(equities.clone());
+                self.log_action(None, ActionType::AllInEquity { equities });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                ic_cdk::println!(
+                    "Failed to annotate all in equities on table {}: {:?}",
+                    self.id.to_text(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Whether the hand of the user is shown to the table because they
+    /// are all in with no more betting possible.
+    pub fn is_hand_face_up(&self, user_principal: Principal) -> bool {
+        self.all_in_equities.as_ref().is_some_and(|equities| {
+            equities
+                .iter()
+                .any(|(principal, _)| *principal == user_principal)
+        })
+    }
+}
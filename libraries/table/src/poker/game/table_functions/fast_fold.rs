@@ -0,0 +1,105 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use super::{
+    action_log::ActionType,
+    table::{Table, TableConfig},
+    types::PlayerAction,
+};
+
+impl TableConfig {
+    /// Checks the stack a player brings into a fast-fold pool against
+    /// the buy in limits of the pool's tables.
+    ///
+    /// # Parameters
+    ///
+    /// - `amount` - The amount the user is buying in for.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::BuyInTooSmall`] if the amount is below the min buy in
+    /// - [`GameError::BuyInTooLarge`] if the amount is above the max buy in
+    pub fn validate_fast_fold_buy_in(&self, amount: u64) -> Result<(), TracedError<GameError>> {
+        let (_, big_blind) = self.game_type.blinds();
+        let minimum = self
+            .min_buy_in_big_blinds
+            .map_or(big_blind, |big_blinds| big_blinds as u64 * big_blind);
+        if amount < minimum {
+            return Err(trace_err!(TracedError::new(GameError::BuyInTooSmall {
+                minimum
+            })));
+        }
+        if let Some(maximum) = self
+            .max_buy_in_big_blinds
+            .map(|big_blinds| big_blinds as u64 * big_blind)
+        {
+            if amount > maximum {
+                return Err(trace_err!(TracedError::new(GameError::BuyInTooLarge {
+                    maximum
+                })));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Table {
+    /// Whether the table is one of the tables of a fast-fold pool.
+    pub fn is_fast_fold(&self) -> bool {
+        self.config.is_fast_fold.unwrap_or(false)
+    }
+
+    /// Takes a player who folded off a fast-fold table so they can be
+    /// dealt into the next hand at another table of the pool straight
+    /// away. The chips they put in the pot stay in the hand, the rest
+    /// of their stack goes with them. Their seat is cleared once the
+    /// hand is over.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    ///
+    /// # Returns
+    ///
+    /// The stack the user carries to the next table.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the table isn't a fast-fold
+    ///   table or the user is still in the hand
+    /// - [`GameError::PlayerNotFound`] if the user isn't at the table
+    pub fn fast_fold_user(
+        &mut self,
+        user_principal: Principal,
+    ) -> Result<u64, TracedError<GameError>> {
+        if !self.is_fast_fold() {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Table is not a fast-fold table".to_string(),
+            })));
+        }
+        if self.has_user_left(user_principal) {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "User is already leaving the table".to_string(),
+            })));
+        }
+        if self.is_game_ongoing()
+            && self.get_user_table_data(user_principal)?.player_action != PlayerAction::Folded
+        {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "User has to fold first".to_string(),
+            })));
+        }
+
+        let user = self
+            .users
+            .get_mut(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
+        let stack = user.balance;
+        user.balance = 0;
+        // The stack left with the player so it is no longer part of the hand
+        self.audit_withdrawal(user_principal, stack);
+
+        self.remove_user(user_principal, ActionType::Leave)?;
+        Ok(stack)
+    }
+}
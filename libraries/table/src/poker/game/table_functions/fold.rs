@@ -164,12 +164,12 @@ impl Table {
     }
 
     /// Have all players folded?
-    pub fn all_players_folded(&mut self) -> bool {
+    pub fn all_players_folded(&self) -> bool {
         // TODO: Refactor: this could be more idiomatic, like using filter
         let mut n = self.number_of_players();
         for user_principal in self.seats.iter() {
             if let SeatStatus::Occupied(user_principal) = user_principal {
-                if let Some(user_table_data) = self.user_table_data.get_mut(user_principal) {
+                if let Some(user_table_data) = self.user_table_data.get(user_principal) {
                     if user_table_data.player_action == PlayerAction::Folded
                         || user_table_data.player_action == PlayerAction::SittingOut
                         || user_table_data.player_action == PlayerAction::Joining
@@ -0,0 +1,191 @@
+use candid::{CandidType, Principal};
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+
+use crate::poker::game::types::GameType;
+
+use super::{
+    action_log::ActionType,
+    table::Table,
+    types::{DealStage, PlayerAction},
+};
+
+/// The actions the acting player may take right now.
+///
+/// Raise amounts are the total the player's bet for the round is
+/// raised to, the same amount that is passed in
+/// [`BetType::Raised`](super::types::BetType::Raised).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct LegalActions {
+    pub can_fold: bool,
+    pub can_check: bool,
+    /// The amount needed to call, capped at the player's balance.
+    /// None if there is nothing to call.
+    pub call_amount: Option<u64>,
+    /// The smallest amount the player may raise to.
+    pub min_raise: Option<u64>,
+    /// The largest amount the player may raise to.
+    pub max_raise: Option<u64>,
+}
+
+impl LegalActions {
+    /// Whether the player may raise at all.
+    pub fn can_raise(&self) -> bool {
+        self.min_raise.is_some()
+    }
+}
+
+impl Table {
+    /// Gets the actions the user may take right now. Users who aren't
+    /// the acting player get no legal actions.
+    ///
+    /// The raise range is what [`Table::bet`] accepts: any raise above the
+    /// highest bet of at least a big blind. Raise increments aren't
+    /// enforced, so an all in for less than a full raise doesn't close
+    /// the raising either.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user isn't at the table
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    pub fn legal_actions(
+        &self,
+        user_principal: Principal,
+    ) -> Result<LegalActions, TracedError<GameError>> {
+        let balance = self
+            .users
+            .get(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
+            .balance;
+        let user_table_data = self
+            .get_user_table_data(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data for legal actions."))?;
+
+        if !self.is_game_ongoing()
+            || !self.is_players_turn(user_principal)
+            || matches!(
+                user_table_data.player_action,
+                PlayerAction::Folded | PlayerAction::AllIn | PlayerAction::SittingOut
+            )
+        {
+            return Ok(LegalActions::default());
+        }
+
+        let current_total_bet = user_table_data.current_total_bet;
+        let amount_to_call = self.highest_bet.saturating_sub(current_total_bet);
+
+        let mut legal_actions = LegalActions {
+            can_fold: true,
+            can_check: amount_to_call == 0,
+            call_amount: (amount_to_call > 0).then_some(amount_to_call.min(balance)),
+            ..Default::default()
+        };
+
+        if balance > amount_to_call {
+            if let Some((min_raise, max_raise)) = self.raise_range(current_total_bet, balance) {
+                legal_actions.min_raise = Some(min_raise);
+                legal_actions.max_raise = Some(max_raise);
+            }
+        }
+
+        Ok(legal_actions)
+    }
+
+    /// Has the user voluntarily acted in the current betting round?
+    /// Posting a blind doesn't count.
+    pub(crate) fn has_acted_this_round(&self, user_principal: Principal) -> bool {
+        self.action_logs
+            .iter()
+            .rev()
+            .take_while(|log| !matches!(log.action_type, ActionType::Stage { .. }))
+            .any(|log| {
+                log.user_principal == Some(user_principal)
+                    && matches!(
+                        log.action_type,
+                        ActionType::Call
+                            | ActionType::Check
+                            | ActionType::Raise { .. }
+                            | ActionType::AllIn { .. }
+                    )
+            })
+    }
+
+    /// The range of amounts the user may raise to for the table's game type,
+    /// or None if no raise is possible.
+    fn raise_range(&self, current_total_bet: u64, balance: u64) -> Option<(u64, u64)> {
+        let all_in = current_total_bet + balance;
+        let min_raise = (self.highest_bet + 1).max(self.big_blind);
+
+        let (min_raise, max_raise) = match self.config.game_type {
+            GameType::NoLimit(_) => (min_raise.min(all_in), all_in),
+            GameType::PotLimit(_) | GameType::PotLimitOmaha4(_) | GameType::PotLimitOmaha5(_) => {
+                let max_raise = self.get_pot().min(all_in);
+                (min_raise.min(max_raise), max_raise)
+            }
+            GameType::FixedLimit(small, big) => {
+                let amount = self.fixed_limit_raise(current_total_bet, small, big, all_in)?;
+                (amount, amount)
+            }
+            GameType::SpreadLimit(min, max) => {
+                self.spread_limit_raise_range(current_total_bet, min, max, all_in)?
+            }
+        };
+
+        (min_raise > self.highest_bet && min_raise >= self.big_blind && min_raise <= max_raise)
+            .then_some((min_raise, max_raise))
+    }
+
+    /// The smallest raise that `bet` accepts in a fixed limit game.
+    fn fixed_limit_raise(
+        &self,
+        current_total_bet: u64,
+        small: u64,
+        big: u64,
+        all_in: u64,
+    ) -> Option<u64> {
+        let candidates = match self.deal_stage {
+            DealStage::Opening | DealStage::Flop | DealStage::Turn => {
+                let mut candidates = vec![
+                    current_total_bet + small,
+                    current_total_bet + self.last_raise + small,
+                    self.highest_bet + small,
+                ];
+                if self.deal_stage == DealStage::Flop {
+                    candidates.push(current_total_bet + small + small / 2);
+                }
+                candidates
+            }
+            DealStage::River | DealStage::Showdown => vec![
+                current_total_bet + big,
+                current_total_bet + self.last_raise + big,
+            ],
+            _ => Vec::new(),
+        };
+
+        candidates
+            .into_iter()
+            .filter(|&amount| {
+                amount > self.highest_bet && amount >= self.big_blind && amount <= all_in
+            })
+            .min()
+    }
+
+    /// The range of raises that `bet` accepts in a spread limit game.
+    fn spread_limit_raise_range(
+        &self,
+        current_total_bet: u64,
+        min: u64,
+        max: u64,
+        all_in: u64,
+    ) -> Option<(u64, u64)> {
+        let lowest = (self.highest_bet + 1).max(self.big_blind);
+        [0, self.last_raise]
+            .into_iter()
+            .filter_map(|offset| {
+                let from = (current_total_bet + offset + min).max(lowest);
+                let to = (current_total_bet + offset + max).min(all_in);
+                (from <= to).then_some((from, to))
+            })
+            .reduce(|(from_a, to_a), (from_b, to_b)| (from_a.min(from_b), to_a.max(to_b)))
+    }
+}
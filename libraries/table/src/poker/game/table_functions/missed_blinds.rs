@@ -0,0 +1,343 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use super::{
+    action_log::ActionType,
+    table::{Table, TableType},
+    types::{BetType, BlindChoice, MissedBlinds, PlayerAction, SeatStatus},
+};
+
+/// The button and the blinds of a hand placed with the dead button rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindPositions {
+    /// The seat of the button, which may be empty.
+    pub dealer_seat: usize,
+    /// The seat the small blind is due from.
+    pub small_blind_seat: usize,
+    /// The player posting the small blind, `None` if it is dead.
+    pub small_blind: Option<Principal>,
+    pub big_blind_seat: usize,
+    pub big_blind: Principal,
+}
+
+impl Table {
+    /// Whether players owe the blinds they miss while sitting out.
+    /// Only cash tables track missed blinds.
+    pub fn tracks_missed_blinds(&self) -> bool {
+        self.config.enforce_missed_blinds.unwrap_or(false)
+            && matches!(self.config.table_type, None | Some(TableType::Cash))
+    }
+
+    /// Whether the user owes blinds before they are dealt in again.
+    pub fn owes_blinds(&self, user_principal: Principal) -> bool {
+        self.user_table_data
+            .get(&user_principal)
+            .and_then(|data| data.missed_blinds)
+            .is_some_and(|missed_blinds| missed_blinds.is_owed())
+    }
+
+    /// Sets how the user settles the blinds they owe. Posting deals
+    /// them in the next hand, waiting deals them in once the big
+    /// blind reaches them.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the user isn't at the table
+    /// - [`GameError::ActionNotAllowed`] if the user doesn't owe any blinds
+    pub fn set_blind_choice(
+        &mut self,
+        user_principal: Principal,
+        blind_choice: BlindChoice,
+    ) -> Result<(), TracedError<GameError>> {
+        let user_table_data = self
+            .get_user_table_data_mut(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?;
+        if !user_table_data
+            .missed_blinds
+            .is_some_and(|missed_blinds| missed_blinds.is_owed())
+        {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "No blinds are owed".to_string(),
+            })));
+        }
+        user_table_data.blind_choice = Some(blind_choice);
+        Ok(())
+    }
+
+    /// Checks that a user who owes blinds chose how to settle them
+    /// before sitting back in.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the user owes blinds and hasn't chosen
+    pub fn check_blind_choice(
+        &self,
+        user_principal: Principal,
+    ) -> Result<(), TracedError<GameError>> {
+        if !self.tracks_missed_blinds() || !self.owes_blinds(user_principal) {
+            return Ok(());
+        }
+        let has_chosen = self
+            .user_table_data
+            .get(&user_principal)
+            .is_some_and(|data| data.blind_choice.is_some());
+        if !has_chosen {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Choose to post the missed blinds or wait for the big blind".to_string(),
+            })));
+        }
+        Ok(())
+    }
+
+    /// Gets the blinds owed by the players who aren't sitting out
+    /// and how they chose to settle them.
+    pub fn owed_blinds(&self) -> Vec<(Principal, MissedBlinds, Option<BlindChoice>)> {
+        self.seats
+            .iter()
+            .filter_map(|seat| match seat {
+                SeatStatus::Occupied(user_principal) => {
+                    let data = self.user_table_data.get(user_principal)?;
+                    let missed_blinds = data.missed_blinds.filter(|missed| missed.is_owed())?;
+                    (data.player_action != PlayerAction::SittingOut).then_some((
+                        *user_principal,
+                        missed_blinds,
+                        data.blind_choice,
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// A player taking a seat once the blinds are moving owes a big
+    /// blind. Unless they choose to post it they wait for the big blind.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the player who joined.
+    pub fn mark_new_player_blinds(&mut self, user_principal: Principal) {
+        if !self.tracks_missed_blinds() || self.blind_seats.is_none() {
+            return;
+        }
+        if let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) {
+            user_table_data.missed_blinds = Some(MissedBlinds {
+                small_blind: false,
+                big_blind: true,
+            });
+            if user_table_data.player_action != PlayerAction::SittingOut {
+                user_table_data.blind_choice = Some(BlindChoice::WaitForBigBlind);
+            }
+        }
+    }
+
+    /// Works out the button and the blinds of the next hand with the
+    /// dead button rule, so every player pays each blind once an orbit
+    /// even when players leave. The big blind moves on to the next
+    /// player who isn't sitting out, the small blind is due from the seat
+    /// of the last big blind and the button moves to the seat of the
+    /// last small blind. The small blind is dead and the button can be
+    /// on an empty seat.
+    ///
+    /// Players the big blind passes while sitting out miss both blinds.
+    /// Players who owe blinds are held out of the hand unless they chose
+    /// to post them or the big blind reached them.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the blinds are placed as usual, on tables that don't
+    /// track missed blinds, before the first hand and short handed.
+    pub fn next_blind_positions(&mut self) -> Option<BlindPositions> {
+        if !self.tracks_missed_blinds() {
+            return None;
+        }
+        let players = self.players_not_sitting_out();
+        let (last_small_blind_seat, last_big_blind_seat) = self.blind_seats?;
+        if players.len() < 3 {
+            // Short handed the blinds are posted as usual and nothing is owed.
+            self.waive_missed_blinds(&players);
+            return None;
+        }
+
+        let mut big_blind = None;
+        for offset in 1..=self.seats.len() {
+            let seat = (last_big_blind_seat + offset) % self.seats.len();
+            let SeatStatus::Occupied(user_principal) = self.seats[seat] else {
+                continue;
+            };
+            let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) else {
+                continue;
+            };
+            if user_table_data.player_action == PlayerAction::SittingOut {
+                user_table_data.missed_blinds = Some(MissedBlinds {
+                    small_blind: true,
+                    big_blind: true,
+                });
+                continue;
+            }
+            big_blind = Some((seat, user_principal));
+            break;
+        }
+        let (big_blind_seat, big_blind) = big_blind?;
+        // Reaching the big blind settles anything the player owed.
+        if let Some(user_table_data) = self.user_table_data.get_mut(&big_blind) {
+            user_table_data.missed_blinds = None;
+            user_table_data.blind_choice = None;
+        }
+
+        let small_blind = match self.seats[last_big_blind_seat] {
+            SeatStatus::Occupied(user_principal) if user_principal != big_blind => {
+                self.small_blind_due_from(user_principal)
+            }
+            _ => None,
+        };
+
+        let held_players: Vec<Principal> = players
+            .iter()
+            .copied()
+            .filter(|user_principal| {
+                *user_principal != big_blind && self.is_waiting_for_big_blind(*user_principal)
+            })
+            .collect();
+        if players.len() - held_players.len() < 2 {
+            self.waive_missed_blinds(&players);
+        } else {
+            for user_principal in held_players {
+                if let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) {
+                    user_table_data.player_action = PlayerAction::Joining;
+                }
+            }
+        }
+
+        Some(BlindPositions {
+            dealer_seat: last_small_blind_seat,
+            small_blind_seat: last_big_blind_seat,
+            small_blind,
+            big_blind_seat,
+            big_blind,
+        })
+    }
+
+    /// Gets the player posting the small blind from the seat of the last
+    /// big blind. A player sitting out there misses the small blind.
+    fn small_blind_due_from(&mut self, user_principal: Principal) -> Option<Principal> {
+        let is_owing = self.owes_blinds(user_principal);
+        let user_table_data = self.user_table_data.get_mut(&user_principal)?;
+        if user_table_data.player_action == PlayerAction::SittingOut {
+            user_table_data
+                .missed_blinds
+                .get_or_insert_with(MissedBlinds::default)
+                .small_blind = true;
+            return None;
+        }
+        (!is_owing).then_some(user_principal)
+    }
+
+    /// Whether the user owes blinds and didn't choose to post them.
+    fn is_waiting_for_big_blind(&self, user_principal: Principal) -> bool {
+        self.owes_blinds(user_principal)
+            && self
+                .user_table_data
+                .get(&user_principal)
+                .is_some_and(|data| data.blind_choice != Some(BlindChoice::PostDeadBlinds))
+    }
+
+    fn players_not_sitting_out(&self) -> Vec<Principal> {
+        self.seats
+            .iter()
+            .filter_map(|seat| match seat {
+                SeatStatus::Occupied(user_principal) => Some(*user_principal),
+                _ => None,
+            })
+            .filter(|user_principal| {
+                self.user_table_data
+                    .get(user_principal)
+                    .is_some_and(|data| data.player_action != PlayerAction::SittingOut)
+            })
+            .collect()
+    }
+
+    fn waive_missed_blinds(&mut self, user_principals: &[Principal]) {
+        for user_principal in user_principals {
+            if let Some(user_table_data) = self.user_table_data.get_mut(user_principal) {
+                user_table_data.missed_blinds = None;
+                user_table_data.blind_choice = None;
+            }
+        }
+    }
+
+    /// Posts the blinds of the players who chose to pay what they owe
+    /// rather than wait. A missed big blind is posted live and a missed
+    /// small blind goes into the pot as dead money. Should be called
+    /// after the small blind and before the big blind are posted.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if a player is not found
+    pub fn post_owed_blinds(&mut self) -> Result<(), TracedError<GameError>> {
+        let posting_players: Vec<(Principal, MissedBlinds)> = self
+            .seats
+            .iter()
+            .filter_map(|seat| match seat {
+                SeatStatus::Occupied(user_principal) => {
+                    let data = self.user_table_data.get(user_principal)?;
+                    let missed_blinds = data.missed_blinds.filter(|missed| missed.is_owed())?;
+                    (data.blind_choice == Some(BlindChoice::PostDeadBlinds)
+                        && data.player_action == PlayerAction::None)
+                        .then_some((*user_principal, missed_blinds))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (user_principal, missed_blinds) in posting_players {
+            if missed_blinds.big_blind {
+                self.bet(user_principal, BetType::BigBlind)
+                    .map_err(|e| trace_err!(e, "Failed to post missed big blind."))?;
+            }
+            if missed_blinds.small_blind {
+                let user = self
+                    .users
+                    .get_mut(&user_principal)
+                    .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
+                let amount = self.small_blind.min(user.balance);
+                if amount > 0 {
+                    user.balance -= amount;
+                    self.pot += amount;
+                    self.log_action(Some(user_principal), ActionType::DeadBlind { amount });
+                }
+            }
+            if let Some(user_table_data) = self.user_table_data.get_mut(&user_principal) {
+                user_table_data.missed_blinds = None;
+                user_table_data.blind_choice = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the index of the player who acts first preflop when the
+    /// blinds were placed with the dead button rule, the first active
+    /// player after the big blind.
+    pub(crate) fn calculate_dead_button_starting_player_index(&self) -> Option<usize> {
+        if !self.tracks_missed_blinds() || self.get_playing_users().ok()? < 3 {
+            return None;
+        }
+        let big_blind_index = self.get_seat_index(self.big_blind_user_principal)? as usize;
+        (1..self.seats.len())
+            .map(|offset| (big_blind_index + offset) % self.seats.len())
+            .find(|&index| match self.seats[index] {
+                SeatStatus::Occupied(principal) => self
+                    .get_user_table_data(principal)
+                    .map(|data| {
+                        !matches!(
+                            data.player_action,
+                            PlayerAction::SittingOut
+                                | PlayerAction::Joining
+                                | PlayerAction::Folded
+                                | PlayerAction::AllIn
+                        )
+                    })
+                    .unwrap_or(false),
+                _ => false,
+            })
+    }
+}
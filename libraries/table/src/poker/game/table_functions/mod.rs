@@ -10,26 +10,58 @@ pub mod all_in_util_functions;
 
 pub mod ante;
 
+pub mod auto_top_up;
+
 pub mod bet;
 
+pub mod board_runs;
+
+pub mod bot;
+
+pub mod buy_in;
+
+pub mod card_encryption;
+
 pub mod check;
 
+pub mod chip_audit;
+
 pub mod deal_functions;
 
+pub mod elimination;
+
+pub mod equity;
+
+pub mod fast_fold;
+
 pub mod fold;
 
+pub mod legal_actions;
+
+pub mod missed_blinds;
+
 pub mod pot;
 
+pub mod pre_action;
+
+pub mod provably_fair;
+
 pub mod rake;
 
+pub mod seat_reservation;
+
 pub mod showdown;
 
 pub mod side_pot;
 
 pub mod sitting_out;
 
+pub mod straddle;
+
 pub mod table;
 
+pub mod time_bank;
+
 pub mod timer;
 
 pub mod turn;
@@ -0,0 +1,185 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use super::{
+    table::Table,
+    types::{BetType, PlayerAction, PreAction, QueuedPreAction, SeatStatus},
+};
+
+impl Table {
+    /// Queues the action the user takes once it is their turn, or
+    /// clears it. A queued action only lasts for the current betting
+    /// round.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    /// - `pre_action` - The action to queue, `None` clears it.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the user isn't waiting to act
+    ///   or the action can't be taken in the current situation
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    pub fn set_pre_action(
+        &mut self,
+        user_principal: Principal,
+        pre_action: Option<PreAction>,
+    ) -> Result<(), TracedError<GameError>> {
+        let Some(pre_action) = pre_action else {
+            self.get_user_table_data_mut(user_principal)
+                .map_err(|e| trace_err!(e, "Failed to get user table data"))?
+                .pre_action = None;
+            return Ok(());
+        };
+
+        let user_table_data = self
+            .get_user_table_data(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?;
+        if !self.is_game_ongoing()
+            || matches!(
+                user_table_data.player_action,
+                PlayerAction::Folded
+                    | PlayerAction::AllIn
+                    | PlayerAction::SittingOut
+                    | PlayerAction::Joining
+            )
+        {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "User is not in the hand".to_string(),
+            })));
+        }
+        if self.is_players_turn(user_principal) {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "It is already the user's turn".to_string(),
+            })));
+        }
+
+        let amount_to_call = self.amount_to_call(user_principal)?;
+        match pre_action {
+            PreAction::Check if amount_to_call > 0 => {
+                return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "There is a bet to call".to_string(),
+                })));
+            }
+            PreAction::Call(amount) if amount != amount_to_call => {
+                return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "The amount doesn't match the bet to call".to_string(),
+                })));
+            }
+            PreAction::RaiseTo(amount) if amount <= self.highest_bet => {
+                return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                    reason: "A raise has to be more than the highest bet".to_string(),
+                })));
+            }
+            _ => {}
+        }
+
+        let highest_bet = self.highest_bet;
+        self.get_user_table_data_mut(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?
+            .pre_action = Some(QueuedPreAction {
+            pre_action,
+            highest_bet,
+        });
+        Ok(())
+    }
+
+    /// The amount the user needs to call, capped at their balance.
+    fn amount_to_call(&self, user_principal: Principal) -> Result<u64, TracedError<GameError>> {
+        let balance = self
+            .users
+            .get(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
+            .balance;
+        let current_total_bet = self.get_user_table_data(user_principal)?.current_total_bet;
+        Ok(self
+            .highest_bet
+            .saturating_sub(current_total_bet)
+            .min(balance))
+    }
+
+    /// The total a pot sized raise goes to, the call plus everything
+    /// in the middle once the call is made.
+    fn pot_sized_raise(&self, amount_to_call: u64) -> u64 {
+        let side_pots: u64 = self.side_pots.iter().map(|side_pot| side_pot.pot).sum();
+        self.highest_bet + self.pot + side_pots + self.get_pot() + amount_to_call
+    }
+
+    /// Takes the action queued by the acting player. A pre action
+    /// that no longer fits the situation, like a call after the bet
+    /// to call went up, is dropped and the player acts themselves.
+    ///
+    /// # Returns
+    ///
+    /// Whether a pre action was taken.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the queued action fails
+    pub(crate) fn execute_pre_action(&mut self) -> Result<bool, TracedError<GameError>> {
+        let SeatStatus::Occupied(user_principal) = self.seats[self.current_player_index] else {
+            return Ok(false);
+        };
+        // A player who already matched the highest bet isn't acting again
+        // this round and nobody acts once the hand is decided. The pre
+        // action is cleared with the next stage.
+        let needs_to_act = !self.has_acted_this_round(user_principal)
+            || !self.is_users_current_total_bet_equal_to_highest_bet(user_principal);
+        if !needs_to_act
+            || self
+                .all_in_cycle_to_showdown()
+                .map_err(|e| trace_err!(e, "Failed to check for showdown in pre action."))?
+        {
+            return Ok(false);
+        }
+        let Some(queued) = self
+            .user_table_data
+            .get_mut(&user_principal)
+            .and_then(|data| data.pre_action.take())
+        else {
+            return Ok(false);
+        };
+
+        let legal_actions = self
+            .legal_actions(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get legal actions for pre action."))?;
+        let is_bet_unchanged = self.highest_bet == queued.highest_bet;
+
+        let result = match queued.pre_action {
+            PreAction::CheckFold => self.check_fold(user_principal),
+            PreAction::CallAny if legal_actions.can_check => self.user_check(user_principal, false),
+            PreAction::CallAny => self.bet(user_principal, BetType::Called),
+            PreAction::Check if is_bet_unchanged && legal_actions.can_check => {
+                self.user_check(user_principal, false)
+            }
+            PreAction::Call(amount)
+                if is_bet_unchanged && legal_actions.call_amount == Some(amount) =>
+            {
+                self.bet(user_principal, BetType::Called)
+            }
+            PreAction::RaiseTo(amount)
+                if is_bet_unchanged
+                    && legal_actions
+                        .min_raise
+                        .zip(legal_actions.max_raise)
+                        .is_some_and(|(min, max)| (min..=max).contains(&amount)) =>
+            {
+                self.bet(user_principal, BetType::Raised(amount))
+            }
+            PreAction::BetPot if is_bet_unchanged && legal_actions.can_raise() => {
+                let (min, max) = legal_actions
+                    .min_raise
+                    .zip(legal_actions.max_raise)
+                    .unwrap_or_default();
+                let amount = self
+                    .pot_sized_raise(legal_actions.call_amount.unwrap_or(0))
+                    .clamp(min, max);
+                self.bet(user_principal, BetType::Raised(amount))
+            }
+            _ => return Ok(false),
+        };
+        result.map_err(|e| trace_err!(e, "Failed to take pre action."))?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,125 @@
+use candid::CandidType;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+use serde::{Deserialize, Serialize};
+
+use crate::poker::core::{shuffle_seed, Deck, FlatDeck};
+
+use super::table::Table;
+
+/// The number of revealed hands kept around for verification.
+const MAX_VERIFIABLE_HANDS: usize = 100;
+
+/// Everything needed to check that the deck of a finished hand
+/// matches the commitment published before the cards were dealt.
+#[derive(Debug, Clone, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct HandVerification {
+    /// The hand number, the table's `round_ticker` when it was dealt.
+    pub hand_id: u64,
+    /// The 32 byte seed the deck was shuffled with.
+    pub seed: Vec<u8>,
+    /// The SHA-256 commitment to the seed and the deck order.
+    pub commitment: String,
+    pub is_short_deck: bool,
+}
+
+impl HandVerification {
+    /// Recomputes the deck of the hand, in the order it was in
+    /// before any card was dealt. Cards are dealt from the end.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the seed isn't 32 bytes
+    pub fn deck(&self) -> Result<FlatDeck, TracedError<GameError>> {
+        let seed: [u8; 32] = self.seed.clone().try_into().map_err(|_| {
+            trace_err!(TracedError::new(GameError::Other(
+                "Seed must be 32 bytes".to_string()
+            )))
+        })?;
+        let deck = if self.is_short_deck {
+            Deck::short_deck()
+        } else {
+            Deck::default()
+        };
+        Ok(FlatDeck::from_seed(&seed, deck))
+    }
+
+    /// Checks that the revealed seed reproduces the committed deck.
+    pub fn verify(&self) -> bool {
+        match (self.deck(), self.seed.clone().try_into()) {
+            (Ok(deck), Ok(seed)) => deck.commitment(&seed) == self.commitment,
+            _ => false,
+        }
+    }
+}
+
+impl Table {
+    /// Shuffles a new deck for the next hand and commits to it.
+    ///
+    /// The seed is derived from the random `bytes` and kept secret
+    /// until the hand is over, only the commitment is public.
+    pub fn shuffle_and_commit_deck(&mut self, bytes: Vec<u8>) {
+        self.commit_deck(shuffle_seed(&bytes));
+    }
+
+    /// Shuffles a new deck for the next hand with a known seed and
+    /// commits to it. Used to replay a hand that was already played.
+    pub fn commit_deck(&mut self, seed: [u8; 32]) {
+        // Make sure the previous hand can still be verified if it
+        // never made it to showdown.
+        self.reveal_deck_seed();
+
+        self.deck = self.config.new_deck(&seed);
+        self.deck_commitment = Some(self.deck.commitment(&seed));
+        self.deck_seed = Some(seed.to_vec());
+        self.deck_hand_id = Some(self.round_ticker);
+    }
+
+    /// Reveals the seed of the current hand so it can be verified.
+    /// Should only be called once no more cards will be dealt.
+    pub fn reveal_deck_seed(&mut self) {
+        let (Some(seed), Some(commitment), Some(hand_id)) = (
+            self.deck_seed.take(),
+            self.deck_commitment.clone(),
+            self.deck_hand_id,
+        ) else {
+            return;
+        };
+
+        self.revealed_hands.push(HandVerification {
+            hand_id,
+            seed,
+            commitment,
+            is_short_deck: self.config.uses_short_deck(),
+        });
+        if self.revealed_hands.len() > MAX_VERIFIABLE_HANDS {
+            self.revealed_hands.remove(0);
+        }
+    }
+
+    /// Gets the revealed seed and commitment of a finished hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the hand hasn't finished yet
+    /// - [`GameError::Other`] if the hand can't be found
+    pub fn verify_hand(&self, hand_id: u64) -> Result<HandVerification, TracedError<GameError>> {
+        if let Some(verification) = self
+            .revealed_hands
+            .iter()
+            .find(|verification| verification.hand_id == hand_id)
+        {
+            return Ok(verification.clone());
+        }
+
+        if self.deck_seed.is_some() && self.deck_hand_id == Some(hand_id) {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Hand is still in progress".to_string(),
+            })));
+        }
+
+        Err(trace_err!(TracedError::new(GameError::Other(format!(
+            "Hand {} not found",
+            hand_id
+        )))))
+    }
+}
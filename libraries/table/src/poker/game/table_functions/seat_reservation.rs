@@ -0,0 +1,96 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use super::{
+    table::Table,
+    types::{NotificationMessage, SeatStatus},
+};
+
+/// How long a seat offered to the head of a waitlist is held for them.
+pub const SEAT_RESERVATION_SECONDS: u64 = 60;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+impl Table {
+    /// Holds a free seat for a user coming off the waitlist and lets
+    /// them know it is available.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    /// - `now` - The current time in nanoseconds.
+    ///
+    /// # Returns
+    ///
+    /// The index of the reserved seat.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::UserAlreadyInGame`] if the user already has a seat
+    /// - [`GameError::GameFull`] if there is no free seat
+    pub fn reserve_seat(
+        &mut self,
+        user_principal: Principal,
+        now: u64,
+    ) -> Result<u8, TracedError<GameError>> {
+        if self.is_user_in_table(user_principal) {
+            return Err(trace_err!(TracedError::new(GameError::UserAlreadyInGame)));
+        }
+        let seat_index = self
+            .get_free_seat_index()
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::GameFull)))?;
+
+        self.seats[seat_index as usize] = SeatStatus::Reserved {
+            principal: user_principal,
+            timestamp: now,
+        };
+        self.notifications.add_notification(
+            user_principal,
+            NotificationMessage::SeatAvailable {
+                seat_index,
+                expires_at: now + SEAT_RESERVATION_SECONDS * NANOS_PER_SECOND,
+            },
+        );
+        Ok(seat_index)
+    }
+
+    /// Gets the seat held for the user, if any.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user.
+    pub fn reserved_seat(&self, user_principal: Principal) -> Option<u8> {
+        self.seats
+            .iter()
+            .position(|seat| {
+                matches!(seat, SeatStatus::Reserved { principal, .. } if *principal == user_principal)
+            })
+            .map(|seat_index| seat_index as u8)
+    }
+
+    /// Frees the seats whose reservation ran out.
+    ///
+    /// # Parameters
+    ///
+    /// - `now` - The current time in nanoseconds.
+    ///
+    /// # Returns
+    ///
+    /// The users who didn't take their seat in time.
+    pub fn release_expired_reservations(&mut self, now: u64) -> Vec<Principal> {
+        let mut released = Vec::new();
+        for seat in self.seats.iter_mut() {
+            if let SeatStatus::Reserved {
+                principal,
+                timestamp,
+            } = seat
+            {
+                if now >= *timestamp + SEAT_RESERVATION_SECONDS * NANOS_PER_SECOND {
+                    released.push(*principal);
+                    *seat = SeatStatus::Empty;
+                }
+            }
+        }
+        released
+    }
+}
@@ -4,32 +4,58 @@ use candid::Principal;
 use errors::{game_error::GameError, trace_err, traced_error::TracedError};
 
 use crate::poker::{
-    core::{Card, Hand, Rank, Rankable},
+    core::{rank_low, rank_omaha, rank_omaha_low, Card, Hand, LowRank, Rank, Rankable},
     game::types::UserCards,
 };
 
 use super::{
     action_log::ActionType,
+    board_runs::split_across_boards,
+    elimination::PotShares,
     rake::Rake,
     table::Table,
     types::{CurrencyType, PlayerAction, SeatStatus},
 };
 
 type RankedHand = (Principal, Hand, Rank, Vec<Card>);
+type LowRankedHand = (Principal, LowRank);
+type LogHands = Vec<(String, Vec<Card>, u64)>;
 
 impl Table {
     /// Compares the hands of the players to determine the winner
     ///
+    /// On hi/lo tables every pot is split between the best high hand
+    /// and the best qualifying low hand. When the board was run more
+    /// than once every pot is split evenly across the boards.
+    ///
     /// # Errors
     ///
     /// - [`GameError::Other`] if the user table data cannot be retrieved
     /// - [`GameError::PlayerNotFound`] if retrieving a player fails
     pub fn showdown(&mut self) -> Result<(), TracedError<GameError>> {
-        let ranked_hands = self
-            .get_ranked_hands()
-            .map_err(|e| trace_err!(e, "Failed to get ranked hands in showdown."))?;
+        let mut board_hands = Vec::new();
+        for board in self.boards() {
+            let ranked_hands = self
+                .get_ranked_hands(&board)
+                .map_err(|e| trace_err!(e, "Failed to get ranked hands in showdown."))?;
+            let low_ranked_hands = self
+                .get_low_ranked_hands(&ranked_hands, &board)
+                .map_err(|e| trace_err!(e, "Failed to get low ranked hands in showdown."))?;
+            board_hands.push((ranked_hands, low_ranked_hands));
+        }
+        let ranked_hands = board_hands
+            .first()
+            .map(|(ranked_hands, _)| ranked_hands.clone())
+            .unwrap_or_default();
+        // Odd chips are handed out relative to the button of the hand being played.
+        let button = self.dealer_position;
+        let rake_before = self.rake_total.unwrap_or(0);
+
+        // No more cards will be dealt this hand so the deck can be verified.
+        self.reveal_deck_seed();
 
         let mut winners_total_amount: HashMap<Principal, u64> = HashMap::new();
+        let mut pot_shares: Vec<PotShares> = Vec::new();
         self.log_action(
             None,
             ActionType::Stage {
@@ -50,14 +76,6 @@ impl Table {
 
         // Distribute the side pots
         for pot in self.side_pots.clone().iter_mut() {
-            let inner_ranked_hands = ranked_hands.clone();
-            let inner_ranked_hands = inner_ranked_hands
-                .iter()
-                .filter(|(user_principal, _, _, _)| pot.user_principals.contains(user_principal))
-                .collect::<Vec<_>>();
-
-            let mut individual_shares: HashMap<Principal, u64> = HashMap::new();
-
             if let Some(enable_rake) = self.config.enable_rake {
                 if enable_rake {
                     if let CurrencyType::Real(currency) = &self.config.currency_type {
@@ -81,56 +99,55 @@ impl Table {
                 }
             }
 
-            let inner_rank_hands_clone = inner_ranked_hands.clone();
-
-            if let Some((_, _, first_rank, _)) = inner_ranked_hands.first() {
-                let tied_users: Vec<Principal> = inner_ranked_hands
+            let board_amounts = split_across_boards(pot.confirmed_pot, board_hands.len());
+            for (amount, (ranked_hands, low_ranked_hands)) in
+                board_amounts.into_iter().zip(board_hands.iter())
+            {
+                let inner_ranked_hands = ranked_hands
                     .iter()
-                    .filter(|(_, _, rank, _)| rank == first_rank)
-                    .map(|(user_principal, _, _, _)| *user_principal)
-                    .collect();
-
-                if !tied_users.is_empty() {
-                    let individual_share = pot.confirmed_pot / tied_users.len() as u64;
-
-                    for user in &tied_users {
-                        self.users
-                            .get_mut(user)
-                            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
-                            .deposit(individual_share);
-                        *winners_total_amount.entry(*user).or_insert(0) += individual_share;
-                        individual_shares.insert(*user, individual_share);
-                    }
-                }
-            }
-
-            let mut log_hands = Vec::new();
-            for (user_principal, _, _, cards) in inner_rank_hands_clone.iter() {
-                let user = self
-                    .users
-                    .get(user_principal)
-                    .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
-                let amount_won = *individual_shares.get(user_principal).unwrap_or(&0);
-                log_hands.push((user.user_name.clone(), cards.clone(), amount_won));
-            }
-
-            if self.all_players_folded() {
-                // If all players except one folded remove all cards from log hands.
-                // This is for ensuring correct mucking of cards in the frontend.
-                log_hands = log_hands
+                    .filter(|(user_principal, _, _, _)| {
+                        pot.user_principals.contains(user_principal)
+                    })
+                    .collect::<Vec<_>>();
+                let inner_low_ranked_hands = low_ranked_hands
                     .iter()
-                    .map(|(user_name, _, amount_won)| (user_name.clone(), vec![], *amount_won))
-                    .collect();
+                    .filter(|(user_principal, _)| pot.user_principals.contains(user_principal))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let (high_shares, low_shares) =
+                    self.split_pot(amount, &inner_ranked_hands, &inner_low_ranked_hands, button);
+
+                let mut shares = HashMap::new();
+                for (user, share) in high_shares.iter().chain(low_shares.iter()) {
+                    self.users
+                        .get_mut(user)
+                        .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
+                        .deposit(*share);
+                    *winners_total_amount.entry(*user).or_insert(0) += *share;
+                    *shares.entry(*user).or_insert(0) += *share;
+                }
+                pot_shares.push((pot.user_principals.clone(), shares));
+
+                let (log_hands, low_log_hands) = self
+                    .get_log_hands(
+                        &inner_ranked_hands,
+                        &inner_low_ranked_hands,
+                        &high_shares,
+                        &low_shares,
+                    )
+                    .map_err(|e| trace_err!(e, "Failed to get log hands for side pot."))?;
+
+                self.log_action(
+                    None,
+                    ActionType::PlayersHandsRankedSidePot {
+                        hands: log_hands,
+                        low_hands: low_log_hands,
+                    },
+                );
             }
-
-            self.log_action(
-                None,
-                ActionType::PlayersHandsRankedSidePot { hands: log_hands },
-            );
         }
 
-        let mut individual_shares: HashMap<Principal, u64> = HashMap::new();
-
         if let Some(enable_rake) = self.config.enable_rake {
             if enable_rake {
                 if let CurrencyType::Real(currency) = &self.config.currency_type {
@@ -155,76 +172,273 @@ impl Table {
         }
 
         // Distribute the main pot
-        if let Some((_, _, first_rank, _)) = ranked_hands.first() {
-            let tied_users: Vec<Principal> = ranked_hands
+        let mut high_shares: HashMap<Principal, u64> = HashMap::new();
+        let mut low_shares: HashMap<Principal, u64> = HashMap::new();
+        let mut board_log_hands = Vec::new();
+        let board_amounts = split_across_boards(self.pot, board_hands.len());
+        for (amount, (ranked_hands, low_ranked_hands)) in
+            board_amounts.into_iter().zip(board_hands.iter())
+        {
+            let main_pot_ranked_hands = ranked_hands.iter().collect::<Vec<_>>();
+            let (board_high_shares, board_low_shares) =
+                self.split_pot(amount, &main_pot_ranked_hands, low_ranked_hands, button);
+
+            board_log_hands.push(
+                self.get_log_hands(
+                    &main_pot_ranked_hands,
+                    low_ranked_hands,
+                    &board_high_shares,
+                    &board_low_shares,
+                )
+                .map_err(|e| trace_err!(e, "Failed to get log hands for main pot."))?,
+            );
+
+            for (user, share) in board_high_shares {
+                *high_shares.entry(user).or_insert(0) += share;
+            }
+            for (user, share) in board_low_shares {
+                *low_shares.entry(user).or_insert(0) += share;
+            }
+        }
+
+        let mut main_pot_shares = high_shares.clone();
+        for (user, share) in low_shares.iter() {
+            *main_pot_shares.entry(*user).or_insert(0) += share;
+        }
+        pot_shares.push((
+            ranked_hands
                 .iter()
-                .filter(|(_, _, rank, _)| rank == first_rank)
                 .map(|(user_principal, _, _, _)| *user_principal)
-                .collect();
-
-            if !tied_users.is_empty() {
-                let mut winners = Vec::new();
-                let individual_share = self.pot / tied_users.len() as u64;
+                .collect(),
+            main_pot_shares,
+        ));
+
+        if !high_shares.is_empty() {
+            let mut winners = Vec::new();
+
+            // Keep the winners in rank order, a player scooping both halves is only added once.
+            for (user_principal, _, _, _) in ranked_hands.iter() {
+                let share = high_shares.get(user_principal).unwrap_or(&0)
+                    + low_shares.get(user_principal).unwrap_or(&0);
+                if !high_shares.contains_key(user_principal)
+                    && !low_shares.contains_key(user_principal)
+                {
+                    continue;
+                }
 
-                for user in &tied_users {
-                    let user = {
-                        let user = self.users.get_mut(user).ok_or_else(|| {
-                            trace_err!(TracedError::new(GameError::PlayerNotFound))
-                        })?;
-                        user.deposit(individual_share);
-                        user.clone()
-                    };
+                let user = {
+                    let user = self
+                        .users
+                        .get_mut(user_principal)
+                        .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
+                    user.deposit(share);
+                    user.clone()
+                };
 
-                    *winners_total_amount.entry(user.principal_id).or_insert(0) += individual_share;
-                    individual_shares.insert(user.principal_id, individual_share);
+                *winners_total_amount.entry(user.principal_id).or_insert(0) += share;
+                winners.push(user);
+            }
 
-                    winners.push(user.clone());
+            if self.config.is_hi_lo.unwrap_or(false) || board_log_hands.len() > 1 {
+                for (log_hands, low_log_hands) in board_log_hands {
+                    self.log_action(
+                        None,
+                        ActionType::PlayersHandsRankedMainPot {
+                            hands: log_hands,
+                            low_hands: low_log_hands,
+                        },
+                    );
                 }
-                // Handle ties according to your game's rules
-                self.winners = Some(winners.clone());
-                self.set_sorted_users(winners_total_amount)
-                    .map_err(|e| trace_err!(e, "Failed to set sorted users."))?;
-                self.pot = 0;
-                self.side_pots.clear();
-
-                #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+            }
+
+            self.record_eliminations(&pot_shares);
+            let rake = self.rake_total.unwrap_or(0).saturating_sub(rake_before);
+            self.log_hand_finished(&winners_total_amount, rake);
+            self.finish_chip_audit(rake);
+
+            // Handle ties according to your game's rules
+            self.winners = Some(winners.clone());
+            self.set_sorted_users(winners_total_amount)
+                .map_err(|e| trace_err!(e, "Failed to set sorted users."))?;
+            self.pot = 0;
+            self.side_pots.clear();
+            self.queue_auto_top_ups();
+
+            #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+            {
+                self.handle_queued_deposits();
                 self.start_next_turn_timer(self.config.auto_start_timer.into());
-                return Ok(());
             }
+            return Ok(());
+        }
+
+        for (log_hands, low_log_hands) in board_log_hands {
+            self.log_action(
+                None,
+                ActionType::PlayersHandsRankedMainPot {
+                    hands: log_hands,
+                    low_hands: low_log_hands,
+                },
+            );
         }
 
+        self.record_eliminations(&pot_shares);
+        let rake = self.rake_total.unwrap_or(0).saturating_sub(rake_before);
+        self.log_hand_finished(&winners_total_amount, rake);
+        self.finish_chip_audit(rake);
+
+        self.set_sorted_users(winners_total_amount)
+            .map_err(|e| trace_err!(e, "Failed to set sorted users."))?;
+        self.pot = 0;
+        self.side_pots.clear();
+        self.queue_auto_top_ups();
+
+        #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+        {
+            self.handle_queued_deposits();
+            self.start_next_turn_timer(self.config.auto_start_timer.into());
+        }
+        Ok(())
+    }
+
+    /// Splits a pot between the best high hands and, on hi/lo tables,
+    /// the best qualifying low hands.
+    ///
+    /// The high half gets the odd chip. If no one qualifies for the low
+    /// the high hands take the whole pot.
+    ///
+    /// # Returns
+    ///
+    /// The high and the low shares of each winning player.
+    fn split_pot(
+        &self,
+        amount: u64,
+        ranked_hands: &[&RankedHand],
+        low_ranked_hands: &[LowRankedHand],
+        button: usize,
+    ) -> (HashMap<Principal, u64>, HashMap<Principal, u64>) {
+        let high_winners: Vec<Principal> = match ranked_hands.first() {
+            Some((_, _, first_rank, _)) => ranked_hands
+                .iter()
+                .filter(|(_, _, rank, _)| rank == first_rank)
+                .map(|(user_principal, _, _, _)| *user_principal)
+                .collect(),
+            None => return (HashMap::new(), HashMap::new()),
+        };
+
+        let low_winners: Vec<Principal> = match low_ranked_hands.first() {
+            Some((_, first_low_rank)) => low_ranked_hands
+                .iter()
+                .filter(|(_, low_rank)| low_rank == first_low_rank)
+                .map(|(user_principal, _)| *user_principal)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        if low_winners.is_empty() {
+            return (
+                self.split_between(amount, &high_winners, button),
+                HashMap::new(),
+            );
+        }
+
+        let low_amount = amount / 2;
+        let high_amount = amount - low_amount;
+        (
+            self.split_between(high_amount, &high_winners, button),
+            self.split_between(low_amount, &low_winners, button),
+        )
+    }
+
+    /// Splits an amount evenly between the winners. Chips that can't be
+    /// split evenly go one at a time to the winners closest to the left
+    /// of the button.
+    fn split_between(
+        &self,
+        amount: u64,
+        winners: &[Principal],
+        button: usize,
+    ) -> HashMap<Principal, u64> {
+        let mut shares = HashMap::new();
+        if winners.is_empty() {
+            return shares;
+        }
+
+        let seat_count = self.seats.len().max(button + 1);
+        let mut ordered_winners = winners.to_vec();
+        ordered_winners.sort_by_key(|winner| {
+            self.get_seat_index(*winner).map_or(usize::MAX, |seat| {
+                (seat as usize + seat_count - button - 1) % seat_count
+            })
+        });
+
+        let share = amount / ordered_winners.len() as u64;
+        let mut odd_chips = amount % ordered_winners.len() as u64;
+        for winner in ordered_winners {
+            let mut winner_share = share;
+            if odd_chips > 0 {
+                winner_share += 1;
+                odd_chips -= 1;
+            }
+            *shares.entry(winner).or_insert(0) += winner_share;
+        }
+        shares
+    }
+
+    /// Builds the high and low hands for the pot's action log.
+    /// The low hands are only logged on hi/lo tables.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if retrieving a player fails
+    fn get_log_hands(
+        &self,
+        ranked_hands: &[&RankedHand],
+        low_ranked_hands: &[LowRankedHand],
+        high_shares: &HashMap<Principal, u64>,
+        low_shares: &HashMap<Principal, u64>,
+    ) -> Result<(LogHands, Option<LogHands>), TracedError<GameError>> {
+        let all_players_folded = self.all_players_folded();
+
         let mut log_hands = Vec::new();
         for (user_principal, _, _, cards) in ranked_hands.iter() {
             let user = self
                 .users
                 .get(user_principal)
                 .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
-            let amount_won = *individual_shares.get(user_principal).unwrap_or(&0);
-            log_hands.push((user.user_name.clone(), cards.clone(), amount_won));
-        }
-
-        if self.all_players_folded() {
+            let amount_won = *high_shares.get(user_principal).unwrap_or(&0);
             // If all players except one folded remove all cards from log hands.
             // This is for ensuring correct mucking of cards in the frontend.
-            log_hands = log_hands
-                .iter()
-                .map(|(user_name, _, amount_won)| (user_name.clone(), vec![], *amount_won))
-                .collect();
+            let cards = if all_players_folded {
+                vec![]
+            } else {
+                cards.clone()
+            };
+            log_hands.push((user.user_name.clone(), cards, amount_won));
         }
 
-        self.log_action(
-            None,
-            ActionType::PlayersHandsRankedMainPot { hands: log_hands },
-        );
+        if !self.config.is_hi_lo.unwrap_or(false) {
+            return Ok((log_hands, None));
+        }
 
-        self.set_sorted_users(winners_total_amount)
-            .map_err(|e| trace_err!(e, "Failed to set sorted users."))?;
-        self.pot = 0;
-        self.side_pots.clear();
+        let mut low_log_hands = Vec::new();
+        for (user_principal, _) in low_ranked_hands.iter() {
+            let user = self
+                .users
+                .get(user_principal)
+                .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
+            let cards = match ranked_hands
+                .iter()
+                .find(|(principal, _, _, _)| principal == user_principal)
+            {
+                Some((_, _, _, cards)) if !all_players_folded => cards.clone(),
+                _ => vec![],
+            };
+            let amount_won = *low_shares.get(user_principal).unwrap_or(&0);
+            low_log_hands.push((user.user_name.clone(), cards, amount_won));
+        }
 
-        #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
-        self.start_next_turn_timer(self.config.auto_start_timer.into());
-        Ok(())
+        Ok((log_hands, Some(low_log_hands)))
     }
 
     /// Checks if the side pots have been confirmed and if not, confirms them
@@ -251,7 +465,7 @@ impl Table {
         winners_total_amount: HashMap<Principal, u64>,
     ) -> Result<(), TracedError<GameError>> {
         let ranked_hands = self
-            .get_ranked_hands()
+            .get_ranked_hands(&self.community_cards)
             .map_err(|e| trace_err!(e, "Failed to ger ranked hands in set sorted users."))?;
         self.sorted_users = Some(
             ranked_hands
@@ -265,25 +479,24 @@ impl Table {
         Ok(())
     }
 
-    /// Gets the ranked hands of the players sorted by each player's rank
+    /// Gets the ranked hands of the players on the given board
+    /// sorted by each player's rank
     ///
     /// # Errors
     ///
     /// - [`GameError::Other`] if the user table data cannot be retrieved
     /// - [`GameError::PlayerNotFound`] if retrieving a player fails
-    fn get_ranked_hands(&mut self) -> Result<Vec<RankedHand>, TracedError<GameError>> {
+    fn get_ranked_hands(&self, board: &[Card]) -> Result<Vec<RankedHand>, TracedError<GameError>> {
         let mut ranked_hands: Vec<(Principal, Hand, Rank, Vec<Card>)> = Vec::new();
 
         for user_principal in self.seats.iter() {
             if let SeatStatus::Occupied(user_principal) = user_principal {
                 let user_table_data =
-                    self.user_table_data
-                        .get_mut(user_principal)
-                        .ok_or_else(|| {
-                            trace_err!(TracedError::new(GameError::Other(
-                                "Could not get users table data".to_string(),
-                            )))
-                        })?;
+                    self.user_table_data.get(user_principal).ok_or_else(|| {
+                        trace_err!(TracedError::new(GameError::Other(
+                            "Could not get users table data".to_string(),
+                        )))
+                    })?;
                 if user_table_data.player_action == PlayerAction::Folded
                     || user_table_data.player_action == PlayerAction::SittingOut
                     || user_table_data.player_action == PlayerAction::Joining
@@ -293,18 +506,65 @@ impl Table {
 
                 let user = self
                     .users
-                    .get_mut(user_principal)
+                    .get(user_principal)
                     .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
                 let mut all_cards = user_table_data.cards.clone();
-                all_cards.extend(self.community_cards.clone());
+                all_cards.extend_from_slice(board);
                 let hand = Hand::new_with_cards(all_cards.clone());
-                let rank = hand.rank();
+                let rank = if self.config.game_type.is_omaha() {
+                    rank_omaha(&user_table_data.cards, board)
+                } else if self.config.uses_short_deck() {
+                    hand.rank_short_deck()
+                } else {
+                    hand.rank()
+                };
                 ranked_hands.push((user.principal_id, hand, rank, all_cards));
             }
         }
 
         // Sort ranked hands by their rank
-        ranked_hands.sort_by(|a, b| b.2.cmp(&a.2)); // Descending order
+        if self.config.uses_short_deck() {
+            ranked_hands.sort_by(|a, b| b.2.cmp_short_deck(&a.2)); // Descending order
+        } else {
+            ranked_hands.sort_by(|a, b| b.2.cmp(&a.2)); // Descending order
+        }
         Ok(ranked_hands)
     }
+
+    /// Gets the qualifying low hands of the players still in the hand,
+    /// sorted from the best low to the worst.
+    /// Always empty unless the table is hi/lo.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    fn get_low_ranked_hands(
+        &self,
+        ranked_hands: &[RankedHand],
+        board: &[Card],
+    ) -> Result<Vec<LowRankedHand>, TracedError<GameError>> {
+        let mut low_ranked_hands = Vec::new();
+        if !self.config.is_hi_lo.unwrap_or(false) {
+            return Ok(low_ranked_hands);
+        }
+
+        for (user_principal, _, _, _) in ranked_hands.iter() {
+            let user_table_data = self
+                .get_user_table_data(*user_principal)
+                .map_err(|e| trace_err!(e, "Failed to get user table data for low hand."))?;
+            let low_rank = if self.config.game_type.is_omaha() {
+                rank_omaha_low(&user_table_data.cards, board)
+            } else {
+                let mut all_cards = user_table_data.cards.clone();
+                all_cards.extend_from_slice(board);
+                rank_low(&all_cards)
+            };
+            if let Some(low_rank) = low_rank {
+                low_ranked_hands.push((*user_principal, low_rank));
+            }
+        }
+
+        low_ranked_hands.sort_by(|a, b| b.1.cmp(&a.1)); // Best low first
+        Ok(low_ranked_hands)
+    }
 }
@@ -0,0 +1,137 @@
+use candid::Principal;
+use errors::{game_error::GameError, trace_err, traced_error::TracedError};
+
+use super::{
+    table::{StraddleType, Table, TableType},
+    types::{BetType, PlayerAction, SeatStatus},
+};
+
+impl Table {
+    /// Sets whether the user wants to post a straddle in the next hand.
+    /// The choice only applies to a single hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if the table doesn't allow straddles
+    /// - [`GameError::Other`] if the user isn't at the table
+    pub fn set_straddle_next_hand(
+        &mut self,
+        user_principal: Principal,
+        straddle_next_hand: bool,
+    ) -> Result<(), TracedError<GameError>> {
+        if !self.allows_straddle() {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Straddles are not allowed at this table".to_string(),
+            })));
+        }
+        let user_table_data = self
+            .get_user_table_data_mut(user_principal)
+            .map_err(|e| trace_err!(e, "Failed to get user table data"))?;
+        user_table_data.straddle_next_hand = Some(straddle_next_hand);
+        Ok(())
+    }
+
+    /// Whether straddles can be posted at this table. Straddles are
+    /// only played at no limit and pot limit cash tables.
+    pub fn allows_straddle(&self) -> bool {
+        self.config.straddle_type.is_some()
+            && matches!(self.config.table_type, None | Some(TableType::Cash))
+            && !self.config.game_type.is_limit()
+    }
+
+    /// The size of a straddle, twice the big blind.
+    pub fn straddle_amount(&self) -> u64 {
+        self.big_blind * 2
+    }
+
+    /// Posts the straddle for the hand if the player in the straddle
+    /// position asked for one and can afford it. Should be called after
+    /// the blinds are posted.
+    ///
+    /// Every player's straddle choice is reset afterwards.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the straddler is not found
+    pub fn handle_straddle(&mut self) -> Result<(), TracedError<GameError>> {
+        self.straddle_user_principal = None;
+        let occupied_seats = self
+            .seats
+            .iter()
+            .filter(|seat| matches!(seat, SeatStatus::Occupied(_)))
+            .count();
+        if !self.allows_straddle() || occupied_seats < 3 {
+            self.reset_straddle_choices();
+            return Ok(());
+        }
+
+        if let Some(straddler) = self.get_straddle_position_user_principal() {
+            let wants_to_straddle = self
+                .get_user_table_data(straddler)
+                .map(|data| {
+                    data.straddle_next_hand == Some(true)
+                        && data.player_action != PlayerAction::SittingOut
+                })
+                .unwrap_or(false);
+            let can_afford = self
+                .users
+                .get(&straddler)
+                .is_some_and(|user| user.balance > self.straddle_amount());
+
+            if wants_to_straddle && can_afford {
+                self.bet(straddler, BetType::Straddle)
+                    .map_err(|e| trace_err!(e, "Failed to post straddle"))?;
+                self.straddle_user_principal = Some(straddler);
+            }
+        }
+
+        self.reset_straddle_choices();
+        Ok(())
+    }
+
+    /// Gets the user in the straddle position: the first player after
+    /// the big blind for a UTG straddle or the dealer for a button straddle.
+    fn get_straddle_position_user_principal(&self) -> Option<Principal> {
+        match self.config.straddle_type? {
+            StraddleType::Utg => {
+                let big_blind_index = self.get_seat_index(self.big_blind_user_principal)? as usize;
+                (1..self.seats.len())
+                    .map(|offset| (big_blind_index + offset) % self.seats.len())
+                    .find_map(|index| match self.seats[index] {
+                        SeatStatus::Occupied(principal) => Some(principal),
+                        _ => None,
+                    })
+            }
+            StraddleType::Button => match self.seats.get(self.dealer_position)? {
+                SeatStatus::Occupied(principal) => Some(*principal),
+                _ => None,
+            },
+        }
+    }
+
+    fn reset_straddle_choices(&mut self) {
+        for user_table_data in self.user_table_data.values_mut() {
+            user_table_data.straddle_next_hand = None;
+        }
+    }
+
+    /// Gets the index of the player who acts first preflop when a
+    /// straddle was posted, the first active player after the straddler.
+    pub(crate) fn calculate_straddle_starting_player_index(&self) -> Option<usize> {
+        let straddle_index = self.get_seat_index(self.straddle_user_principal?)? as usize;
+        (1..self.seats.len())
+            .map(|offset| (straddle_index + offset) % self.seats.len())
+            .find(|&index| match self.seats[index] {
+                SeatStatus::Occupied(principal) => self
+                    .get_user_table_data(principal)
+                    .map(|data| {
+                        !matches!(
+                            data.player_action,
+                            PlayerAction::SittingOut | PlayerAction::Folded | PlayerAction::AllIn
+                        )
+                    })
+                    .unwrap_or(false),
+                _ => false,
+            })
+    }
+}
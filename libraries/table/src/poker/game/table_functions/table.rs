@@ -12,7 +12,7 @@ use ic_cdk_timers::TimerId;
 use serde::{Deserialize, Serialize};
 use user::user::User;
 
-use crate::poker::core::{Card, FlatDeck};
+use crate::poker::core::{shuffle_seed, Card, Deck, FlatDeck};
 use crate::poker::game::types::TableStatus;
 use crate::poker::game::users::Users;
 
@@ -20,10 +20,16 @@ use crate::poker::game::types::{GameType, QueueItem, UserCards};
 
 use super::action_log::{ActionLog, ActionType};
 use super::ante::AnteType;
+use super::board_runs::BoardRunsOffer;
+use super::buy_in::TableExit;
+use super::card_encryption::card_master_key;
+use super::missed_blinds::BlindPositions;
+use super::provably_fair::HandVerification;
 use super::rake::Rake;
 use super::side_pot::SidePot;
 use super::types::{
-    BetType, CurrencyType, DealStage, Notifications, PlayerAction, SeatStatus, UserTableData,
+    BetType, BotPersonality, CurrencyType, DealStage, Notifications, PlayerAction, SeatStatus,
+    UserTableData,
 };
 
 #[derive(Debug, Clone, Serialize, CandidType, Deserialize, PartialEq, Eq)]
@@ -46,6 +52,19 @@ pub struct TableConfig {
     pub is_shared_rake: Option<(Principal, String)>,
     pub require_proof_of_humanity: Option<bool>,
     pub is_paused: Option<bool>,
+    pub is_hi_lo: Option<bool>,
+    pub is_short_deck: Option<bool>,
+    pub encrypt_hole_cards: Option<bool>,
+    pub straddle_type: Option<StraddleType>,
+    pub max_board_runs: Option<u8>,
+    pub time_bank_seconds: Option<u16>,
+    pub time_bank_refill_seconds: Option<u16>,
+    pub time_bank_refill_hands: Option<u16>,
+    pub enforce_missed_blinds: Option<bool>,
+    pub min_buy_in_big_blinds: Option<u16>,
+    pub max_buy_in_big_blinds: Option<u16>,
+    pub rathole_window_seconds: Option<u32>,
+    pub is_fast_fold: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, CandidType, Deserialize, PartialEq, Eq)]
@@ -59,6 +78,15 @@ pub enum TableType {
     Satellite, // Tournament table where winners get entry to larger tournament
 }
 
+/// Who may post a live straddle at a cash table.
+#[derive(Debug, Clone, Copy, Serialize, CandidType, Deserialize, PartialEq, Eq)]
+pub enum StraddleType {
+    /// The player to the left of the big blind.
+    Utg,
+    /// The player on the button (Mississippi straddle).
+    Button,
+}
+
 #[derive(Debug, Clone)]
 pub struct Table {
     pub id: Principal,
@@ -93,6 +121,21 @@ pub struct Table {
     pub queue: Vec<QueueItem>,
     pub rake_config: Option<Rake>,
     pub rake_total: Option<u64>,
+    pub deck_commitment: Option<String>,
+    pub deck_seed: Option<Vec<u8>>,
+    pub deck_hand_id: Option<u64>,
+    pub revealed_hands: Vec<HandVerification>,
+    pub card_master_key: Option<Vec<u8>>,
+    pub straddle_user_principal: Option<Principal>,
+    pub extra_boards: Vec<Vec<Card>>,
+    pub board_runs_offer: Option<BoardRunsOffer>,
+    pub time_bank_user_principal: Option<Principal>,
+    pub hand_start_chips: Option<u64>,
+    pub blind_seats: Option<(usize, usize)>,
+    pub recent_exits: HashMap<Principal, TableExit>,
+    pub bots: HashMap<Principal, BotPersonality>,
+    pub all_in_equities: Option<Vec<(Principal, u16)>>,
+    pub eliminations: HashMap<Principal, Vec<(Principal, u64)>>,
 }
 
 impl Default for TableConfig {
@@ -116,11 +159,53 @@ impl Default for TableConfig {
             is_shared_rake: None,
             require_proof_of_humanity: None,
             is_paused: None,
+            is_hi_lo: None,
+            is_short_deck: None,
+            encrypt_hole_cards: None,
+            straddle_type: None,
+            max_board_runs: None,
+            time_bank_seconds: None,
+            time_bank_refill_seconds: None,
+            time_bank_refill_hands: None,
+            enforce_missed_blinds: None,
+            min_buy_in_big_blinds: None,
+            max_buy_in_big_blinds: None,
+            rathole_window_seconds: None,
+            is_fast_fold: None,
         }
     }
 }
 
 impl TableConfig {
+    /// Whether the table plays with the 36 card short deck.
+    pub fn uses_short_deck(&self) -> bool {
+        self.is_short_deck.unwrap_or(false)
+    }
+
+    /// Whether hole cards are only published encrypted to each player.
+    pub fn encrypts_hole_cards(&self) -> bool {
+        self.encrypt_hole_cards.unwrap_or(false)
+    }
+
+    /// The number of cards in the deck used at this table.
+    pub fn deck_size(&self) -> usize {
+        if self.uses_short_deck() {
+            36
+        } else {
+            52
+        }
+    }
+
+    /// Creates a deck for this table shuffled with the given seed.
+    pub fn new_deck(&self, seed: &[u8; 32]) -> FlatDeck {
+        let deck = if self.uses_short_deck() {
+            Deck::short_deck()
+        } else {
+            Deck::default()
+        };
+        FlatDeck::from_seed(seed, deck)
+    }
+
     pub fn default_spin_and_go(small_blind: u64, id: Principal) -> Self {
         Self {
             name: "Spin & Go Table".to_string(),
@@ -144,6 +229,19 @@ impl TableConfig {
             is_shared_rake: None,              // No shared rake
             require_proof_of_humanity: Some(false), // No proof of humanity required
             is_paused: Some(false),            // Not paused initially
+            is_hi_lo: Some(false),             // High hand wins the whole pot
+            is_short_deck: Some(false),        // Full 52 card deck
+            encrypt_hole_cards: Some(false),   // Hole cards are hidden per caller
+            straddle_type: None,               // No straddles in tournaments
+            max_board_runs: None,              // Boards are run once in tournaments
+            time_bank_seconds: None,           // No time bank on top of the turn timer
+            time_bank_refill_seconds: None,    // Time bank is never refilled
+            time_bank_refill_hands: None,      // Time bank is never refilled
+            enforce_missed_blinds: None,       // Blinds are never missed in tournaments
+            min_buy_in_big_blinds: None,       // Tournaments have a fixed buy in
+            max_buy_in_big_blinds: None,       // Tournaments have a fixed buy in
+            rathole_window_seconds: None,      // Players can't rejoin tournament tables
+            is_fast_fold: None,                // Tournament tables aren't pooled
         }
     }
 }
@@ -183,6 +281,21 @@ impl Default for Table {
             queue: Vec::new(),
             rake_config: None,
             rake_total: None,
+            deck_commitment: None,
+            deck_seed: None,
+            deck_hand_id: None,
+            revealed_hands: Vec::new(),
+            card_master_key: None,
+            straddle_user_principal: None,
+            extra_boards: Vec::new(),
+            board_runs_offer: None,
+            time_bank_user_principal: None,
+            hand_start_chips: None,
+            blind_seats: None,
+            recent_exits: HashMap::new(),
+            bots: HashMap::new(),
+            all_in_equities: None,
+            eliminations: HashMap::new(),
         }
     }
 }
@@ -208,6 +321,19 @@ impl TableConfig {
         is_shared_rake: Option<(Principal, String)>,
         require_proof_of_humanity: Option<bool>,
         is_paused: Option<bool>,
+        is_hi_lo: Option<bool>,
+        is_short_deck: Option<bool>,
+        encrypt_hole_cards: Option<bool>,
+        straddle_type: Option<StraddleType>,
+        max_board_runs: Option<u8>,
+        time_bank_seconds: Option<u16>,
+        time_bank_refill_seconds: Option<u16>,
+        time_bank_refill_hands: Option<u16>,
+        enforce_missed_blinds: Option<bool>,
+        min_buy_in_big_blinds: Option<u16>,
+        max_buy_in_big_blinds: Option<u16>,
+        rathole_window_seconds: Option<u32>,
+        is_fast_fold: Option<bool>,
     ) -> TableConfig {
         TableConfig {
             name,
@@ -228,20 +354,28 @@ impl TableConfig {
             is_shared_rake,
             require_proof_of_humanity,
             is_paused,
+            is_hi_lo,
+            is_short_deck,
+            encrypt_hole_cards,
+            straddle_type,
+            max_board_runs,
+            time_bank_seconds,
+            time_bank_refill_seconds,
+            time_bank_refill_hands,
+            enforce_missed_blinds,
+            min_buy_in_big_blinds,
+            max_buy_in_big_blinds,
+            rathole_window_seconds,
+            is_fast_fold,
         }
     }
 }
 
 impl Table {
     pub fn new(id: Principal, config: TableConfig, bytes: Vec<u8>) -> Table {
-        let deck = FlatDeck::new(bytes);
+        let deck = config.new_deck(&shuffle_seed(&bytes));
 
-        let (small_blind, big_blind) = match config.game_type {
-            GameType::NoLimit(small_blind) => (small_blind, small_blind * 2),
-            GameType::SpreadLimit(min, _) => (min / 2, min),
-            GameType::FixedLimit(small, _) => (small / 2, small),
-            GameType::PotLimit(small) => (small, small * 2),
-        };
+        let (small_blind, big_blind) = config.game_type.blinds();
         let rake = if let CurrencyType::Real(currency) = &config.currency_type {
             Rake::new(small_blind, &config.game_type, currency).ok()
         } else {
@@ -283,6 +417,21 @@ impl Table {
             queue: Vec::new(),
             rake_config: rake,
             rake_total: Some(0),
+            deck_commitment: None,
+            deck_seed: None,
+            deck_hand_id: None,
+            revealed_hands: Vec::new(),
+            card_master_key: Some(card_master_key(&bytes).to_vec()),
+            straddle_user_principal: None,
+            extra_boards: Vec::new(),
+            board_runs_offer: None,
+            time_bank_user_principal: None,
+            hand_start_chips: None,
+            blind_seats: None,
+            recent_exits: HashMap::new(),
+            bots: HashMap::new(),
+            all_in_equities: None,
+            eliminations: HashMap::new(),
         }
     }
 
@@ -421,6 +570,7 @@ impl Table {
             BetType::Raised(_) => amount,
             BetType::BigBlind => self.big_blind,
             BetType::SmallBlind => self.small_blind,
+            BetType::Straddle => self.straddle_amount(),
             BetType::Ante(amount) => amount,
             _ => 0,
         };
@@ -453,6 +603,7 @@ impl Table {
             let player_action = match bet_type {
                 BetType::BigBlind => PlayerAction::Raised(amount),
                 BetType::SmallBlind => PlayerAction::Raised(amount),
+                BetType::Straddle => PlayerAction::Raised(amount),
                 BetType::Raised(_) => PlayerAction::Raised(amount),
                 _ => unreachable!(),
             };
@@ -478,6 +629,7 @@ impl Table {
             BetType::Raised(_) => amount.saturating_sub(self.last_raise),
             BetType::BigBlind => self.big_blind,
             BetType::SmallBlind => self.small_blind,
+            BetType::Straddle => amount,
             _ => 0,
         };
         self.last_raise_principal = user_principal;
@@ -490,8 +642,6 @@ impl Table {
             .map_err(|e| trace_err!(e, "Failed to check and reset players called."))?;
 
         if !is_user_all_in {
-            self.next_player()
-                .map_err(|e| trace_err!(e, "Next player failed in raise."))?;
             match bet_type {
                 BetType::Raised(_) => self.log_action(
                     Some(user_principal),
@@ -501,8 +651,14 @@ impl Table {
                 BetType::SmallBlind => {
                     self.log_action(Some(user_principal), ActionType::SmallBlind)
                 }
+                BetType::Straddle => {
+                    self.log_action(Some(user_principal), ActionType::Straddle { amount })
+                }
                 _ => {}
             }
+            // Logged first so a pre action of the next player comes after the raise
+            self.next_player()
+                .map_err(|e| trace_err!(e, "Next player failed in raise."))?;
         } else {
             self.log_action(
                 Some(user_principal),
@@ -512,7 +668,7 @@ impl Table {
             self.next_player()
                 .map_err(|e| trace_err!(e, "Next player failed in raise."))?;
 
-            if bet_type != BetType::BigBlind && bet_type != BetType::SmallBlind {
+            if !bet_type.is_forced() {
                 self.check_next_turn_or_showdown()
                     .map_err(|e| trace_err!(e, "check_next_turn_or_showdown failed in raise."))?;
             }
@@ -570,6 +726,21 @@ impl Table {
     pub fn start_betting_round(
         &mut self,
         bytes: Vec<u8>,
+    ) -> Result<(Vec<(Principal, u64)>, Vec<(Principal, u64)>), TracedError<GameError>> {
+        self.start_betting_round_with_seed(shuffle_seed(&bytes))
+    }
+
+    /// Method to start the betting round with the deck shuffled by `seed`
+    /// instead of random bytes, so a logged hand can be dealt again.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::ActionNotAllowed`] if there are not enough players to start a betting round
+    /// - [`GameError::Other`] if the user table data cannot be retrieved
+    #[allow(clippy::type_complexity)]
+    pub fn start_betting_round_with_seed(
+        &mut self,
+        seed: [u8; 32],
     ) -> Result<(Vec<(Principal, u64)>, Vec<(Principal, u64)>), TracedError<GameError>> {
         #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
         {
@@ -592,12 +763,14 @@ impl Table {
 
         for user_principal in self.seats.clone().into_iter() {
             if let SeatStatus::Occupied(user_principal) = user_principal {
-                let is_kicked = self
-                    .check_and_kick_user_for_insufficient_funds(user_principal, self.big_blind)
-                    .map_err(|e| trace_err!(e, "Failed to kick user for insufficient funds"))?;
+                if !self.sit_out_for_rebuy(user_principal) {
+                    let is_kicked = self
+                        .check_and_kick_user_for_insufficient_funds(user_principal, self.big_blind)
+                        .map_err(|e| trace_err!(e, "Failed to kick user for insufficient funds"))?;
 
-                if let Some(balance) = is_kicked {
-                    self.handle_kicked_player(&mut kicked_players, user_principal, balance);
+                    if let Some(balance) = is_kicked {
+                        self.handle_kicked_player(&mut kicked_players, user_principal, balance);
+                    }
                 }
 
                 let is_kicked = self
@@ -655,6 +828,12 @@ impl Table {
             }
         }
 
+        if self.has_only_bots() {
+            return Err(trace_err!(TracedError::new(GameError::ActionNotAllowed {
+                reason: "Not enough players to start a betting round".to_string(),
+            })));
+        }
+
         if self
             .get_playing_users()
             .map_err(|e| trace_err!(e, "Failed to get playing users"))?
@@ -691,6 +870,7 @@ impl Table {
         }
 
         self.round_ticker += 1;
+        self.refill_time_banks();
         #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
         if self.round_ticker % 10 == 0 {
             let rake_total = self.rake_total.unwrap_or(0);
@@ -708,13 +888,17 @@ impl Table {
             }
         }
 
-        match self.seats[self.dealer_position] {
-            SeatStatus::Occupied(_) => {}
-            _ => {
-                self.rotate_dealer()
-                    .map_err(|e| trace_err!(e, "Failed to rotate dealer"))?;
-            }
-        };
+        // The blinds as they were before this hand places them,
+        // so the hand can be replayed from its logs.
+        let last_blind_seats = self.blind_seats;
+        let missed_blinds = self.owed_blinds();
+        let blind_positions = self.next_blind_positions();
+        if let Some(blind_positions) = blind_positions {
+            self.dealer_position = blind_positions.dealer_seat;
+        } else if !matches!(self.seats[self.dealer_position], SeatStatus::Occupied(_)) {
+            self.rotate_dealer()
+                .map_err(|e| trace_err!(e, "Failed to rotate dealer"))?;
+        }
 
         self.pot = 0;
         self.side_pots.clear();
@@ -722,24 +906,26 @@ impl Table {
         self.winners = None;
         self.sorted_users = None;
         self.community_cards.clear();
-        self.deck = FlatDeck::new(bytes);
+        self.extra_boards.clear();
+        self.board_runs_offer = None;
+        self.all_in_equities = None;
+        self.commit_deck(seed);
 
         self.action_logs.clear();
+        self.log_hand_started(last_blind_seats, missed_blinds);
+        self.start_chip_audit();
         self.highest_bet = 0;
         self.last_raise = 0;
-        let small_blind_user_principal = self
-            .get_small_blind_user_principal()
-            .map_err(|e| trace_err!(e, "Failed to get small blind principal"))?;
-        let big_blind_user_principal = self
-            .get_big_blind_user_principal()
-            .map_err(|e| trace_err!(e, "Failed to get big blind principal"))?;
-
-        self.handle_blind_sitting_out(small_blind_user_principal, BetType::SmallBlind)
-            .map_err(|e| trace_err!(e, "Failed to handle small blind sitting out"))?;
-        self.handle_blind_sitting_out(big_blind_user_principal, BetType::BigBlind)
-            .map_err(|e| trace_err!(e, "Failed to handle big blind sitting out"))?;
+        let (small_blind_user_principal, big_blind_user_principal) =
+            if let Some(blind_positions) = blind_positions {
+                self.post_dead_button_blinds(blind_positions)?
+            } else {
+                self.post_blinds()?
+            };
         self.big_blind_user_principal = big_blind_user_principal;
         self.small_blind_user_principal = small_blind_user_principal;
+        self.handle_straddle()
+            .map_err(|e| trace_err!(e, "Failed to handle straddle"))?;
         if let Some(ante_type) = self.config.ante_type.clone() {
             self.handle_ante(ante_type)?;
         }
@@ -797,7 +983,11 @@ impl Table {
             }
             AnteType::BigBlindAnte => {
                 let ante_amount = self.get_ante_amount();
-                let dealer = self.get_player_at_seat(self.dealer_position)?;
+                // The big blind pays for a dead button.
+                let dealer = match self.seats[self.dealer_position] {
+                    SeatStatus::Occupied(dealer) => dealer,
+                    _ => self.big_blind_user_principal,
+                };
                 self.bet(dealer, BetType::Ante(ante_amount))?;
             }
             AnteType::Fixed(amount) => {
@@ -818,6 +1008,66 @@ impl Table {
         Ok(())
     }
 
+    /// Posts the blinds of the players after the button.
+    ///
+    /// # Returns
+    ///
+    /// The small blind and the big blind.
+    fn post_blinds(&mut self) -> Result<(Principal, Principal), TracedError<GameError>> {
+        let small_blind_user_principal = self
+            .get_small_blind_user_principal()
+            .map_err(|e| trace_err!(e, "Failed to get small blind principal"))?;
+        let big_blind_user_principal = self
+            .get_big_blind_user_principal()
+            .map_err(|e| trace_err!(e, "Failed to get big blind principal"))?;
+
+        self.handle_blind_sitting_out(small_blind_user_principal, BetType::SmallBlind)
+            .map_err(|e| trace_err!(e, "Failed to handle small blind sitting out"))?;
+        self.handle_blind_sitting_out(big_blind_user_principal, BetType::BigBlind)
+            .map_err(|e| trace_err!(e, "Failed to handle big blind sitting out"))?;
+
+        if self.tracks_missed_blinds() {
+            self.blind_seats = self
+                .get_seat_index(small_blind_user_principal)
+                .zip(self.get_seat_index(big_blind_user_principal))
+                .map(|(small_blind_seat, big_blind_seat)| {
+                    (small_blind_seat as usize, big_blind_seat as usize)
+                });
+        }
+        Ok((small_blind_user_principal, big_blind_user_principal))
+    }
+
+    /// Posts the blinds placed with the dead button rule along
+    /// with the blinds owed by the players coming back in.
+    ///
+    /// # Returns
+    ///
+    /// The small blind, anonymous if it is dead, and the big blind.
+    fn post_dead_button_blinds(
+        &mut self,
+        blind_positions: BlindPositions,
+    ) -> Result<(Principal, Principal), TracedError<GameError>> {
+        if let Some(small_blind_user_principal) = blind_positions.small_blind {
+            self.bet(small_blind_user_principal, BetType::SmallBlind)
+                .map_err(|e| trace_err!(e, "Failed to post small blind"))?;
+        }
+        self.post_owed_blinds()
+            .map_err(|e| trace_err!(e, "Failed to post owed blinds"))?;
+        self.bet(blind_positions.big_blind, BetType::BigBlind)
+            .map_err(|e| trace_err!(e, "Failed to post big blind"))?;
+
+        self.blind_seats = Some((
+            blind_positions.small_blind_seat,
+            blind_positions.big_blind_seat,
+        ));
+        Ok((
+            blind_positions
+                .small_blind
+                .unwrap_or(Principal::anonymous()),
+            blind_positions.big_blind,
+        ))
+    }
+
     /// Handle the user's blind bet. If the player is sitting out, the blind should still be placed.
     fn handle_blind_sitting_out(
         &mut self,
@@ -893,10 +1143,21 @@ impl Table {
             if let Some(TableType::Tournament { tournament_id, .. }) =
                 self.config.table_type.clone()
             {
+                let eliminators = self
+                    .eliminations
+                    .remove(&user_principal)
+                    .unwrap_or_default();
                 ic_cdk::futures::spawn(async move {
                     ic_cdk::println!("Removing from tournament: {:?}", tournament_id.to_text());
                     for _ in 0..3 {
-                        match handle_user_losing_wrapper(tournament_id, user_principal, id).await {
+                        match handle_user_losing_wrapper(
+                            tournament_id,
+                            user_principal,
+                            id,
+                            eliminators.clone(),
+                        )
+                        .await
+                        {
                             Ok(_) => return,
                             Err(err) => {
                                 ic_cdk::println!(
@@ -216,7 +216,15 @@ fn test_calling_all_in_action_log() {
 
     let big_blind_uid: Principal = table.get_big_blind_user_principal().unwrap();
     let small_blind_uid = table.get_small_blind_user_principal().unwrap();
-    table.users.get_mut(&small_blind_uid).unwrap().balance = convert_to_e8s(200.0);
+    // Top up the small blind to 200 during the hand
+    assert_eq!(
+        table.deposit_to_stack(small_blind_uid, convert_to_e8s(101.0)),
+        Ok(())
+    );
+    assert_eq!(
+        table.users.get(&small_blind_uid).unwrap().balance,
+        convert_to_e8s(200.0)
+    );
 
     assert!(is_it_users_turn(&table, small_blind_uid));
     assert_eq!(
@@ -242,15 +250,23 @@ fn test_calling_all_in_action_log() {
     );
     println!("{:#?}", table.action_logs);
     assert_eq!(
-        table.action_logs[table.action_logs.len() - 5].action_type,
+        table.action_logs[table.action_logs.len() - 9].action_type,
         ActionType::AllIn {
             amount: convert_to_e8s(100.0)
         }
     );
+    assert!(matches!(
+        table.action_logs[table.action_logs.len() - 8].action_type,
+        ActionType::AllInEquity { .. }
+    ));
     assert_eq!(
-        table.action_logs[table.action_logs.len() - 1].action_type,
+        table.action_logs[table.action_logs.len() - 2].action_type,
         ActionType::Stage {
             stage: DealStage::Showdown
         }
     );
+    assert!(matches!(
+        table.action_logs[table.action_logs.len() - 1].action_type,
+        ActionType::HandFinished { .. }
+    ));
 }
@@ -0,0 +1,220 @@
+use candid::Principal;
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        table::{Table, TableConfig, TableType},
+        tests::{create_test_table, get_table_config, principal},
+        types::{AutoTopUp, PlayerAction},
+    },
+    types::{GameType, QueueItem},
+    utils::convert_to_e8s,
+};
+
+fn top_up_config() -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 6);
+    config.max_buy_in_big_blinds = Some(100);
+    config
+}
+
+fn set_balance(table: &mut Table, seat: usize, balance: u64) {
+    table.users.get_mut(&principal(seat)).unwrap().balance = balance;
+}
+
+const TOP_UP: AutoTopUp = AutoTopUp {
+    threshold_big_blinds: 50,
+    target_big_blinds: 100,
+    auto_rebuy: true,
+};
+
+#[test]
+fn test_auto_top_up_settings_are_validated() {
+    let mut table = create_test_table(top_up_config(), 1);
+
+    assert_eq!(
+        table
+            .set_auto_top_up(
+                principal(0),
+                Some(AutoTopUp {
+                    threshold_big_blinds: 50,
+                    target_big_blinds: 50,
+                    auto_rebuy: false,
+                })
+            )
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "The top up target has to be above the threshold".to_string(),
+        })
+    );
+    assert_eq!(
+        table
+            .set_auto_top_up(
+                principal(0),
+                Some(AutoTopUp {
+                    threshold_big_blinds: 50,
+                    target_big_blinds: 150,
+                    auto_rebuy: false,
+                })
+            )
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooLarge {
+            maximum: convert_to_e8s(100.0)
+        })
+    );
+    assert_eq!(table.set_auto_top_up(principal(0), Some(TOP_UP)), Ok(()));
+    assert_eq!(table.set_auto_top_up(principal(0), None), Ok(()));
+    assert_eq!(
+        table.get_user_table_data(principal(0)).unwrap().auto_top_up,
+        None
+    );
+}
+
+#[test]
+fn test_auto_top_up_only_at_cash_tables() {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 6);
+    config.table_type = Some(TableType::Tournament {
+        tournament_id: Principal::anonymous(),
+        is_final_table: false,
+    });
+    let mut table = create_test_table(config, 1);
+
+    assert_eq!(
+        table
+            .set_auto_top_up(principal(0), Some(TOP_UP))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "Auto top ups are only available at cash tables".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_auto_top_up_amount() {
+    let mut table = create_test_table(top_up_config(), 1);
+    assert_eq!(table.set_auto_top_up(principal(0), Some(TOP_UP)), Ok(()));
+
+    set_balance(&mut table, 0, convert_to_e8s(60.0));
+    assert_eq!(table.auto_top_up_amount(principal(0)), None);
+
+    set_balance(&mut table, 0, convert_to_e8s(30.0));
+    assert_eq!(
+        table.auto_top_up_amount(principal(0)),
+        Some(convert_to_e8s(70.0))
+    );
+
+    // A busted player only buys back in with auto rebuy on.
+    set_balance(&mut table, 0, convert_to_e8s(0.5));
+    assert_eq!(
+        table.auto_top_up_amount(principal(0)),
+        Some(convert_to_e8s(99.5))
+    );
+    assert_eq!(
+        table.set_auto_top_up(
+            principal(0),
+            Some(AutoTopUp {
+                auto_rebuy: false,
+                ..TOP_UP
+            })
+        ),
+        Ok(())
+    );
+    assert_eq!(table.auto_top_up_amount(principal(0)), None);
+}
+
+#[test]
+fn test_top_ups_are_queued_as_deposits() {
+    let mut table = create_test_table(top_up_config(), 3);
+    for seat in 0..3 {
+        assert_eq!(table.set_auto_top_up(principal(seat), Some(TOP_UP)), Ok(()));
+        set_balance(&mut table, seat, convert_to_e8s(40.0));
+    }
+    assert_eq!(table.user_sitting_out(principal(2), false), Ok(()));
+    table.queue_auto_top_ups();
+
+    let deposits: Vec<(Principal, u64)> = table
+        .queue
+        .iter()
+        .filter_map(|item| match item {
+            QueueItem::Deposit(user_principal, _, amount) => Some((*user_principal, *amount)),
+            _ => None,
+        })
+        .collect();
+    // Players sitting out aren't topped up.
+    assert_eq!(
+        deposits,
+        vec![
+            (principal(0), convert_to_e8s(60.0)),
+            (principal(1), convert_to_e8s(60.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_busted_player_sits_out_until_rebuy_lands() {
+    let mut table = create_test_table(top_up_config(), 3);
+    assert_eq!(table.set_auto_top_up(principal(0), Some(TOP_UP)), Ok(()));
+    set_balance(&mut table, 0, 0);
+
+    let (kicked_players, _) = table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .unwrap();
+    assert!(kicked_players.is_empty());
+    let user_table_data = table.get_user_table_data(principal(0)).unwrap();
+    assert_eq!(user_table_data.player_action, PlayerAction::SittingOut);
+    assert_eq!(user_table_data.awaiting_rebuy, Some(true));
+
+    table
+        .users
+        .get_mut(&principal(0))
+        .unwrap()
+        .deposit(convert_to_e8s(100.0));
+    assert!(table.finish_rebuy(principal(0)));
+    assert!(table
+        .queue
+        .iter()
+        .any(|item| matches!(item, QueueItem::SittingIn(p, false) if *p == principal(0))));
+    assert_eq!(
+        table
+            .get_user_table_data(principal(0))
+            .unwrap()
+            .awaiting_rebuy,
+        None
+    );
+}
+
+#[test]
+fn test_busted_player_without_auto_rebuy_is_kicked() {
+    let mut table = create_test_table(top_up_config(), 3);
+    set_balance(&mut table, 0, 0);
+
+    let (kicked_players, _) = table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .unwrap();
+    assert_eq!(kicked_players, vec![(principal(0), 0)]);
+}
+
+#[test]
+fn test_failed_rebuy_sits_player_out() {
+    let mut table = create_test_table(top_up_config(), 3);
+    assert_eq!(table.set_auto_top_up(principal(0), Some(TOP_UP)), Ok(()));
+    set_balance(&mut table, 0, 0);
+
+    assert_eq!(table.handle_failed_top_up(principal(0)), Ok(()));
+    let user_table_data = table.get_user_table_data(principal(0)).unwrap();
+    assert_eq!(user_table_data.player_action, PlayerAction::SittingOut);
+    assert_eq!(user_table_data.auto_top_up, None);
+
+    // The busted player keeps their seat at the next hand.
+    let (kicked_players, _) = table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .unwrap();
+    assert!(kicked_players.is_empty());
+    assert!(table.is_user_in_table(principal(0)));
+    assert_eq!(
+        table
+            .get_user_table_data(principal(0))
+            .unwrap()
+            .player_action,
+        PlayerAction::SittingOut
+    );
+}
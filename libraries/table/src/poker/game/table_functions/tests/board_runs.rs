@@ -0,0 +1,121 @@
+use candid::Principal;
+
+use crate::poker::game::{
+    table_functions::{
+        action_log::ActionType,
+        board_runs::split_across_boards,
+        table::{Table, TableConfig, TableType},
+        tests::{all_in_pre_flop, create_test_table, get_table_config},
+        types::DealStage,
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn board_runs_config(max_board_runs: Option<u8>) -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    config.max_board_runs = max_board_runs;
+    config
+}
+
+fn total_balance(table: &Table) -> u64 {
+    table.users.users.values().map(|user| user.balance).sum()
+}
+
+#[test]
+fn test_board_runs_offered_when_all_in() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    let (small_blind_uid, big_blind_uid) = all_in_pre_flop(&mut table);
+
+    let offer = table.board_runs_offer.clone().unwrap();
+    assert_eq!(offer.max_runs, 2);
+    assert!(offer.user_principals.contains(&small_blind_uid));
+    assert!(offer.user_principals.contains(&big_blind_uid));
+    // The board isn't dealt until the players have chosen.
+    assert_eq!(table.deal_stage, DealStage::Flop);
+    assert!(table.community_cards.is_empty());
+}
+
+#[test]
+fn test_run_it_twice() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    let (small_blind_uid, big_blind_uid) = all_in_pre_flop(&mut table);
+
+    assert_eq!(table.set_board_runs(small_blind_uid, 2), Ok(()));
+    assert_eq!(table.deal_stage, DealStage::Flop);
+    assert_eq!(table.set_board_runs(big_blind_uid, 2), Ok(()));
+
+    assert_eq!(table.board_runs_offer, None);
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+    assert_eq!(table.community_cards.len(), 5);
+    assert_eq!(table.extra_boards.len(), 1);
+    assert_eq!(table.extra_boards[0].len(), 5);
+    assert_ne!(table.extra_boards[0], table.community_cards);
+    assert!(table.action_logs.iter().any(|log| matches!(
+        &log.action_type,
+        ActionType::BoardRun { run: 2, cards } if *cards == table.extra_boards[0]
+    )));
+    assert_eq!(total_balance(&table), convert_to_e8s(200.0));
+}
+
+#[test]
+fn test_board_run_once_if_any_player_declines() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    let (small_blind_uid, big_blind_uid) = all_in_pre_flop(&mut table);
+
+    assert_eq!(table.set_board_runs(small_blind_uid, 2), Ok(()));
+    assert_eq!(table.set_board_runs(big_blind_uid, 1), Ok(()));
+
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+    assert!(table.extra_boards.is_empty());
+    assert_eq!(total_balance(&table), convert_to_e8s(200.0));
+}
+
+#[test]
+fn test_board_run_once_when_time_runs_out() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    let (small_blind_uid, _) = all_in_pre_flop(&mut table);
+
+    assert_eq!(table.set_board_runs(small_blind_uid, 2), Ok(()));
+    assert_eq!(table.resolve_board_runs(), Ok(()));
+
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+    assert!(table.extra_boards.is_empty());
+    assert!(table.resolve_board_runs().is_err());
+}
+
+#[test]
+fn test_board_runs_choice_validation() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    let (small_blind_uid, _) = all_in_pre_flop(&mut table);
+
+    assert!(table.set_board_runs(small_blind_uid, 0).is_err());
+    assert!(table.set_board_runs(small_blind_uid, 3).is_err());
+    assert!(table.set_board_runs(Principal::anonymous(), 2).is_err());
+}
+
+#[test]
+fn test_board_runs_not_offered_when_disabled() {
+    let mut table = create_test_table(board_runs_config(None), 2);
+    all_in_pre_flop(&mut table);
+
+    assert_eq!(table.board_runs_offer, None);
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+}
+
+#[test]
+fn test_board_runs_not_offered_in_tournaments() {
+    let mut table = create_test_table(board_runs_config(Some(2)), 2);
+    table.config.table_type = Some(TableType::SitAndGo);
+    all_in_pre_flop(&mut table);
+
+    assert_eq!(table.board_runs_offer, None);
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+}
+
+#[test]
+fn test_split_across_boards() {
+    assert_eq!(split_across_boards(100, 1), vec![100]);
+    assert_eq!(split_across_boards(100, 2), vec![50, 50]);
+    assert_eq!(split_across_boards(100, 3), vec![34, 33, 33]);
+}
@@ -0,0 +1,261 @@
+use errors::game_error::GameError;
+
+use crate::poker::{
+    core::{Card, Suit, Value},
+    game::{
+        table_functions::{
+            action_log::ActionType,
+            bot::{BotStrategy, BotView, LooseAggressive, TightPassive},
+            legal_actions::LegalActions,
+            table::TableConfig,
+            tests::{create_test_table, create_user, get_table_config, principal},
+            types::{BetType, BotPersonality, CurrencyType, PlayerAction},
+        },
+        types::{GameType, PublicTable},
+        utils::convert_to_e8s,
+    },
+};
+
+fn play_money_config() -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    config.currency_type = CurrencyType::Fake;
+    config
+}
+
+fn card(value: Value, suit: Suit) -> Card {
+    Card::new(value, suit)
+}
+
+fn facing_a_bet(hand_strength: f64, pot_odds: f64) -> BotView {
+    BotView {
+        hand_strength,
+        pot_odds,
+        legal_actions: LegalActions {
+            can_fold: true,
+            can_check: false,
+            call_amount: Some(10),
+            min_raise: Some(20),
+            max_raise: Some(100),
+        },
+        small_raise: Some(20),
+        pot_raise: Some(40),
+    }
+}
+
+#[test]
+fn test_bots_only_at_play_money_tables() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 0);
+
+    assert_eq!(
+        table
+            .add_bot(BotPersonality::TightPassive, convert_to_e8s(100.0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "Bots can only play at play money tables".to_string(),
+        })
+    );
+    assert!(table.users.users.is_empty());
+}
+
+#[test]
+fn test_bots_are_flagged() {
+    let mut table = create_test_table(play_money_config(), 0);
+    let bot = table
+        .add_bot(BotPersonality::LooseAggressive, convert_to_e8s(100.0))
+        .unwrap();
+
+    assert_eq!(table.users.get(&bot).unwrap().is_bot, Some(true));
+    assert!(table.is_bot(bot));
+    let public_table: PublicTable = table.clone().into();
+    assert_eq!(
+        public_table.bots.get(&bot),
+        Some(&BotPersonality::LooseAggressive)
+    );
+
+    assert_eq!(table.remove_user(bot, ActionType::Leave), Ok(()));
+    assert!(!table.is_bot(bot));
+}
+
+#[test]
+fn test_bots_dont_play_among_themselves() {
+    let mut table = create_test_table(play_money_config(), 0);
+    for personality in [
+        BotPersonality::TightPassive,
+        BotPersonality::LooseAggressive,
+    ] {
+        assert!(table.add_bot(personality, convert_to_e8s(100.0)).is_ok());
+    }
+
+    assert_eq!(
+        table
+            .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "Not enough players to start a betting round".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_hand_strength() {
+    let mut table = create_test_table(play_money_config(), 0);
+    let user = create_user(principal(0), convert_to_e8s(100.0));
+    assert!(table.add_user(user, 0, false).is_ok());
+    let mut strength = |cards: Vec<Card>| {
+        table.get_user_table_data_mut(principal(0)).unwrap().cards = cards;
+        table.hand_strength(principal(0)).unwrap()
+    };
+
+    let aces = strength(vec![
+        card(Value::Ace, Suit::Spade),
+        card(Value::Ace, Suit::Heart),
+    ]);
+    let ace_king_suited = strength(vec![
+        card(Value::Ace, Suit::Spade),
+        card(Value::King, Suit::Spade),
+    ]);
+    let seven_deuce = strength(vec![
+        card(Value::Seven, Suit::Spade),
+        card(Value::Two, Suit::Heart),
+    ]);
+    assert_eq!(aces, 1.0);
+    assert!(ace_king_suited < aces && ace_king_suited > seven_deuce);
+    assert_eq!(seven_deuce, 0.0);
+
+    // After the flop the made hand counts
+    table.community_cards = vec![
+        card(Value::Seven, Suit::Club),
+        card(Value::Seven, Suit::Diamond),
+        card(Value::Two, Suit::Club),
+    ];
+    let mut strength = |cards: Vec<Card>| {
+        table.get_user_table_data_mut(principal(0)).unwrap().cards = cards;
+        table.hand_strength(principal(0)).unwrap()
+    };
+    let full_house = strength(vec![
+        card(Value::Seven, Suit::Spade),
+        card(Value::Two, Suit::Heart),
+    ]);
+    let two_pair = strength(vec![
+        card(Value::Ace, Suit::Spade),
+        card(Value::Ace, Suit::Heart),
+    ]);
+    assert!(full_house > two_pair);
+}
+
+#[test]
+fn test_hand_strength_short_deck() {
+    let mut config = play_money_config();
+    config.is_short_deck = Some(true);
+    let mut table = create_test_table(config, 0);
+    let user = create_user(principal(0), convert_to_e8s(100.0));
+    assert!(table.add_user(user, 0, false).is_ok());
+    table.community_cards = vec![
+        card(Value::Seven, Suit::Heart),
+        card(Value::Eight, Suit::Heart),
+        card(Value::Nine, Suit::Club),
+        card(Value::Nine, Suit::Heart),
+    ];
+    let mut strength = |cards: Vec<Card>| {
+        table.get_user_table_data_mut(principal(0)).unwrap().cards = cards;
+        table.hand_strength(principal(0)).unwrap()
+    };
+
+    // The ace plays low in A-6-7-8-9
+    let wheel = strength(vec![
+        card(Value::Ace, Suit::Spade),
+        card(Value::Six, Suit::Diamond),
+    ]);
+    let three_of_a_kind = strength(vec![
+        card(Value::Nine, Suit::Spade),
+        card(Value::King, Suit::Diamond),
+    ]);
+    assert!(wheel > three_of_a_kind);
+
+    // A flush beats a full house
+    let flush = strength(vec![
+        card(Value::Ace, Suit::Heart),
+        card(Value::Ten, Suit::Heart),
+    ]);
+    let full_house = strength(vec![
+        card(Value::Nine, Suit::Spade),
+        card(Value::Eight, Suit::Club),
+    ]);
+    assert!(flush > full_house);
+}
+
+#[test]
+fn test_tight_passive_strategy() {
+    assert_eq!(
+        TightPassive.decide(&facing_a_bet(0.9, 0.25)),
+        PlayerAction::Raised(20)
+    );
+    assert_eq!(
+        TightPassive.decide(&facing_a_bet(0.6, 0.25)),
+        PlayerAction::Called
+    );
+    assert_eq!(
+        TightPassive.decide(&facing_a_bet(0.3, 0.1)),
+        PlayerAction::Folded
+    );
+}
+
+#[test]
+fn test_loose_aggressive_strategy() {
+    assert_eq!(
+        LooseAggressive.decide(&facing_a_bet(0.6, 0.25)),
+        PlayerAction::Raised(40)
+    );
+    assert_eq!(
+        LooseAggressive.decide(&facing_a_bet(0.2, 0.1)),
+        PlayerAction::Called
+    );
+    assert_eq!(
+        LooseAggressive.decide(&facing_a_bet(0.2, 0.5)),
+        PlayerAction::Folded
+    );
+
+    // Bets when checked to
+    let mut view = facing_a_bet(0.3, 0.0);
+    view.legal_actions.can_check = true;
+    view.legal_actions.call_amount = None;
+    assert_eq!(LooseAggressive.decide(&view), PlayerAction::Raised(20));
+}
+
+#[test]
+fn test_bot_plays_its_turn() {
+    let mut table = create_test_table(play_money_config(), 0);
+    let user = create_user(principal(0), convert_to_e8s(100.0));
+    assert!(table.add_user(user, 0, false).is_ok());
+    let small_blind = table
+        .add_bot(BotPersonality::TightPassive, convert_to_e8s(100.0))
+        .unwrap();
+    let big_blind = table
+        .add_bot(BotPersonality::LooseAggressive, convert_to_e8s(100.0))
+        .unwrap();
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+    assert_eq!(table.current_player_index, 0);
+
+    assert_eq!(
+        table
+            .play_bot_turn(principal(0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "User is not a bot".to_string(),
+        })
+    );
+    assert_eq!(table.bet(principal(0), BetType::Called), Ok(()));
+    assert_eq!(
+        table.play_bot_turn(big_blind).map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "It is not the bot's turn".to_string(),
+        })
+    );
+
+    assert_eq!(table.play_bot_turn(small_blind), Ok(()));
+    assert_eq!(table.current_player_index, 2);
+}
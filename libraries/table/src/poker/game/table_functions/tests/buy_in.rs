@@ -0,0 +1,133 @@
+use candid::Principal;
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        table::{TableConfig, TableType},
+        tests::{create_test_table, create_user, get_table_config, principal},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+const SECOND: u64 = 1_000_000_000;
+
+fn buy_in_config() -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 6);
+    config.min_buy_in_big_blinds = Some(20);
+    config.max_buy_in_big_blinds = Some(100);
+    config.rathole_window_seconds = Some(3600);
+    config
+}
+
+#[test]
+fn test_buy_in_limits_in_big_blinds() {
+    let table = create_test_table(buy_in_config(), 0);
+    assert_eq!(table.min_buy_in(), convert_to_e8s(20.0));
+    assert_eq!(table.max_buy_in(), Some(convert_to_e8s(100.0)));
+
+    assert_eq!(
+        table
+            .validate_buy_in(principal(0), convert_to_e8s(10.0), 0)
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooSmall {
+            minimum: convert_to_e8s(20.0)
+        })
+    );
+    assert_eq!(
+        table
+            .validate_buy_in(principal(0), convert_to_e8s(150.0), 0)
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooLarge {
+            maximum: convert_to_e8s(100.0)
+        })
+    );
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(50.0), 0),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_buy_in_defaults_to_one_big_blind() {
+    let table = create_test_table(
+        get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 6),
+        0,
+    );
+    assert_eq!(table.min_buy_in(), table.big_blind);
+    assert_eq!(table.max_buy_in(), None);
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(10_000.0), 0),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_buy_in_limits_ignored_at_tournament_tables() {
+    let mut config = buy_in_config();
+    config.table_type = Some(TableType::Tournament {
+        tournament_id: Principal::anonymous(),
+        is_final_table: false,
+    });
+    let table = create_test_table(config, 0);
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(1_000.0), 0),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_top_up_is_capped_at_max_buy_in() {
+    let mut table = create_test_table(buy_in_config(), 0);
+    let user = create_user(principal(0), convert_to_e8s(80.0));
+    assert!(table.add_user(user, 0, false).is_ok());
+
+    assert_eq!(
+        table
+            .validate_top_up(principal(0), convert_to_e8s(30.0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooLarge {
+            maximum: convert_to_e8s(100.0)
+        })
+    );
+    assert_eq!(
+        table.validate_top_up(principal(0), convert_to_e8s(20.0)),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_ratholing_player_must_return_with_their_stack() {
+    let mut table = create_test_table(buy_in_config(), 0);
+    table.record_exit(principal(0), convert_to_e8s(150.0), 0);
+
+    assert_eq!(
+        table
+            .validate_buy_in(principal(0), convert_to_e8s(100.0), 60 * SECOND)
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooSmall {
+            minimum: convert_to_e8s(150.0)
+        })
+    );
+    // Coming back with the whole stack is allowed even over the max buy in.
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(150.0), 60 * SECOND),
+        Ok(())
+    );
+    // Once the window is over the usual limits apply again.
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(100.0), 3600 * SECOND),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_exit_within_max_buy_in_is_not_remembered() {
+    let mut table = create_test_table(buy_in_config(), 0);
+    table.record_exit(principal(0), convert_to_e8s(150.0), 0);
+    assert!(table.recent_exit(principal(0), SECOND).is_some());
+
+    // Leaving again with a normal stack clears the earlier exit.
+    table.record_exit(principal(0), convert_to_e8s(80.0), SECOND);
+    assert_eq!(table.recent_exit(principal(0), SECOND), None);
+}
@@ -0,0 +1,145 @@
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::poker::game::{
+    table_functions::{
+        card_encryption::{decrypt_cards, EciesKeyDerivation},
+        table::TableConfig,
+        tests::{create_test_table, get_table_config, principal},
+        types::DealStage,
+    },
+    types::{GameType, PublicTable},
+    utils::convert_to_e8s,
+};
+
+fn encryption_config(encrypt_hole_cards: bool) -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    config.encrypt_hole_cards = Some(encrypt_hole_cards);
+    config
+}
+
+fn transport_key_pair(seed: u8) -> (StaticSecret, Vec<u8>) {
+    let secret = StaticSecret::from([seed; 32]);
+    let public_key = PublicKey::from(&secret).as_bytes().to_vec();
+    (secret, public_key)
+}
+
+#[test]
+fn test_public_table_only_carries_encrypted_cards() {
+    let mut table = create_test_table(encryption_config(true), 2);
+    let (user1, user2) = (principal(0), principal(1));
+    assert!(table
+        .set_transport_public_key(user1, transport_key_pair(7).1)
+        .is_ok());
+    assert!(table
+        .set_transport_public_key(user2, transport_key_pair(9).1)
+        .is_ok());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    let public_table = PublicTable::from(&table);
+    for user in [user1, user2] {
+        let data = public_table.user_table_data.get(&user).unwrap();
+        assert!(data.cards.is_empty());
+        assert_eq!(data.encrypted_cards.as_ref().unwrap().len(), 2);
+    }
+}
+
+#[test]
+fn test_player_can_decrypt_their_cards() {
+    let mut table = create_test_table(encryption_config(true), 2);
+    let user1 = principal(0);
+    let (transport_secret, transport_key) = transport_key_pair(7);
+    assert!(table
+        .set_transport_public_key(user1, transport_key.clone())
+        .is_ok());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    let card_key = table.get_encrypted_card_key(user1).unwrap();
+    assert_eq!(card_key.hand_id, table.round_ticker);
+
+    let key = EciesKeyDerivation::decrypt_key(&card_key.encrypted_key, &transport_secret).unwrap();
+    let public_table = PublicTable::from(&table);
+    let ciphertext = public_table
+        .user_table_data
+        .get(&user1)
+        .unwrap()
+        .encrypted_cards
+        .clone()
+        .unwrap();
+    assert_eq!(
+        decrypt_cards(&key, &ciphertext).unwrap(),
+        table.get_user_table_data(user1).unwrap().cards
+    );
+
+    // Another transport secret key doesn't recover the cards.
+    let key =
+        EciesKeyDerivation::decrypt_key(&card_key.encrypted_key, &transport_key_pair(8).0).unwrap();
+    assert_ne!(
+        decrypt_cards(&key, &ciphertext).ok(),
+        Some(table.get_user_table_data(user1).unwrap().cards.clone())
+    );
+}
+
+#[test]
+fn test_card_key_requires_transport_key() {
+    let mut table = create_test_table(encryption_config(true), 2);
+    let (user1, user2) = (principal(0), principal(1));
+    assert!(table.set_transport_public_key(user1, Vec::new()).is_err());
+    assert!(table.set_transport_public_key(user1, vec![7; 48]).is_err());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert!(table.get_encrypted_card_key(user2).is_err());
+    assert!(table
+        .get_user_table_data(user2)
+        .unwrap()
+        .encrypted_cards
+        .is_none());
+}
+
+#[test]
+fn test_cards_revealed_at_showdown() {
+    let mut table = create_test_table(encryption_config(true), 2);
+    let user1 = principal(0);
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    table.deal_stage = DealStage::Showdown;
+    let public_table = PublicTable::from(&table);
+    assert_eq!(
+        public_table
+            .user_table_data
+            .get(&user1)
+            .unwrap()
+            .cards
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_plaintext_cards_without_encryption() {
+    let mut table = create_test_table(encryption_config(false), 2);
+    let user1 = principal(0);
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    let public_table = PublicTable::from(table);
+    let data = public_table.user_table_data.get(&user1).unwrap();
+    assert_eq!(data.cards.len(), 2);
+    assert!(data.encrypted_cards.is_none());
+}
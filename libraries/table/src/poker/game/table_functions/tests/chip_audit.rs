@@ -0,0 +1,115 @@
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        action_log::ActionType,
+        tests::{create_test_table, get_table_config, start_hand},
+        types::BetType,
+    },
+    types::{GameType, QueueItem},
+    utils::convert_to_e8s,
+};
+
+#[test]
+fn test_chip_audit_started_with_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    assert_eq!(table.hand_start_chips, Some(convert_to_e8s(200.0)));
+    assert_eq!(
+        table.audit_chips(0).map_err(|e| e.into_inner()),
+        Err(GameError::ChipsNotConserved {
+            chips_before: convert_to_e8s(200.0),
+            chips_after: convert_to_e8s(198.5),
+        })
+    );
+}
+
+#[test]
+fn test_chip_audit_passes_after_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.user_fold(big_blind_uid, false), Ok(()));
+
+    assert!(table.sorted_users.is_some());
+    assert_eq!(table.hand_start_chips, None);
+    assert!(!table
+        .action_logs
+        .iter()
+        .any(|log| matches!(log.action_type, ActionType::ChipAuditFailed { .. })));
+    assert!(!table
+        .queue
+        .iter()
+        .any(|item| matches!(item, QueueItem::PauseTable)));
+}
+
+#[test]
+fn test_chip_audit_counts_deposits_during_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+
+    assert_eq!(
+        table.deposit_to_stack(big_blind_uid, convert_to_e8s(50.0)),
+        Ok(())
+    );
+    assert_eq!(
+        table.withdraw_from_stack(small_blind_uid, convert_to_e8s(10.0)),
+        Ok(())
+    );
+    assert_eq!(table.hand_start_chips, Some(convert_to_e8s(240.0)));
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.user_fold(big_blind_uid, false), Ok(()));
+
+    assert!(!table
+        .action_logs
+        .iter()
+        .any(|log| matches!(log.action_type, ActionType::ChipAuditFailed { .. })));
+}
+
+#[test]
+fn test_chip_audit_pauses_table_when_chips_go_missing() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+
+    // Chips leaking out of a stack in the middle of the hand.
+    table.users.get_mut(&big_blind_uid).unwrap().balance -= convert_to_e8s(1.0);
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.user_fold(big_blind_uid, false), Ok(()));
+
+    assert_eq!(
+        table.action_logs.last().unwrap().action_type,
+        ActionType::ChipAuditFailed {
+            chips_before: convert_to_e8s(200.0),
+            chips_after: convert_to_e8s(199.0),
+        }
+    );
+    assert!(table
+        .queue
+        .iter()
+        .any(|item| matches!(item, QueueItem::PauseTable)));
+
+    // The next hand isn't dealt until the table is unpaused.
+    assert!(table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .is_err());
+    assert_eq!(table.config.is_paused, Some(true));
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use candid::Principal;
+
+use crate::poker::game::{
+    table_functions::{
+        tests::{create_test_table, get_table_config, principal},
+        types::BetType,
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+#[test]
+fn test_eliminator_of_busted_player() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    assert!(table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .is_ok());
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(100.0))),
+        Ok(())
+    );
+    assert_eq!(table.bet(big_blind_uid, BetType::Called), Ok(()));
+
+    let balance = |user_principal: Principal| table.users.get(&user_principal).unwrap().balance;
+    let (small_blind_balance, big_blind_balance) =
+        (balance(small_blind_uid), balance(big_blind_uid));
+    if small_blind_balance == big_blind_balance {
+        // A split pot knocks nobody out
+        assert!(table.eliminations.is_empty());
+        return;
+    }
+
+    let (loser, winner) = if small_blind_balance == 0 {
+        (small_blind_uid, big_blind_uid)
+    } else {
+        (big_blind_uid, small_blind_uid)
+    };
+    let eliminators = table.get_eliminators(loser);
+    assert_eq!(eliminators.len(), 1);
+    assert_eq!(eliminators[0].0, winner);
+    assert!(table.get_eliminators(winner).is_empty());
+}
+
+#[test]
+fn test_eliminators_across_side_pots() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 3);
+    let user_principals: Vec<_> = (0..3).map(principal).collect();
+
+    // The short stack is in the main pot with both players, a side pot
+    // is only contested by the other two.
+    let pots = vec![
+        (
+            user_principals.clone(),
+            HashMap::from([(user_principals[1], convert_to_e8s(30.0))]),
+        ),
+        (
+            user_principals[1..].to_vec(),
+            HashMap::from([(user_principals[2], convert_to_e8s(40.0))]),
+        ),
+    ];
+    table.users.get_mut(&user_principals[0]).unwrap().balance = 0;
+    table.record_eliminations(&pots);
+    assert_eq!(
+        table.get_eliminators(user_principals[0]),
+        vec![(user_principals[1], convert_to_e8s(30.0))]
+    );
+    assert!(table.get_eliminators(user_principals[1]).is_empty());
+
+    // Splitting the main pot splits the knockout
+    let pots = vec![(
+        user_principals.clone(),
+        HashMap::from([
+            (user_principals[1], convert_to_e8s(10.0)),
+            (user_principals[2], convert_to_e8s(20.0)),
+        ]),
+    )];
+    table.record_eliminations(&pots);
+    assert_eq!(
+        table.get_eliminators(user_principals[0]),
+        vec![
+            (user_principals[2], convert_to_e8s(20.0)),
+            (user_principals[1], convert_to_e8s(10.0)),
+        ]
+    );
+}
@@ -0,0 +1,129 @@
+use candid::Principal;
+
+use crate::poker::{
+    core::EquityRules,
+    game::{
+        table_functions::{
+            action_log::ActionType,
+            tests::{all_in_pre_flop, create_test_table, get_table_config},
+            types::DealStage,
+        },
+        types::{GameType, PublicTable},
+        utils::convert_to_e8s,
+    },
+};
+
+#[test]
+fn test_equity_rules() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    assert_eq!(table.config.equity_rules(), EquityRules::Holdem);
+
+    table.config.is_short_deck = Some(true);
+    assert_eq!(table.config.equity_rules(), EquityRules::ShortDeck);
+
+    table.config.game_type = GameType::PotLimitOmaha4(convert_to_e8s(0.5));
+    assert_eq!(table.config.equity_rules(), EquityRules::Omaha);
+}
+
+#[test]
+fn test_no_equities_before_all_in() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    assert!(table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .is_ok());
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+
+    assert_eq!(table.all_in_equities, None);
+    assert!(!table.is_hand_face_up(small_blind_uid));
+    assert_eq!(table.hide_cards(small_blind_uid), Ok(()));
+    assert_eq!(
+        table
+            .get_user_table_data(small_blind_uid)
+            .unwrap()
+            .cards
+            .len(),
+        2
+    );
+    assert!(table
+        .get_user_table_data(big_blind_uid)
+        .unwrap()
+        .cards
+        .is_empty());
+}
+
+#[test]
+fn test_equities_annotated_when_all_in() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    let (small_blind_uid, big_blind_uid) = all_in_pre_flop(&mut table);
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+
+    let equities = table.all_in_equities.clone().unwrap();
+    let principals: Vec<Principal> = equities.iter().map(|(principal, _)| *principal).collect();
+    assert!(principals.contains(&small_blind_uid));
+    assert!(principals.contains(&big_blind_uid));
+    // Rounding can be off by a basis point either way
+    let total: u16 = equities.iter().map(|(_, equity)| equity).sum();
+    assert!((9_999..=10_001).contains(&total));
+
+    assert!(table.action_logs.iter().any(|log| matches!(
+        &log.action_type,
+        ActionType::AllInEquity { equities: logged } if *logged == equities
+    )));
+    let public_table: PublicTable = table.clone().into();
+    assert_eq!(public_table.all_in_equities, Some(equities));
+}
+
+#[test]
+fn test_equities_recalculated_every_street() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    all_in_pre_flop(&mut table);
+
+    // Before the flop, on the flop and on the turn
+    let annotations = table
+        .action_logs
+        .iter()
+        .filter(|log| matches!(log.action_type, ActionType::AllInEquity { .. }))
+        .count();
+    assert_eq!(annotations, 3);
+    assert!(table.action_logs.iter().any(|log| matches!(
+        &log.action_type,
+        ActionType::AllInEquity { equities } if Some(equities) == table.all_in_equities.as_ref()
+    )));
+}
+
+#[test]
+fn test_all_in_hands_are_face_up() {
+    // The hand waits for the players to choose how many boards to run
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    config.max_board_runs = Some(2);
+    let mut table = create_test_table(config, 2);
+    let (small_blind_uid, big_blind_uid) = all_in_pre_flop(&mut table);
+    assert!(table.community_cards.is_empty());
+    assert!(table.all_in_equities.is_some());
+    assert!(table.is_hand_face_up(small_blind_uid));
+    assert!(table.is_hand_face_up(big_blind_uid));
+
+    // Spectators see both hands
+    assert_eq!(table.hide_cards(Principal::anonymous()), Ok(()));
+    assert_eq!(
+        table
+            .get_user_table_data(small_blind_uid)
+            .unwrap()
+            .cards
+            .len(),
+        2
+    );
+    assert_eq!(
+        table
+            .get_user_table_data(big_blind_uid)
+            .unwrap()
+            .cards
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,141 @@
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        action_log::ActionType,
+        table::TableConfig,
+        tests::{create_test_table, get_table_config, principal, start_hand},
+        types::SeatStatus,
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn fast_fold_config() -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    config.is_fast_fold = Some(true);
+    config
+}
+
+#[test]
+fn test_fast_fold_only_on_fast_fold_tables() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(table.user_fold(principal(0), false), Ok(()));
+
+    assert_eq!(
+        table
+            .fast_fold_user(principal(0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "Table is not a fast-fold table".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_fast_fold_requires_a_fold() {
+    let mut table = create_test_table(fast_fold_config(), 3);
+    start_hand(&mut table);
+
+    assert_eq!(
+        table
+            .fast_fold_user(principal(1))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "User has to fold first".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_folded_player_leaves_with_their_stack() {
+    let mut table = create_test_table(fast_fold_config(), 3);
+    start_hand(&mut table);
+
+    assert_eq!(table.user_fold(principal(0), false), Ok(()));
+    assert_eq!(
+        table.fast_fold_user(principal(0)),
+        Ok(convert_to_e8s(100.0))
+    );
+    assert_eq!(table.users.get(&principal(0)).unwrap().balance, 0);
+    assert_eq!(
+        table
+            .fast_fold_user(principal(0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "User is already leaving the table".to_string(),
+        })
+    );
+
+    // The hand goes on without them
+    assert_eq!(table.user_fold(principal(1), false), Ok(()));
+    assert!(table.sorted_users.is_some());
+    assert_eq!(
+        table.users.get(&principal(2)).unwrap().balance,
+        convert_to_e8s(100.5)
+    );
+    assert!(!table
+        .action_logs
+        .iter()
+        .any(|log| matches!(log.action_type, ActionType::ChipAuditFailed { .. })));
+
+    // Their seat is freed before the next hand is dealt
+    assert!(table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .is_ok());
+    assert_eq!(table.seats[0], SeatStatus::Empty);
+    assert!(table.users.get(&principal(0)).is_none());
+}
+
+#[test]
+fn test_fast_fold_between_hands() {
+    let mut table = create_test_table(fast_fold_config(), 3);
+
+    assert_eq!(
+        table.fast_fold_user(principal(2)),
+        Ok(convert_to_e8s(100.0))
+    );
+    assert_eq!(table.seats[2], SeatStatus::Empty);
+    assert!(table.users.get(&principal(2)).is_none());
+}
+
+#[test]
+fn test_fast_fold_pool_buy_in() {
+    let mut config = fast_fold_config();
+    config.min_buy_in_big_blinds = Some(40);
+    config.max_buy_in_big_blinds = Some(100);
+
+    assert_eq!(
+        config
+            .validate_fast_fold_buy_in(convert_to_e8s(39.0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooSmall {
+            minimum: convert_to_e8s(40.0)
+        })
+    );
+    assert_eq!(
+        config
+            .validate_fast_fold_buy_in(convert_to_e8s(101.0))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::BuyInTooLarge {
+            maximum: convert_to_e8s(100.0)
+        })
+    );
+    assert_eq!(
+        config.validate_fast_fold_buy_in(convert_to_e8s(100.0)),
+        Ok(())
+    );
+
+    // A stack carried to another table of the pool isn't checked again
+    let mut table = create_test_table(config, 3);
+    assert_eq!(
+        table.validate_buy_in(principal(0), convert_to_e8s(250.0), 0),
+        Ok(())
+    );
+    table.config.is_fast_fold = None;
+    assert!(table
+        .validate_buy_in(principal(0), convert_to_e8s(250.0), 0)
+        .is_err());
+}
@@ -0,0 +1,175 @@
+use candid::Principal;
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    hand_history::{export_hand, group_logs_by_hand, HandHistoryFormat},
+    table_functions::{
+        table::Table,
+        tests::{create_test_table, get_table_config, start_hand},
+        types::{BetType, DealStage},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn user_name(table: &Table, user_principal: Principal) -> String {
+    table.users.get(&user_principal).unwrap().user_name.clone()
+}
+
+/// Calls preflop and checks the hand down to the showdown.
+fn check_down(table: &mut Table) {
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+    assert_eq!(table.bet(small_blind_uid, BetType::Called), Ok(()));
+    assert_eq!(table.user_check(big_blind_uid, false), Ok(()));
+
+    for _ in 0..6 {
+        let user_principal = table
+            .get_player_at_seat(table.current_player_index)
+            .unwrap();
+        assert_eq!(table.user_check(user_principal, false), Ok(()));
+    }
+    assert!(table.sorted_users.is_some());
+}
+
+#[test]
+fn test_group_logs_by_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    check_down(&mut table);
+
+    let hands = group_logs_by_hand(table.id, &table.action_logs);
+    assert_eq!(hands.len(), 1);
+    assert_eq!(hands[0].hand_id(), Some(1));
+    assert_eq!(hands[0].logs.len(), table.action_logs.len());
+
+    // Logs from before the first hand started are skipped.
+    assert!(group_logs_by_hand(table.id, &table.action_logs[1..]).is_empty());
+}
+
+#[test]
+fn test_poker_stars_export_uncalled_bet() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+    let small_blind_name = user_name(&table, small_blind_uid);
+    let big_blind_name = user_name(&table, big_blind_uid);
+
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.user_fold(big_blind_uid, false), Ok(()));
+
+    let text = export_hand(
+        table.id,
+        &table.action_logs,
+        1,
+        HandHistoryFormat::PokerStars,
+    )
+    .unwrap();
+
+    assert!(text.starts_with(
+        "PokerStars Hand #1: Hold'em No Limit (0.50/1.00) - 1970/01/01 00:00:00 UTC\n"
+    ));
+    assert!(text.contains(&format!("{}: posts small blind 0.50\n", small_blind_name)));
+    assert!(text.contains(&format!("{}: posts big blind 1.00\n", big_blind_name)));
+    assert!(text.contains(&format!(
+        "*** HOLE CARDS ***\n{}: raises 2.00 to 3.00\n{}: folds\n",
+        small_blind_name, big_blind_name
+    )));
+    assert!(text.contains(&format!(
+        "Uncalled bet (2.00) returned to {}\n",
+        small_blind_name
+    )));
+    assert!(text.contains(&format!("{} collected 2.00 from pot\n", small_blind_name)));
+    assert!(text.contains("Total pot 2.00 | Rake 0.00\n"));
+    assert!(text.contains("folded before Flop"));
+    assert!(!text.contains("*** SHOW DOWN ***"));
+}
+
+#[test]
+fn test_poker_stars_export_showdown() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    check_down(&mut table);
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+
+    let text = export_hand(
+        table.id,
+        &table.action_logs,
+        1,
+        HandHistoryFormat::PokerStars,
+    )
+    .unwrap();
+
+    let board = table
+        .community_cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>();
+    assert!(text.contains(&format!("*** FLOP *** [{}]\n", board[0..3].join(" "))));
+    assert!(text.contains(&format!(
+        "*** RIVER *** [{}] [{}]\n",
+        board[0..4].join(" "),
+        board[4]
+    )));
+    assert!(text.contains("*** SHOW DOWN ***\n"));
+    assert!(text.contains(&format!("Board [{}]\n", board.join(" "))));
+    assert!(text.contains("Total pot 2.00 | Rake 0.00\n"));
+}
+
+#[test]
+fn test_open_hand_history_export() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    check_down(&mut table);
+
+    let json = export_hand(
+        table.id,
+        &table.action_logs,
+        1,
+        HandHistoryFormat::OpenHandHistory,
+    )
+    .unwrap();
+    let ohh: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let ohh = &ohh["ohh"];
+
+    assert_eq!(ohh["game_number"], "1");
+    assert_eq!(ohh["game_type"], "Holdem");
+    assert_eq!(ohh["bet_limit"]["bet_type"], "NL");
+    assert_eq!(ohh["big_blind_amount"], 1.0);
+    assert_eq!(ohh["players"].as_array().unwrap().len(), 2);
+
+    let rounds = ohh["rounds"].as_array().unwrap();
+    let streets = rounds
+        .iter()
+        .map(|round| round["street"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(streets, ["Preflop", "Flop", "Turn", "River", "Showdown"]);
+    assert_eq!(rounds[1]["cards"].as_array().unwrap().len(), 3);
+    assert_eq!(rounds[4]["actions"].as_array().unwrap().len(), 2);
+    assert_eq!(ohh["pots"][0]["amount"], 2.0);
+}
+
+#[test]
+fn test_export_unknown_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    check_down(&mut table);
+
+    let err = export_hand(
+        table.id,
+        &table.action_logs,
+        2,
+        HandHistoryFormat::PokerStars,
+    )
+    .unwrap_err();
+    assert_eq!(err.into_inner(), GameError::HandNotFound);
+}
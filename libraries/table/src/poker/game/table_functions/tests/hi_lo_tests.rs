@@ -0,0 +1,212 @@
+use candid::Principal;
+
+use crate::poker::{
+    core::{Card, Suit, Value},
+    game::{
+        table_functions::{
+            action_log::ActionType,
+            table::Table,
+            tests::{create_user, get_table_config},
+            types::BetType,
+        },
+        types::GameType,
+        utils::convert_to_e8s,
+    },
+};
+
+fn create_hi_lo_table() -> (Table, Principal, Principal) {
+    let mut config = get_table_config(GameType::PotLimitOmaha4(convert_to_e8s(1.0)), 2);
+    config.is_hi_lo = Some(true);
+    let mut table = Table::new(
+        Principal::anonymous(),
+        config,
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    );
+    let user1 = create_user(
+        Principal::from_text("2chl6-4hpzw-vqaaa-aaaaa-c").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+    let user2 = create_user(
+        Principal::from_text("br5f7-7uaaa-aaaaa-qaaca-cai").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+
+    assert!(table.add_user(user1.clone(), 0, false).is_ok());
+    assert!(table.add_user(user2.clone(), 1, false).is_ok());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+    assert_eq!(
+        table.bet(user1.principal_id, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.bet(user2.principal_id, BetType::Called), Ok(()));
+
+    (table, user1.principal_id, user2.principal_id)
+}
+
+fn set_cards(table: &mut Table, user: Principal, cards: &[(Value, Suit)]) {
+    table.get_user_table_data_mut(user).unwrap().cards = cards
+        .iter()
+        .map(|(value, suit)| Card::new(*value, *suit))
+        .collect();
+}
+
+fn balance(table: &Table, user: Principal) -> u64 {
+    table.users.get(&user).unwrap().balance
+}
+
+#[test]
+fn test_hi_lo_splits_pot_between_high_and_low() {
+    let (mut table, user1, user2) = create_hi_lo_table();
+
+    // Board: [2♥, 3♣, 7♦, K♠, Q♦]
+    table.community_cards = vec![
+        Card::new(Value::Two, Suit::Heart),
+        Card::new(Value::Three, Suit::Club),
+        Card::new(Value::Seven, Suit::Diamond),
+        Card::new(Value::King, Suit::Spade),
+        Card::new(Value::Queen, Suit::Diamond),
+    ];
+    // Player 1 has three kings and no low.
+    set_cards(
+        &mut table,
+        user1,
+        &[
+            (Value::King, Suit::Heart),
+            (Value::King, Suit::Club),
+            (Value::Nine, Suit::Spade),
+            (Value::Nine, Suit::Diamond),
+        ],
+    );
+    // Player 2 has a 7-4-3-2-A low.
+    set_cards(
+        &mut table,
+        user2,
+        &[
+            (Value::Ace, Suit::Heart),
+            (Value::Four, Suit::Spade),
+            (Value::Jack, Suit::Club),
+            (Value::Jack, Suit::Diamond),
+        ],
+    );
+
+    // Make the pot odd so the high half has to take the odd chip.
+    table.pot = convert_to_e8s(10.0) + 1;
+    let user1_balance = balance(&table, user1);
+    let user2_balance = balance(&table, user2);
+
+    table.showdown().unwrap();
+
+    assert_eq!(
+        balance(&table, user1),
+        user1_balance + convert_to_e8s(5.0) + 1
+    );
+    assert_eq!(balance(&table, user2), user2_balance + convert_to_e8s(5.0));
+    assert_eq!(table.winners.as_ref().unwrap().len(), 2);
+
+    let ranked_hands = table
+        .action_logs
+        .iter()
+        .rev()
+        .find_map(|log| match &log.action_type {
+            ActionType::PlayersHandsRankedMainPot { hands, low_hands } => Some((hands, low_hands)),
+            _ => None,
+        });
+    let (hands, low_hands) = ranked_hands.expect("The main pot hands should be ranked");
+    assert_eq!(hands.len(), 2);
+    let low_hands = low_hands.as_ref().unwrap();
+    assert_eq!(low_hands.len(), 1);
+    assert_eq!(low_hands[0].2, convert_to_e8s(5.0));
+}
+
+#[test]
+fn test_hi_lo_no_qualifying_low_high_takes_all() {
+    let (mut table, user1, user2) = create_hi_lo_table();
+
+    // Board: [T♠, J♣, 9♦, K♠, 2♦]. Only one low card, so no low is possible.
+    table.community_cards = vec![
+        Card::new(Value::Ten, Suit::Spade),
+        Card::new(Value::Jack, Suit::Club),
+        Card::new(Value::Nine, Suit::Diamond),
+        Card::new(Value::King, Suit::Spade),
+        Card::new(Value::Two, Suit::Diamond),
+    ];
+    // Player 1 has three kings.
+    set_cards(
+        &mut table,
+        user1,
+        &[
+            (Value::King, Suit::Heart),
+            (Value::King, Suit::Club),
+            (Value::Four, Suit::Spade),
+            (Value::Five, Suit::Diamond),
+        ],
+    );
+    set_cards(
+        &mut table,
+        user2,
+        &[
+            (Value::Ace, Suit::Heart),
+            (Value::Three, Suit::Spade),
+            (Value::Four, Suit::Club),
+            (Value::Five, Suit::Club),
+        ],
+    );
+
+    let pot = table.pot;
+    let user1_balance = balance(&table, user1);
+    let user2_balance = balance(&table, user2);
+
+    table.showdown().unwrap();
+
+    assert_eq!(balance(&table, user1), user1_balance + pot);
+    assert_eq!(balance(&table, user2), user2_balance);
+}
+
+#[test]
+fn test_hi_lo_scoop() {
+    let (mut table, user1, user2) = create_hi_lo_table();
+
+    // Board: [2♥, 3♣, 7♦, K♠, Q♦]
+    table.community_cards = vec![
+        Card::new(Value::Two, Suit::Heart),
+        Card::new(Value::Three, Suit::Club),
+        Card::new(Value::Seven, Suit::Diamond),
+        Card::new(Value::King, Suit::Spade),
+        Card::new(Value::Queen, Suit::Diamond),
+    ];
+    // Player 1 has three kings and a 7-4-3-2-A low.
+    set_cards(
+        &mut table,
+        user1,
+        &[
+            (Value::Ace, Suit::Heart),
+            (Value::Four, Suit::Spade),
+            (Value::King, Suit::Heart),
+            (Value::King, Suit::Club),
+        ],
+    );
+    set_cards(
+        &mut table,
+        user2,
+        &[
+            (Value::Jack, Suit::Club),
+            (Value::Jack, Suit::Diamond),
+            (Value::Nine, Suit::Spade),
+            (Value::Nine, Suit::Heart),
+        ],
+    );
+
+    let pot = table.pot;
+    let user1_balance = balance(&table, user1);
+    let user2_balance = balance(&table, user2);
+
+    table.showdown().unwrap();
+
+    assert_eq!(balance(&table, user1), user1_balance + pot);
+    assert_eq!(balance(&table, user2), user2_balance);
+    assert_eq!(table.winners.as_ref().unwrap().len(), 1);
+}
@@ -0,0 +1,173 @@
+use crate::poker::game::{
+    table_functions::{
+        legal_actions::LegalActions,
+        tests::{create_test_table, get_table_config, start_hand},
+        types::{BetType, SeatStatus},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+#[test]
+fn test_legal_actions_no_limit_pre_flop() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.small_blind_user_principal;
+
+    assert_eq!(
+        table.legal_actions(small_blind_uid),
+        Ok(LegalActions {
+            can_fold: true,
+            can_check: false,
+            call_amount: Some(convert_to_e8s(1.0)),
+            min_raise: Some(convert_to_e8s(2.0) + 1),
+            max_raise: Some(convert_to_e8s(100.0)),
+        })
+    );
+}
+
+#[test]
+fn test_legal_actions_not_players_turn() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let big_blind_uid = table.big_blind_user_principal;
+
+    assert_eq!(
+        table.legal_actions(big_blind_uid),
+        Ok(LegalActions::default())
+    );
+}
+
+#[test]
+fn test_legal_actions_big_blind_option() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.small_blind_user_principal;
+    let big_blind_uid = table.big_blind_user_principal;
+
+    assert_eq!(table.bet(small_blind_uid, BetType::Called), Ok(()));
+
+    let legal_actions = table.legal_actions(big_blind_uid).unwrap();
+    assert!(legal_actions.can_check);
+    assert_eq!(legal_actions.call_amount, None);
+    assert_eq!(legal_actions.min_raise, Some(convert_to_e8s(2.0) + 1));
+}
+
+#[test]
+fn test_legal_actions_min_raise_above_highest_bet() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.small_blind_user_principal;
+    let big_blind_uid = table.big_blind_user_principal;
+
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(7.0))),
+        Ok(())
+    );
+
+    let legal_actions = table.legal_actions(big_blind_uid).unwrap();
+    assert_eq!(legal_actions.call_amount, Some(convert_to_e8s(5.0)));
+    assert_eq!(legal_actions.min_raise, Some(convert_to_e8s(7.0) + 1));
+    assert_eq!(legal_actions.max_raise, Some(convert_to_e8s(100.0)));
+}
+
+#[test]
+fn test_short_all_in_leaves_raising_open() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let button_uid = table
+        .seats
+        .iter()
+        .find_map(|seat| match seat {
+            SeatStatus::Occupied(uid) if *uid != big_blind_uid && *uid != small_blind_uid => {
+                Some(*uid)
+            }
+            _ => None,
+        })
+        .unwrap();
+    table.users.get_mut(&small_blind_uid).unwrap().balance = convert_to_e8s(8.0);
+    start_hand(&mut table);
+
+    // Button raises to 6.
+    assert_eq!(
+        table.bet(button_uid, BetType::Raised(convert_to_e8s(6.0))),
+        Ok(())
+    );
+    // Small blind is all in for 8, which is less than a full raise.
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(8.0))),
+        Ok(())
+    );
+
+    let legal_actions = table.legal_actions(big_blind_uid).unwrap();
+    assert_eq!(legal_actions.min_raise, Some(convert_to_e8s(8.0) + 1));
+    assert_eq!(table.bet(big_blind_uid, BetType::Called), Ok(()));
+
+    // The engine doesn't enforce raise increments, so the button may
+    // still raise after acting.
+    let legal_actions = table.legal_actions(button_uid).unwrap();
+    assert_eq!(legal_actions.call_amount, Some(convert_to_e8s(2.0)));
+    assert_eq!(legal_actions.min_raise, Some(convert_to_e8s(8.0) + 1));
+    assert_eq!(
+        table.bet(button_uid, BetType::Raised(convert_to_e8s(12.0))),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_legal_actions_pot_limit() {
+    let config = get_table_config(GameType::PotLimit(convert_to_e8s(1.0)), 2);
+    let mut table = create_test_table(config, 2);
+    start_hand(&mut table);
+    let small_blind_uid = table.small_blind_user_principal;
+
+    let legal_actions = table.legal_actions(small_blind_uid).unwrap();
+    assert_eq!(legal_actions.max_raise, Some(table.get_pot()));
+    assert!(table
+        .bet(
+            small_blind_uid,
+            BetType::Raised(legal_actions.max_raise.unwrap() + 1)
+        )
+        .is_err());
+    assert_eq!(
+        table.bet(
+            small_blind_uid,
+            BetType::Raised(legal_actions.max_raise.unwrap())
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_legal_raises_accepted_by_bet() {
+    for game_type in [
+        GameType::NoLimit(convert_to_e8s(1.0)),
+        GameType::PotLimit(convert_to_e8s(1.0)),
+        GameType::FixedLimit(convert_to_e8s(2.0), convert_to_e8s(4.0)),
+        GameType::SpreadLimit(convert_to_e8s(2.0), convert_to_e8s(10.0)),
+    ] {
+        let config = get_table_config(game_type.clone(), 2);
+        let mut table = create_test_table(config, 2);
+        start_hand(&mut table);
+        let small_blind_uid = table.small_blind_user_principal;
+
+        let legal_actions = table.legal_actions(small_blind_uid).unwrap();
+        for amount in [legal_actions.min_raise, legal_actions.max_raise] {
+            let amount = amount.expect("Raising should be allowed");
+            let mut table = table.clone();
+            assert_eq!(
+                table.bet(small_blind_uid, BetType::Raised(amount)),
+                Ok(()),
+                "{:?} raise to {}",
+                game_type,
+                amount
+            );
+        }
+    }
+}
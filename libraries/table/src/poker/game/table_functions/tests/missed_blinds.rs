@@ -0,0 +1,246 @@
+use candid::Principal;
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    hand_history::group_logs_by_hand,
+    replay::HandReplay,
+    table_functions::{
+        action_log::ActionType,
+        table::{Table, TableConfig},
+        tests::{create_test_table, create_user, get_table_config, principal, start_hand},
+        types::{BlindChoice, MissedBlinds, PlayerAction, SeatStatus},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn missed_blinds_config(seats: u8) -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), seats);
+    config.enforce_missed_blinds = Some(true);
+    config
+}
+
+fn join(table: &mut Table, seat: usize) {
+    let user = create_user(principal(seat), convert_to_e8s(100.0));
+    assert!(table.add_user(user, seat as u8, false).is_ok());
+}
+
+/// Everyone folds to the big blind.
+fn fold_around(table: &mut Table) {
+    while table.sorted_users.is_none() {
+        let uid = table
+            .get_player_at_seat(table.current_player_index)
+            .unwrap();
+        assert_eq!(table.user_fold(uid, false), Ok(()));
+    }
+}
+
+fn balance(table: &Table, seat: usize) -> u64 {
+    table.users.get(&principal(seat)).unwrap().balance
+}
+
+/// Replays the hand that was just played and checks it ends with the same stacks.
+fn assert_replays(table: &Table) {
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+    let hand = group_logs_by_hand(table.id, &table.action_logs)
+        .pop()
+        .unwrap();
+    let replayed = HandReplay::new(table.config.clone(), &hand, &verification)
+        .unwrap()
+        .replay_to_end()
+        .unwrap();
+    for (user_principal, user) in table.users.users.iter() {
+        assert_eq!(
+            replayed.users.get(user_principal).unwrap().balance,
+            user.balance
+        );
+    }
+}
+
+/// Plays the first hand: the button on seat 0, the blinds on seats 1 and 2.
+fn play_first_hand(table: &mut Table) {
+    start_hand(table);
+    assert_eq!(table.dealer_position, 0);
+    assert_eq!(table.blind_seats, Some((1, 2)));
+    fold_around(table);
+}
+
+#[test]
+fn test_sitting_out_player_misses_blinds() {
+    let mut table = create_test_table(missed_blinds_config(4), 4);
+    play_first_hand(&mut table);
+
+    assert_eq!(table.user_sitting_out(principal(3), false), Ok(()));
+    start_hand(&mut table);
+
+    // The big blind skips the player sitting out instead of charging them.
+    assert_eq!(table.dealer_position, 1);
+    assert_eq!(table.small_blind_user_principal, principal(2));
+    assert_eq!(table.big_blind_user_principal, principal(0));
+    assert_eq!(balance(&table, 3), convert_to_e8s(100.0));
+    assert_eq!(
+        table
+            .get_user_table_data(principal(3))
+            .unwrap()
+            .missed_blinds,
+        Some(MissedBlinds {
+            small_blind: true,
+            big_blind: true,
+        })
+    );
+}
+
+#[test]
+fn test_returning_player_posts_dead_blinds() {
+    let mut table = create_test_table(missed_blinds_config(4), 4);
+    play_first_hand(&mut table);
+    assert_eq!(table.user_sitting_out(principal(3), false), Ok(()));
+    start_hand(&mut table);
+    fold_around(&mut table);
+
+    // Owed blinds have to be settled one way or the other to sit back in.
+    assert_eq!(
+        table
+            .check_blind_choice(principal(3))
+            .map_err(|e| e.into_inner()),
+        Err(GameError::ActionNotAllowed {
+            reason: "Choose to post the missed blinds or wait for the big blind".to_string(),
+        })
+    );
+    assert_eq!(
+        table.set_blind_choice(principal(3), BlindChoice::PostDeadBlinds),
+        Ok(())
+    );
+    assert_eq!(table.check_blind_choice(principal(3)), Ok(()));
+    assert_eq!(
+        table.set_player_action(principal(3), PlayerAction::None),
+        Ok(())
+    );
+    start_hand(&mut table);
+
+    assert_eq!(table.small_blind_user_principal, principal(0));
+    assert_eq!(table.big_blind_user_principal, principal(1));
+    // A live big blind and a dead small blind.
+    assert_eq!(balance(&table, 3), convert_to_e8s(98.5));
+    assert!(table
+        .action_logs
+        .iter()
+        .any(|log| log.user_principal == Some(principal(3))
+            && log.action_type
+                == ActionType::DeadBlind {
+                    amount: convert_to_e8s(0.5)
+                }));
+    let user_table_data = table.get_user_table_data(principal(3)).unwrap();
+    assert_eq!(user_table_data.current_total_bet, convert_to_e8s(1.0));
+    assert_eq!(user_table_data.missed_blinds, None);
+    assert_eq!(user_table_data.cards.len(), 2);
+}
+
+#[test]
+fn test_new_player_waits_for_big_blind() {
+    let mut table = create_test_table(missed_blinds_config(5), 4);
+    play_first_hand(&mut table);
+
+    join(&mut table, 4);
+    assert_eq!(
+        table
+            .get_user_table_data(principal(4))
+            .unwrap()
+            .blind_choice,
+        Some(BlindChoice::WaitForBigBlind)
+    );
+    start_hand(&mut table);
+
+    // The big blind is on seat 3, the new player sits this hand out.
+    assert_eq!(table.big_blind_user_principal, principal(3));
+    let user_table_data = table.get_user_table_data(principal(4)).unwrap();
+    assert_eq!(user_table_data.player_action, PlayerAction::Joining);
+    assert!(user_table_data.cards.is_empty());
+    assert_eq!(balance(&table, 4), convert_to_e8s(100.0));
+    fold_around(&mut table);
+
+    start_hand(&mut table);
+    assert_eq!(table.big_blind_user_principal, principal(4));
+    let user_table_data = table.get_user_table_data(principal(4)).unwrap();
+    assert_eq!(user_table_data.missed_blinds, None);
+    assert_eq!(user_table_data.cards.len(), 2);
+    assert_eq!(balance(&table, 4), convert_to_e8s(99.0));
+}
+
+#[test]
+fn test_dead_button_when_small_blind_leaves() {
+    let mut table = create_test_table(missed_blinds_config(4), 4);
+    play_first_hand(&mut table);
+
+    assert_eq!(table.remove_user(principal(1), ActionType::Leave), Ok(()));
+    start_hand(&mut table);
+
+    // The button stays on the empty seat so the last big blind
+    // still pays the small blind.
+    assert_eq!(table.dealer_position, 1);
+    assert_eq!(table.seats[1], SeatStatus::Empty);
+    assert_eq!(table.small_blind_user_principal, principal(2));
+    assert_eq!(table.big_blind_user_principal, principal(3));
+}
+
+#[test]
+fn test_dead_small_blind_when_big_blind_leaves() {
+    let mut table = create_test_table(missed_blinds_config(4), 4);
+    play_first_hand(&mut table);
+
+    assert_eq!(table.remove_user(principal(2), ActionType::Leave), Ok(()));
+    start_hand(&mut table);
+
+    // Nobody pays the small blind rather than the button skipping it.
+    assert_eq!(table.dealer_position, 1);
+    assert_eq!(table.small_blind_user_principal, Principal::anonymous());
+    assert_eq!(table.big_blind_user_principal, principal(3));
+    assert_eq!(table.highest_bet, convert_to_e8s(1.0));
+    assert_eq!(balance(&table, 1), convert_to_e8s(99.5));
+    assert_eq!(balance(&table, 3), convert_to_e8s(99.0));
+}
+
+#[test]
+fn test_replay_dead_button_hands() {
+    let mut table = create_test_table(missed_blinds_config(5), 4);
+    play_first_hand(&mut table);
+
+    // The new player on seat 4 is held out until the big blind.
+    join(&mut table, 4);
+    start_hand(&mut table);
+    assert_eq!(
+        table
+            .get_user_table_data(principal(4))
+            .unwrap()
+            .player_action,
+        PlayerAction::Joining
+    );
+    fold_around(&mut table);
+    assert_replays(&table);
+
+    // Seat 0 sits out and misses the blinds when the big blind passes.
+    assert_eq!(table.user_sitting_out(principal(0), false), Ok(()));
+    for _ in 0..2 {
+        start_hand(&mut table);
+        fold_around(&mut table);
+        assert_replays(&table);
+    }
+
+    // Seat 0 comes back posting a dead small blind.
+    assert_eq!(
+        table.set_blind_choice(principal(0), BlindChoice::PostDeadBlinds),
+        Ok(())
+    );
+    assert_eq!(
+        table.set_player_action(principal(0), PlayerAction::None),
+        Ok(())
+    );
+    start_hand(&mut table);
+    assert!(table
+        .action_logs
+        .iter()
+        .any(|log| log.user_principal == Some(principal(0))
+            && matches!(log.action_type, ActionType::DeadBlind { .. })));
+    fold_around(&mut table);
+    assert_replays(&table);
+}
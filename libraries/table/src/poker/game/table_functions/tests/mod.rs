@@ -1,9 +1,12 @@
 use candid::Principal;
 use user::user::User;
 
-use crate::poker::game::types::GameType;
+use crate::poker::game::{types::GameType, utils::convert_to_e8s};
 
-use super::table::TableConfig;
+use super::{
+    table::{Table, TableConfig},
+    types::BetType,
+};
 
 pub mod action_logs;
 
@@ -13,22 +16,64 @@ pub mod all_in_tests_side_pot;
 
 pub mod ante_tests;
 
+pub mod auto_top_up;
+
 pub mod betting_order;
 
+pub mod board_runs;
+
+pub mod bots;
+
+pub mod buy_in;
+
+pub mod card_encryption;
+
+pub mod chip_audit;
+
+pub mod elimination;
+
+pub mod equity;
+
+pub mod fast_fold;
+
 pub mod fixed_limit_tests;
 
 pub mod general_tests;
 
+pub mod hand_history;
+
+pub mod hi_lo_tests;
+
+pub mod legal_actions;
+
+pub mod missed_blinds;
+
 pub mod no_limit_tests;
 
 pub mod pot_distribution_tests;
 
 pub mod pot_limit;
 
+pub mod pot_limit_omaha;
+
+pub mod pre_action;
+
+pub mod provably_fair;
+
 pub mod rake;
 
+pub mod replay;
+
+pub mod seat_reservation;
+
+pub mod short_deck;
+
 pub mod spread_limit_tests;
 
+pub mod straddle;
+
+pub mod time_bank;
+
 pub mod turn_tests;
 
 pub fn create_user(canister_id: Principal, balance: u64) -> User {
@@ -65,5 +110,71 @@ pub fn get_table_config(game_type: GameType, seats: u8) -> TableConfig {
         None,
         None,
         None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
 }
+
+/// The principals of the test players, in seat order.
+pub const PRINCIPALS: [&str; 5] = [
+    "2chl6-4hpzw-vqaaa-aaaaa-c",
+    "br5f7-7uaaa-aaaaa-qaaca-cai",
+    "bw4dl-smaaa-aaaaa-qaacq-cai",
+    "by6od-j4aaa-aaaaa-qaadq-cai",
+    "b77ix-eeaaa-aaaaa-qaada-cai",
+];
+
+/// The principal of the test player on the seat.
+pub fn principal(seat: usize) -> Principal {
+    Principal::from_text(PRINCIPALS[seat]).expect("Could not decode principal")
+}
+
+/// Creates a table with a player of 100 chips on each of the first
+/// `players` seats.
+pub fn create_test_table(config: TableConfig, players: usize) -> Table {
+    let mut table = Table::new(
+        Principal::anonymous(),
+        config,
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    );
+    for seat in 0..players {
+        let user = create_user(principal(seat), convert_to_e8s(100.0));
+        assert!(table.add_user(user, seat as u8, false).is_ok());
+    }
+    table
+}
+
+/// Starts the next hand.
+pub fn start_hand(table: &mut Table) {
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+}
+
+/// Starts a hand and gets both players all in before the flop.
+pub fn all_in_pre_flop(table: &mut Table) -> (Principal, Principal) {
+    assert!(table
+        .start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+        .is_ok());
+    let small_blind_uid = table.get_small_blind_user_principal().unwrap();
+    let big_blind_uid = table.get_big_blind_user_principal().unwrap();
+
+    assert_eq!(
+        table.bet(small_blind_uid, BetType::Raised(convert_to_e8s(100.0))),
+        Ok(())
+    );
+    assert_eq!(table.bet(big_blind_uid, BetType::Called), Ok(()));
+    (small_blind_uid, big_blind_uid)
+}
@@ -0,0 +1,140 @@
+use candid::Principal;
+
+use crate::poker::{
+    core::{Card, Suit, Value},
+    game::{
+        table_functions::{
+            table::Table,
+            tests::{create_user, get_table_config},
+            types::BetType,
+        },
+        types::GameType,
+        utils::convert_to_e8s,
+    },
+};
+
+fn create_omaha_table(game_type: GameType) -> (Table, Principal, Principal) {
+    let mut table = Table::new(
+        Principal::anonymous(),
+        get_table_config(game_type, 2),
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    );
+    let user1 = create_user(
+        Principal::from_text("2chl6-4hpzw-vqaaa-aaaaa-c").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+    let user2 = create_user(
+        Principal::from_text("br5f7-7uaaa-aaaaa-qaaca-cai").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+
+    assert!(table.add_user(user1.clone(), 0, false).is_ok());
+    assert!(table.add_user(user2.clone(), 1, false).is_ok());
+
+    (table, user1.principal_id, user2.principal_id)
+}
+
+#[test]
+fn test_pot_limit_omaha_four_deals_four_hole_cards() {
+    let (mut table, user1, user2) =
+        create_omaha_table(GameType::PotLimitOmaha4(convert_to_e8s(1.0)));
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert_eq!(table.get_user_table_data(user1).unwrap().cards.len(), 4);
+    assert_eq!(table.get_user_table_data(user2).unwrap().cards.len(), 4);
+    assert_eq!(table.deck.len(), 52 - 8);
+}
+
+#[test]
+fn test_pot_limit_omaha_five_deals_five_hole_cards() {
+    let (mut table, user1, user2) =
+        create_omaha_table(GameType::PotLimitOmaha5(convert_to_e8s(1.0)));
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert_eq!(table.get_user_table_data(user1).unwrap().cards.len(), 5);
+    assert_eq!(table.get_user_table_data(user2).unwrap().cards.len(), 5);
+    assert_eq!(table.deck.len(), 52 - 10);
+}
+
+#[test]
+fn test_pot_limit_omaha_correct_blinds() {
+    let (mut table, _, _) = create_omaha_table(GameType::PotLimitOmaha4(convert_to_e8s(1.0)));
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert_eq!(table.small_blind, convert_to_e8s(1.0));
+    assert_eq!(table.big_blind, convert_to_e8s(2.0));
+}
+
+#[test]
+fn test_pot_limit_omaha_raise_above_pot_not_allowed() {
+    let (mut table, user1, _) = create_omaha_table(GameType::PotLimitOmaha4(convert_to_e8s(1.0)));
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert!(table
+        .bet(user1, BetType::Raised(convert_to_e8s(50.0)))
+        .is_err());
+}
+
+#[test]
+fn test_pot_limit_omaha_showdown_uses_two_hole_cards() {
+    let (mut table, user1, user2) =
+        create_omaha_table(GameType::PotLimitOmaha4(convert_to_e8s(1.0)));
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    assert_eq!(
+        table.bet(user1, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    assert_eq!(table.bet(user2, BetType::Called), Ok(()));
+
+    // Board: [T♥, 2♥, 3♣, 7♦, 8♠]
+    table.community_cards = vec![
+        Card::new(Value::Ten, Suit::Heart),
+        Card::new(Value::Two, Suit::Heart),
+        Card::new(Value::Three, Suit::Club),
+        Card::new(Value::Seven, Suit::Diamond),
+        Card::new(Value::Eight, Suit::Spade),
+    ];
+
+    // Player 1: [A♥, K♥, Q♥, J♥]. This would be a royal flush in Hold'em
+    // but only makes ace high in Omaha.
+    table.get_user_table_data_mut(user1).unwrap().cards = vec![
+        Card::new(Value::Ace, Suit::Heart),
+        Card::new(Value::King, Suit::Heart),
+        Card::new(Value::Queen, Suit::Heart),
+        Card::new(Value::Jack, Suit::Heart),
+    ];
+
+    // Player 2: [2♦, 2♠, 4♣, 5♣]. Three of a kind (deuces).
+    table.get_user_table_data_mut(user2).unwrap().cards = vec![
+        Card::new(Value::Two, Suit::Diamond),
+        Card::new(Value::Two, Suit::Spade),
+        Card::new(Value::Four, Suit::Club),
+        Card::new(Value::Five, Suit::Club),
+    ];
+
+    table.showdown().unwrap();
+
+    assert!(table.users.get(&user2).unwrap().balance > convert_to_e8s(100.0));
+    assert!(table.users.get(&user1).unwrap().balance < convert_to_e8s(100.0));
+}
@@ -0,0 +1,158 @@
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        table::Table,
+        tests::{create_test_table, get_table_config, principal, start_hand},
+        types::{BetType, PlayerAction, PreAction},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn set_pre_action(table: &mut Table, seat: usize, pre_action: PreAction) -> Result<(), GameError> {
+    table
+        .set_pre_action(principal(seat), Some(pre_action))
+        .map_err(|e| e.into_inner())
+}
+
+#[test]
+fn test_pre_action_is_validated() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+
+    assert_eq!(
+        set_pre_action(&mut table, 0, PreAction::CallAny),
+        Err(GameError::ActionNotAllowed {
+            reason: "It is already the user's turn".to_string(),
+        })
+    );
+    assert_eq!(
+        set_pre_action(&mut table, 1, PreAction::Check),
+        Err(GameError::ActionNotAllowed {
+            reason: "There is a bet to call".to_string(),
+        })
+    );
+    assert_eq!(
+        set_pre_action(&mut table, 1, PreAction::Call(convert_to_e8s(2.0))),
+        Err(GameError::ActionNotAllowed {
+            reason: "The amount doesn't match the bet to call".to_string(),
+        })
+    );
+    assert_eq!(
+        set_pre_action(&mut table, 1, PreAction::RaiseTo(convert_to_e8s(2.0))),
+        Err(GameError::ActionNotAllowed {
+            reason: "A raise has to be more than the highest bet".to_string(),
+        })
+    );
+    assert_eq!(set_pre_action(&mut table, 2, PreAction::Check), Ok(()));
+
+    assert_eq!(table.set_pre_action(principal(2), None), Ok(()));
+    assert_eq!(
+        table.get_user_table_data(principal(2)).unwrap().pre_action,
+        None
+    );
+}
+
+#[test]
+fn test_call_any_is_taken_when_the_action_arrives() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(set_pre_action(&mut table, 1, PreAction::CallAny), Ok(()));
+
+    assert_eq!(
+        table.bet(principal(0), BetType::Raised(convert_to_e8s(6.0))),
+        Ok(())
+    );
+
+    let user_table_data = table.get_user_table_data(principal(1)).unwrap();
+    assert_eq!(user_table_data.player_action, PlayerAction::Called);
+    assert_eq!(user_table_data.current_total_bet, convert_to_e8s(6.0));
+    assert_eq!(user_table_data.pre_action, None);
+    assert_eq!(table.current_player_index, 2);
+}
+
+#[test]
+fn test_call_is_dropped_when_the_bet_goes_up() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(
+        set_pre_action(&mut table, 1, PreAction::Call(convert_to_e8s(1.0))),
+        Ok(())
+    );
+
+    assert_eq!(
+        table.bet(principal(0), BetType::Raised(convert_to_e8s(6.0))),
+        Ok(())
+    );
+
+    // The small blind decides for themselves facing the raise.
+    let user_table_data = table.get_user_table_data(principal(1)).unwrap();
+    assert_eq!(user_table_data.current_total_bet, convert_to_e8s(1.0));
+    assert_eq!(user_table_data.pre_action, None);
+    assert_eq!(table.current_player_index, 1);
+}
+
+#[test]
+fn test_check_fold_folds_facing_a_raise() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(set_pre_action(&mut table, 2, PreAction::CheckFold), Ok(()));
+
+    assert_eq!(
+        table.bet(principal(0), BetType::Raised(convert_to_e8s(6.0))),
+        Ok(())
+    );
+    assert_eq!(table.user_fold(principal(1), false), Ok(()));
+
+    assert!(table.sorted_users.is_some());
+    assert_eq!(
+        table.users.get(&principal(2)).unwrap().balance,
+        convert_to_e8s(98.0)
+    );
+}
+
+#[test]
+fn test_raise_to_is_taken_when_nobody_raised() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(
+        set_pre_action(&mut table, 2, PreAction::RaiseTo(convert_to_e8s(8.0))),
+        Ok(())
+    );
+
+    assert_eq!(table.bet(principal(0), BetType::Called), Ok(()));
+    assert_eq!(table.bet(principal(1), BetType::Called), Ok(()));
+
+    assert_eq!(table.highest_bet, convert_to_e8s(8.0));
+    assert_eq!(
+        table
+            .get_user_table_data(principal(2))
+            .unwrap()
+            .current_total_bet,
+        convert_to_e8s(8.0)
+    );
+    assert_eq!(table.current_player_index, 0);
+}
+
+#[test]
+fn test_check_moves_the_hand_to_the_flop() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    assert_eq!(set_pre_action(&mut table, 2, PreAction::Check), Ok(()));
+
+    assert_eq!(table.bet(principal(0), BetType::Called), Ok(()));
+    assert_eq!(table.bet(principal(1), BetType::Called), Ok(()));
+
+    assert_eq!(table.community_cards.len(), 3);
+    assert_eq!(
+        table.get_user_table_data(principal(2)).unwrap().pre_action,
+        None
+    );
+}
@@ -0,0 +1,97 @@
+use candid::Principal;
+
+use crate::poker::{
+    core::Card,
+    game::{
+        table_functions::{
+            table::Table,
+            tests::{create_user, get_table_config},
+            types::BetType,
+        },
+        types::GameType,
+        utils::convert_to_e8s,
+    },
+};
+
+fn create_started_table() -> (Table, Principal, Principal) {
+    let mut table = Table::new(
+        Principal::anonymous(),
+        get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2),
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    );
+    let user1 = create_user(
+        Principal::from_text("2chl6-4hpzw-vqaaa-aaaaa-c").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+    let user2 = create_user(
+        Principal::from_text("br5f7-7uaaa-aaaaa-qaaca-cai").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+
+    assert!(table.add_user(user1.clone(), 0, false).is_ok());
+    assert!(table.add_user(user2.clone(), 1, false).is_ok());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    (table, user1.principal_id, user2.principal_id)
+}
+
+#[test]
+fn test_deck_commitment_published_before_dealing() {
+    let (table, _, _) = create_started_table();
+
+    assert!(table.deck_commitment.is_some());
+    assert_eq!(table.deck_hand_id, Some(table.round_ticker));
+    assert!(table.deck_seed.is_some());
+}
+
+#[test]
+fn test_verify_hand_not_allowed_while_in_progress() {
+    let (table, _, _) = create_started_table();
+
+    assert!(table.verify_hand(table.round_ticker).is_err());
+    assert!(table.verify_hand(table.round_ticker + 1).is_err());
+}
+
+#[test]
+fn test_verify_hand_after_showdown() {
+    let (mut table, user1, user2) = create_started_table();
+    let small_blind_uid = table.small_blind_user_principal;
+    let big_blind_uid = table.big_blind_user_principal;
+
+    assert_eq!(table.bet(small_blind_uid, BetType::Called), Ok(()));
+    assert_eq!(table.user_check(big_blind_uid, false), Ok(()));
+
+    let commitment = table.deck_commitment.clone();
+    let remaining: Vec<Card> = table.deck[..].to_vec();
+    let mut dealt = table.get_user_table_data(user1).unwrap().cards.clone();
+    dealt.extend(table.get_user_table_data(user2).unwrap().cards.clone());
+
+    table.showdown().unwrap();
+    assert!(table.deck_seed.is_none());
+
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+    assert_eq!(Some(verification.commitment.clone()), commitment);
+    assert!(verification.verify());
+
+    // The undealt cards are the start of the committed deck and the
+    // hole cards came off its end.
+    let deck = verification.deck().unwrap();
+    assert_eq!(deck[..remaining.len()].to_vec(), remaining);
+    for card in dealt {
+        assert!(deck[remaining.len()..].contains(&card));
+    }
+}
+
+#[test]
+fn test_tampered_seed_fails_verification() {
+    let (mut table, _, _) = create_started_table();
+    table.showdown().unwrap();
+
+    let mut verification = table.verify_hand(table.round_ticker).unwrap();
+    verification.seed[0] ^= 1;
+    assert!(!verification.verify());
+}
@@ -0,0 +1,175 @@
+use candid::Principal;
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    hand_history::{group_logs_by_hand, HandHistory},
+    replay::HandReplay,
+    table_functions::{
+        action_log::ActionType,
+        table::Table,
+        tests::{create_test_table, get_table_config, principal, start_hand},
+        types::{BetType, DealStage},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn current_player(table: &Table) -> Principal {
+    table
+        .get_player_at_seat(table.current_player_index)
+        .unwrap()
+}
+
+fn played_hand(table: &Table) -> HandHistory {
+    group_logs_by_hand(table.id, &table.action_logs)
+        .pop()
+        .unwrap()
+}
+
+/// Raises preflop, folds one player and checks the hand down.
+fn play_hand(table: &mut Table) {
+    let raiser = current_player(table);
+    assert_eq!(
+        table.bet(raiser, BetType::Raised(convert_to_e8s(3.0))),
+        Ok(())
+    );
+    let folder = current_player(table);
+    assert_eq!(table.user_fold(folder, false), Ok(()));
+    let caller = current_player(table);
+    assert_eq!(table.bet(caller, BetType::Called), Ok(()));
+
+    while table.sorted_users.is_none() {
+        let user_principal = current_player(table);
+        assert_eq!(table.user_check(user_principal, false), Ok(()));
+    }
+    assert_eq!(table.deal_stage, DealStage::Showdown);
+}
+
+#[test]
+fn test_replay_rebuilds_played_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    play_hand(&mut table);
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+
+    let replay =
+        HandReplay::new(table.config.clone(), &played_hand(&table), &verification).unwrap();
+    let replayed = replay.replay_to_end().unwrap();
+
+    assert_eq!(replayed.community_cards, table.community_cards);
+    assert_eq!(replayed.rake_total, table.rake_total);
+    for (user_principal, user) in table.users.users.iter() {
+        assert_eq!(
+            replayed.users.get(user_principal).unwrap().balance,
+            user.balance
+        );
+    }
+}
+
+#[test]
+fn test_replay_snapshots() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    play_hand(&mut table);
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+    let hand = played_hand(&table);
+    let actions = hand
+        .logs
+        .iter()
+        .filter(|log| {
+            matches!(
+                log.action_type,
+                ActionType::Fold | ActionType::Check | ActionType::Call | ActionType::Raise { .. }
+            )
+        })
+        .count();
+
+    let snapshots = HandReplay::new(table.config.clone(), &hand, &verification)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // The deal and then every action.
+    assert_eq!(snapshots.len(), actions + 1);
+    assert_eq!(snapshots[0].deal_stage, DealStage::Flop);
+    assert!(snapshots[0].sorted_users.is_none());
+    let last = snapshots.last().unwrap();
+    assert_eq!(last.deal_stage, DealStage::Showdown);
+    assert_eq!(last.action_logs.len(), hand.logs.len());
+}
+
+#[test]
+fn test_replay_all_in() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    let all_in_user = principal(2);
+    table.users.get_mut(&all_in_user).unwrap().balance = convert_to_e8s(50.0);
+    start_hand(&mut table);
+    // The big blind goes all in once the others limped in,
+    // the others call and check the hand down.
+    while table.sorted_users.is_none() {
+        let user_principal = current_player(&table);
+        let current_total_bet = table.get_users_current_total_bet(user_principal).unwrap();
+        if user_principal == all_in_user {
+            assert_eq!(
+                table.bet(user_principal, BetType::Raised(convert_to_e8s(50.0))),
+                Ok(())
+            );
+        } else if current_total_bet < table.highest_bet {
+            assert_eq!(table.bet(user_principal, BetType::Called), Ok(()));
+        } else {
+            assert_eq!(table.user_check(user_principal, false), Ok(()));
+        }
+    }
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+
+    let replayed = HandReplay::new(table.config.clone(), &played_hand(&table), &verification)
+        .unwrap()
+        .replay_to_end()
+        .unwrap();
+    assert_eq!(replayed.community_cards, table.community_cards);
+    for (user_principal, user) in table.users.users.iter() {
+        assert_eq!(
+            replayed.users.get(user_principal).unwrap().balance,
+            user.balance
+        );
+    }
+}
+
+#[test]
+fn test_replay_detects_wrong_winnings() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    play_hand(&mut table);
+    let verification = table.verify_hand(table.round_ticker).unwrap();
+    let mut hand = played_hand(&table);
+    for log in hand.logs.iter_mut() {
+        if let ActionType::HandFinished { winnings, .. } = &mut log.action_type {
+            winnings.reverse();
+            winnings[0].1 += 1;
+        }
+    }
+
+    let err = HandReplay::new(table.config.clone(), &hand, &verification)
+        .unwrap()
+        .replay_to_end()
+        .unwrap_err();
+    assert!(matches!(err.into_inner(), GameError::ReplayMismatch { .. }));
+}
+
+#[test]
+fn test_replay_rejects_seed_of_other_hand() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 3);
+    let mut table = create_test_table(config, 3);
+    start_hand(&mut table);
+    play_hand(&mut table);
+    let mut verification = table.verify_hand(table.round_ticker).unwrap();
+    verification.hand_id += 1;
+
+    let err =
+        HandReplay::new(table.config.clone(), &played_hand(&table), &verification).unwrap_err();
+    assert!(matches!(err.into_inner(), GameError::ReplayMismatch { .. }));
+}
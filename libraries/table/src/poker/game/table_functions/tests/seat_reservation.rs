@@ -0,0 +1,95 @@
+use errors::game_error::GameError;
+
+use crate::poker::game::{
+    table_functions::{
+        seat_reservation::SEAT_RESERVATION_SECONDS,
+        tests::{create_test_table, create_user, get_table_config, principal},
+        types::{NotificationMessage, SeatStatus},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+const NOW: u64 = 1_000_000_000_000;
+
+#[test]
+fn test_reserve_seat_holds_it_and_notifies_the_user() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 1);
+
+    assert_eq!(table.reserve_seat(principal(1), NOW), Ok(1));
+    assert_eq!(
+        table.seats[1],
+        SeatStatus::Reserved {
+            principal: principal(1),
+            timestamp: NOW,
+        }
+    );
+    assert_eq!(table.reserved_seat(principal(1)), Some(1));
+
+    let notification = table.notifications.notifications.last().unwrap();
+    assert_eq!(notification.user_principal, principal(1));
+    assert_eq!(
+        notification.message,
+        NotificationMessage::SeatAvailable {
+            seat_index: 1,
+            expires_at: NOW + SEAT_RESERVATION_SECONDS * 1_000_000_000,
+        }
+    );
+}
+
+#[test]
+fn test_only_the_waiting_user_can_take_a_reserved_seat() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 1);
+    assert_eq!(table.reserve_seat(principal(1), NOW), Ok(1));
+
+    let user = create_user(principal(2), convert_to_e8s(100.0));
+    assert!(table.add_user(user, 1, false).is_err());
+
+    let user = create_user(principal(1), convert_to_e8s(100.0));
+    assert!(table.add_user(user, 1, false).is_ok());
+    assert_eq!(table.seats[1], SeatStatus::Occupied(principal(1)));
+    assert_eq!(table.reserved_seat(principal(1)), None);
+}
+
+#[test]
+fn test_expired_reservations_are_released() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 1);
+    assert_eq!(table.reserve_seat(principal(1), NOW), Ok(1));
+    let later = NOW + 30 * 1_000_000_000;
+    assert_eq!(table.reserve_seat(principal(2), later), Ok(2));
+
+    let expiry = NOW + SEAT_RESERVATION_SECONDS * 1_000_000_000;
+    assert!(table.release_expired_reservations(expiry - 1).is_empty());
+    assert_eq!(
+        table.release_expired_reservations(expiry),
+        vec![principal(1)]
+    );
+    assert_eq!(table.seats[1], SeatStatus::Empty);
+    assert_eq!(table.reserved_seat(principal(2)), Some(2));
+}
+
+#[test]
+fn test_reserve_seat_errors() {
+    let config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 3);
+    let mut table = create_test_table(config, 1);
+
+    assert_eq!(
+        table
+            .reserve_seat(principal(0), NOW)
+            .map_err(|e| e.into_inner()),
+        Err(GameError::UserAlreadyInGame)
+    );
+
+    assert_eq!(table.reserve_seat(principal(1), NOW), Ok(1));
+    assert_eq!(table.reserve_seat(principal(2), NOW), Ok(2));
+    let user = create_user(principal(3), convert_to_e8s(100.0));
+    assert_eq!(
+        table
+            .reserve_seat(user.principal_id, NOW)
+            .map_err(|e| e.into_inner()),
+        Err(GameError::GameFull)
+    );
+}
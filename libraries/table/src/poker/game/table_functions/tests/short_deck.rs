@@ -0,0 +1,97 @@
+use candid::Principal;
+
+use crate::poker::{
+    core::{Card, Suit, Value},
+    game::{
+        table_functions::{
+            table::Table,
+            tests::{create_user, get_table_config},
+            types::BetType,
+        },
+        types::GameType,
+        utils::convert_to_e8s,
+    },
+};
+
+fn create_short_deck_table() -> (Table, Principal, Principal) {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    config.is_short_deck = Some(true);
+    let mut table = Table::new(
+        Principal::anonymous(),
+        config,
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    );
+    let user1 = create_user(
+        Principal::from_text("2chl6-4hpzw-vqaaa-aaaaa-c").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+    let user2 = create_user(
+        Principal::from_text("br5f7-7uaaa-aaaaa-qaaca-cai").expect("Could not decode principal"),
+        convert_to_e8s(100.0),
+    );
+
+    assert!(table.add_user(user1.clone(), 0, false).is_ok());
+    assert!(table.add_user(user2.clone(), 1, false).is_ok());
+
+    assert_eq!(
+        table.start_betting_round(vec![0, 1, 2, 3, 4, 5, 6, 7, 8]),
+        Ok((Vec::new(), Vec::new()))
+    );
+
+    (table, user1.principal_id, user2.principal_id)
+}
+
+#[test]
+fn test_short_deck_deals_from_36_cards() {
+    let (table, user1, user2) = create_short_deck_table();
+
+    assert_eq!(table.deck.len(), 36 - 4);
+    for user in [user1, user2] {
+        for card in table.get_user_table_data(user).unwrap().cards.iter() {
+            assert!(card.value >= Value::Six);
+        }
+    }
+}
+
+#[test]
+fn test_short_deck_flush_beats_full_house() {
+    let (mut table, user1, user2) = create_short_deck_table();
+
+    assert_eq!(
+        table.bet(user1, BetType::Raised(convert_to_e8s(10.0))),
+        Ok(())
+    );
+    assert_eq!(table.bet(user2, BetType::Called), Ok(()));
+
+    // Board: [K♥, K♠, 7♥, 9♥, Q♦]
+    table.community_cards = vec![
+        Card::new(Value::King, Suit::Heart),
+        Card::new(Value::King, Suit::Spade),
+        Card::new(Value::Seven, Suit::Heart),
+        Card::new(Value::Nine, Suit::Heart),
+        Card::new(Value::Queen, Suit::Diamond),
+    ];
+
+    // Player 1: [A♥, 6♥] makes a flush.
+    table.get_user_table_data_mut(user1).unwrap().cards = vec![
+        Card::new(Value::Ace, Suit::Heart),
+        Card::new(Value::Six, Suit::Heart),
+    ];
+
+    // Player 2: [K♦, Q♣] makes a full house.
+    table.get_user_table_data_mut(user2).unwrap().cards = vec![
+        Card::new(Value::King, Suit::Diamond),
+        Card::new(Value::Queen, Suit::Club),
+    ];
+
+    table.showdown().unwrap();
+
+    assert_eq!(
+        table.users.get(&user1).unwrap().balance,
+        convert_to_e8s(110.0)
+    );
+    assert_eq!(
+        table.users.get(&user2).unwrap().balance,
+        convert_to_e8s(90.0)
+    );
+}
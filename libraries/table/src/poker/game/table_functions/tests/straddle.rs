@@ -0,0 +1,134 @@
+use crate::poker::game::{
+    table_functions::{
+        action_log::ActionType,
+        table::{StraddleType, TableConfig},
+        tests::{create_test_table, get_table_config, start_hand},
+        types::{BetType, DealStage, PlayerAction},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn straddle_config(straddle_type: Option<StraddleType>) -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(0.5)), 4);
+    config.straddle_type = straddle_type;
+    config
+}
+
+#[test]
+fn test_utg_straddle_is_posted() {
+    let mut table = create_test_table(straddle_config(Some(StraddleType::Utg)), 4);
+    let utg_uid = table.get_player_at_seat(3).unwrap();
+    assert_eq!(table.set_straddle_next_hand(utg_uid, true), Ok(()));
+    start_hand(&mut table);
+
+    assert_eq!(table.straddle_user_principal, Some(utg_uid));
+    assert_eq!(table.highest_bet, convert_to_e8s(2.0));
+    assert_eq!(
+        table.get_user_table_data(utg_uid).unwrap().player_action,
+        PlayerAction::Raised(convert_to_e8s(2.0))
+    );
+    assert_eq!(
+        table.users.get(&utg_uid).unwrap().balance,
+        convert_to_e8s(98.0)
+    );
+    assert!(table
+        .action_logs
+        .iter()
+        .any(|log| log.user_principal == Some(utg_uid)
+            && log.action_type
+                == ActionType::Straddle {
+                    amount: convert_to_e8s(2.0)
+                }));
+
+    // The choice only applies to a single hand.
+    assert_eq!(
+        table
+            .get_user_table_data(utg_uid)
+            .unwrap()
+            .straddle_next_hand,
+        None
+    );
+}
+
+#[test]
+fn test_straddle_not_posted_without_opt_in() {
+    let mut table = create_test_table(straddle_config(Some(StraddleType::Utg)), 4);
+    start_hand(&mut table);
+
+    assert_eq!(table.straddle_user_principal, None);
+    assert_eq!(table.highest_bet, convert_to_e8s(1.0));
+    // Without a straddle the player after the big blind acts first.
+    assert_eq!(table.current_player_index, 3);
+}
+
+#[test]
+fn test_utg_straddle_action_order() {
+    let mut table = create_test_table(straddle_config(Some(StraddleType::Utg)), 4);
+    let dealer_uid = table.get_player_at_seat(0).unwrap();
+    let small_blind_uid = table.get_player_at_seat(1).unwrap();
+    let big_blind_uid = table.get_player_at_seat(2).unwrap();
+    let utg_uid = table.get_player_at_seat(3).unwrap();
+    assert_eq!(table.set_straddle_next_hand(utg_uid, true), Ok(()));
+    start_hand(&mut table);
+
+    // The player after the straddler acts first.
+    assert_eq!(table.current_player_index, 0);
+    assert_eq!(table.bet(dealer_uid, BetType::Called), Ok(()));
+    assert_eq!(table.bet(small_blind_uid, BetType::Called), Ok(()));
+    assert_eq!(table.bet(big_blind_uid, BetType::Called), Ok(()));
+
+    // Everyone called, but the straddler still has the option.
+    assert_eq!(table.deal_stage, DealStage::Flop);
+    assert!(table.is_players_turn(utg_uid));
+    assert_eq!(table.user_check(utg_uid, false), Ok(()));
+
+    assert_eq!(table.deal_stage, DealStage::Turn);
+}
+
+#[test]
+fn test_straddler_can_raise_option() {
+    let mut table = create_test_table(straddle_config(Some(StraddleType::Utg)), 4);
+    let dealer_uid = table.get_player_at_seat(0).unwrap();
+    let small_blind_uid = table.get_player_at_seat(1).unwrap();
+    let big_blind_uid = table.get_player_at_seat(2).unwrap();
+    let utg_uid = table.get_player_at_seat(3).unwrap();
+    assert_eq!(table.set_straddle_next_hand(utg_uid, true), Ok(()));
+    start_hand(&mut table);
+
+    assert_eq!(table.bet(dealer_uid, BetType::Called), Ok(()));
+    assert_eq!(table.user_fold(small_blind_uid, false), Ok(()));
+    assert_eq!(table.user_fold(big_blind_uid, false), Ok(()));
+
+    let legal_actions = table.legal_actions(utg_uid).unwrap();
+    assert!(legal_actions.can_check);
+    assert_eq!(legal_actions.min_raise, Some(convert_to_e8s(2.0) + 1));
+
+    assert_eq!(
+        table.bet(utg_uid, BetType::Raised(convert_to_e8s(6.0))),
+        Ok(())
+    );
+    assert_eq!(table.deal_stage, DealStage::Flop);
+    assert!(table.is_players_turn(dealer_uid));
+}
+
+#[test]
+fn test_button_straddle_action_order() {
+    let mut table = create_test_table(straddle_config(Some(StraddleType::Button)), 4);
+    let dealer_uid = table.get_player_at_seat(0).unwrap();
+    assert_eq!(table.set_straddle_next_hand(dealer_uid, true), Ok(()));
+    start_hand(&mut table);
+
+    assert_eq!(table.straddle_user_principal, Some(dealer_uid));
+    assert_eq!(table.highest_bet, convert_to_e8s(2.0));
+    // The small blind acts first and the button acts last.
+    assert_eq!(table.current_player_index, 1);
+}
+
+#[test]
+fn test_straddle_toggle_rejected_when_disabled() {
+    let mut table = create_test_table(straddle_config(None), 4);
+    let utg_uid = table.get_player_at_seat(3).unwrap();
+
+    assert!(table.set_straddle_next_hand(utg_uid, true).is_err());
+}
@@ -0,0 +1,107 @@
+use candid::Principal;
+
+use crate::poker::game::{
+    table_functions::{
+        table::{Table, TableConfig},
+        tests::{create_test_table, get_table_config, principal},
+    },
+    types::GameType,
+    utils::convert_to_e8s,
+};
+
+fn time_bank_config(
+    time_bank_seconds: Option<u16>,
+    time_bank_refill_seconds: Option<u16>,
+    time_bank_refill_hands: Option<u16>,
+) -> TableConfig {
+    let mut config = get_table_config(GameType::NoLimit(convert_to_e8s(1.0)), 2);
+    config.time_bank_seconds = time_bank_seconds;
+    config.time_bank_refill_seconds = time_bank_refill_seconds;
+    config.time_bank_refill_hands = time_bank_refill_hands;
+    config
+}
+
+fn time_bank(table: &Table, user_principal: Principal) -> Option<u64> {
+    table.get_user_table_data(user_principal).unwrap().time_bank
+}
+
+#[test]
+fn test_time_bank_starts_at_configured_amount() {
+    let table = create_test_table(time_bank_config(Some(30), None, None), 2);
+    let (uid1, uid2) = (principal(0), principal(1));
+
+    assert_eq!(time_bank(&table, uid1), Some(30));
+    assert_eq!(time_bank(&table, uid2), Some(30));
+    assert_eq!(table.available_time_bank(uid1), Some(30));
+}
+
+#[test]
+fn test_no_time_bank_when_disabled() {
+    let table = create_test_table(time_bank_config(None, None, None), 2);
+    let uid1 = principal(0);
+
+    assert_eq!(time_bank(&table, uid1), None);
+    assert_eq!(table.available_time_bank(uid1), None);
+}
+
+#[test]
+fn test_settle_time_bank_deducts_used_time() {
+    let mut table = create_test_table(time_bank_config(Some(30), None, None), 2);
+    let (uid1, uid2) = (principal(0), principal(1));
+
+    table.set_last_timer_started_timestamp(1_000_000_000);
+    table.time_bank_user_principal = Some(uid1);
+    // The time bank can't be drawn twice in the same turn.
+    assert_eq!(table.available_time_bank(uid1), None);
+
+    table.settle_time_bank(13_500_000_000);
+    assert_eq!(table.time_bank_user_principal, None);
+    assert_eq!(time_bank(&table, uid1), Some(18));
+    assert_eq!(time_bank(&table, uid2), Some(30));
+
+    // Settling again doesn't deduct anything.
+    table.settle_time_bank(40_000_000_000);
+    assert_eq!(time_bank(&table, uid1), Some(18));
+}
+
+#[test]
+fn test_time_bank_settled_when_next_timer_starts() {
+    let mut table = create_test_table(time_bank_config(Some(30), None, None), 2);
+    let uid1 = principal(0);
+
+    table.set_last_timer_started_timestamp(0);
+    table.time_bank_user_principal = Some(uid1);
+    table.set_last_timer_started_timestamp(60_000_000_000);
+
+    assert_eq!(time_bank(&table, uid1), Some(0));
+    assert_eq!(table.available_time_bank(uid1), None);
+}
+
+#[test]
+fn test_time_bank_refills_every_configured_hands() {
+    let mut table = create_test_table(time_bank_config(Some(30), Some(5), Some(2)), 2);
+    let uid1 = principal(0);
+    let bytes = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+
+    assert!(table.start_betting_round(bytes.clone()).is_ok());
+    assert_eq!(table.round_ticker, 1);
+    assert_eq!(time_bank(&table, uid1), Some(30));
+
+    table.round_ticker += 1;
+    table.refill_time_banks();
+    assert_eq!(time_bank(&table, uid1), Some(35));
+
+    table.round_ticker += 1;
+    table.refill_time_banks();
+    assert_eq!(time_bank(&table, uid1), Some(35));
+}
+
+#[test]
+fn test_add_time_bank_for_new_level() {
+    let mut table = create_test_table(time_bank_config(None, None, None), 2);
+    let (uid1, uid2) = (principal(0), principal(1));
+
+    table.add_time_bank(20);
+    assert_eq!(time_bank(&table, uid1), Some(20));
+    assert_eq!(time_bank(&table, uid2), Some(20));
+}
@@ -0,0 +1,68 @@
+use candid::Principal;
+
+use super::table::Table;
+
+impl Table {
+    /// The time bank a player starts a session at the table with.
+    pub fn starting_time_bank(&self) -> Option<u64> {
+        self.config.time_bank_seconds.map(|seconds| seconds as u64)
+    }
+
+    /// Gets the seconds the user can still draw from their time bank
+    /// once their turn timer has run out.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the user has no time left or is already on their time bank.
+    pub fn available_time_bank(&self, user_principal: Principal) -> Option<u64> {
+        if self.time_bank_user_principal == Some(user_principal) {
+            return None;
+        }
+        self.get_user_table_data(user_principal)
+            .ok()?
+            .time_bank
+            .filter(|seconds| *seconds > 0)
+    }
+
+    /// Deducts the time the player on their time bank used up since
+    /// their timer started. Should be called whenever the turn timer
+    /// is cleared or replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `now` - The current time in nanoseconds
+    pub fn settle_time_bank(&mut self, now: u64) {
+        let Some(user_principal) = self.time_bank_user_principal.take() else {
+            return;
+        };
+        let used_seconds = now.saturating_sub(self.last_timer_started_timestamp) / 1_000_000_000;
+        if let Ok(user_table_data) = self.get_user_table_data_mut(user_principal) {
+            user_table_data.time_bank = user_table_data
+                .time_bank
+                .map(|seconds| seconds.saturating_sub(used_seconds));
+        }
+    }
+
+    /// Adds time to the time bank of every player at the table.
+    /// Used when a tournament moves to a new blind level.
+    pub fn add_time_bank(&mut self, seconds: u64) {
+        for user_table_data in self.user_table_data.values_mut() {
+            user_table_data.time_bank = Some(user_table_data.time_bank.unwrap_or(0) + seconds);
+        }
+    }
+
+    /// Refills the time banks by the configured amount
+    /// every configured number of hands.
+    pub fn refill_time_banks(&mut self) {
+        let (Some(refill_seconds), Some(refill_hands)) = (
+            self.config.time_bank_refill_seconds,
+            self.config.time_bank_refill_hands,
+        ) else {
+            return;
+        };
+        if refill_hands == 0 || self.round_ticker % refill_hands as u64 != 0 {
+            return;
+        }
+        self.add_time_bank(refill_seconds as u64);
+    }
+}
@@ -4,9 +4,12 @@ use candid::Principal;
 use errors::{game_error::GameError, table_error::TableError};
 use ic_cdk::futures::spawn;
 
-use crate::table_canister::{handle_timer_expiration_wrapper, start_new_betting_round_wrapper};
+use crate::table_canister::{
+    handle_timer_expiration_wrapper, play_bot_turn_wrapper, release_expired_seats_wrapper,
+    resolve_board_runs_wrapper, start_new_betting_round_wrapper,
+};
 
-use super::table::Table;
+use super::{bot::BOT_THINK_SECONDS, seat_reservation::SEAT_RESERVATION_SECONDS, table::Table};
 
 impl Table {
     /// Sets a turn timer for a given user on a table.
@@ -26,6 +29,11 @@ impl Table {
         // If a timer already exists, clear it before setting a new one
         self.clear_turn_timer();
 
+        if self.is_bot(user_id) {
+            self.start_bot_turn_timer(user_id);
+            return;
+        }
+
         let table_principal = self.id;
         let timer_id: ic_cdk_timers::TimerId = ic_cdk_timers::set_timer(delay, move || {
             spawn(async move {
@@ -49,6 +57,50 @@ impl Table {
         self.timer = Some(timer_id);
     }
 
+    /// Sets a timer after which the bot takes its turn. A bot that
+    /// fails to act is checked or folded like a player whose time ran
+    /// out.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_id`: The principal of the bot
+    fn start_bot_turn_timer(&mut self, user_id: Principal) {
+        let delay = Duration::from_secs(BOT_THINK_SECONDS);
+
+        let table_principal = self.id;
+        let timer_id: ic_cdk_timers::TimerId = ic_cdk_timers::set_timer(delay, move || {
+            spawn(async move {
+                if let Err(err) = play_bot_turn_wrapper(table_principal, user_id).await {
+                    ic_cdk::println!("Error playing bot turn: {:?}", err);
+                    let _ = handle_timer_expiration_wrapper(table_principal, user_id).await;
+                }
+            })
+        });
+
+        self.timer = Some(timer_id);
+    }
+
+    /// Gives the user their time bank once their turn timer has run out.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_id`: The principal of the user whose turn timer ran out
+    ///
+    /// # Returns
+    ///
+    /// Whether the time bank was started. If not, the user should be
+    /// checked or folded as usual.
+    pub fn start_time_bank_timer(&mut self, user_id: Principal) -> bool {
+        let Some(time_bank) = self.available_time_bank(user_id) else {
+            return false;
+        };
+
+        self.set_last_timer_started_timestamp(ic_cdk::api::time());
+        self.start_turn_timer(user_id, time_bank);
+        self.time_bank_user_principal = Some(user_id);
+        true
+    }
+
     /// Sets a timer to start the next turn.
     ///
     /// # Parameters
@@ -94,8 +146,68 @@ impl Table {
         });
     }
 
+    /// Sets a timer after which the board is run as many times as the
+    /// players agreed to so far.
+    ///
+    /// # Parameters
+    ///
+    /// - `delay_seconds`: The number of seconds the players have to choose
+    pub fn start_board_runs_timer(&mut self, delay_seconds: u64) {
+        let delay = Duration::from_secs(delay_seconds);
+        self.clear_turn_timer();
+
+        let table_principal = self.id;
+        let timer_id: ic_cdk_timers::TimerId = ic_cdk_timers::set_timer(delay, move || {
+            spawn(async move {
+                let mut retries = 0;
+                while retries < 3 {
+                    match resolve_board_runs_wrapper(table_principal).await {
+                        Ok(_) => return,
+                        Err(err) => {
+                            ic_cdk::println!(
+                                "Error resolving board runs: {:?}\nAttempting retry after delay...",
+                                err
+                            );
+                        }
+                    }
+
+                    retries += 1;
+                }
+            })
+        });
+
+        self.timer = Some(timer_id);
+    }
+
+    /// Sets a timer after which the seats nobody took in time are
+    /// freed up again.
+    pub fn start_seat_reservation_timer(&self) {
+        let delay = Duration::from_secs(SEAT_RESERVATION_SECONDS);
+
+        let table_principal = self.id;
+        let _: ic_cdk_timers::TimerId = ic_cdk_timers::set_timer(delay, move || {
+            spawn(async move {
+                let mut retries = 0;
+                while retries < 3 {
+                    match release_expired_seats_wrapper(table_principal).await {
+                        Ok(_) => return,
+                        Err(err) => {
+                            ic_cdk::println!(
+                                "Error releasing expired seats: {:?}\nAttempting retry after delay...",
+                                err
+                            );
+                        }
+                    }
+
+                    retries += 1;
+                }
+            })
+        });
+    }
+
     /// Clears the timer for a user.
     pub fn clear_turn_timer(&mut self) {
+        self.settle_time_bank(ic_cdk::api::time());
         if let Some(timer_id) = self.timer {
             ic_cdk_timers::clear_timer(timer_id);
             self.timer = None;
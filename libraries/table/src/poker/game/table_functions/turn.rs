@@ -10,6 +10,10 @@ use super::{
 impl Table {
     /// Check if the table should go to the next round or showdown
     pub fn check_next_turn_or_showdown(&mut self) -> Result<(), TracedError<GameError>> {
+        // A pre action taken along the way may have finished the hand already
+        if self.sorted_users.is_some() {
+            return Ok(());
+        }
         if self.go_to_next_round().map_err(|e| trace_err!(e, ""))?
             && self.deal_stage != DealStage::Showdown
         {
@@ -32,7 +36,11 @@ impl Table {
             .map_err(|e| trace_err!(e, ""))?
         {
             self.calculate_pots().map_err(|e| trace_err!(e, ""))?;
-            self.cycle_to_showdown().map_err(|e| trace_err!(e, ""))?;
+            self.annotate_all_in_equities();
+            // The players may agree to run the board more than once first
+            if !self.offer_board_runs() {
+                self.cycle_to_showdown().map_err(|e| trace_err!(e, ""))?;
+            }
         }
         Ok(())
     }
@@ -184,6 +192,10 @@ impl Table {
                 let highest_bet = self
                     .get_highest_current_total_bet()
                     .map_err(|e| trace_err!(e, ""))?;
+                // The straddler gets the option to raise preflop even if nobody raised
+                let has_straddle_option = self.deal_stage == DealStage::Flop
+                    && self.straddle_user_principal == Some(*user_principal)
+                    && !self.has_acted_this_round(*user_principal);
                 if let Some(user_table_data) = self.user_table_data.get_mut(user_principal) {
                     // Skip players who are folded, sitting out, joining, or all-in
                     if matches!(
@@ -201,6 +213,10 @@ impl Table {
                         return Ok(false);
                     }
 
+                    if has_straddle_option {
+                        return Ok(false);
+                    }
+
                     if let PlayerAction::Raised(amount) = user_table_data.player_action {
                         if amount == highest_bet
                             && self.big_blind_user_principal == *user_principal
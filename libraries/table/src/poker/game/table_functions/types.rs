@@ -78,6 +78,19 @@ pub enum BetType {
     SmallBlind,
     /// Bet is equal to the ante.
     Ante(u64),
+    /// A voluntary live blind of twice the big blind.
+    Straddle,
+}
+
+impl BetType {
+    /// Whether the bet is posted before the cards are dealt
+    /// rather than being an action the player took on their turn.
+    pub fn is_forced(&self) -> bool {
+        matches!(
+            self,
+            BetType::SmallBlind | BetType::BigBlind | BetType::Straddle
+        )
+    }
 }
 
 /// Data for a user at the table.
@@ -101,6 +114,97 @@ pub struct UserTableData {
     pub experience_points: u64,
     /// Whether the user has auto check fold enabled.
     pub auto_check_fold: bool,
+    /// The transport public key the user's card keys are delivered under.
+    pub transport_public_key: Option<Vec<u8>>,
+    /// The hole cards of the current hand encrypted for the user.
+    pub encrypted_cards: Option<Vec<u8>>,
+    /// Whether the user wants to straddle in the next hand.
+    pub straddle_next_hand: Option<bool>,
+    /// The seconds left in the user's time bank.
+    pub time_bank: Option<u64>,
+    /// The blinds the user owes before they are dealt in again.
+    pub missed_blinds: Option<MissedBlinds>,
+    /// How the user wants to settle the blinds they owe.
+    pub blind_choice: Option<BlindChoice>,
+    /// The user's auto top up and auto rebuy settings.
+    pub auto_top_up: Option<AutoTopUp>,
+    /// Whether the user busted and sits out until their auto rebuy lands.
+    pub awaiting_rebuy: Option<bool>,
+    /// The action the user queued to take once it is their turn.
+    pub pre_action: Option<QueuedPreAction>,
+}
+
+/// The blinds a player missed while sitting out.
+/// A player joining a table that has already dealt owes a big blind.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct MissedBlinds {
+    pub small_blind: bool,
+    pub big_blind: bool,
+}
+
+impl MissedBlinds {
+    /// Whether any blind is owed.
+    pub fn is_owed(&self) -> bool {
+        self.small_blind || self.big_blind
+    }
+}
+
+/// How a player who owes blinds gets dealt back in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub enum BlindChoice {
+    /// Post the owed blinds and play the next hand.
+    /// The big blind is live and the small blind is dead.
+    PostDeadBlinds,
+    /// Sit out until the big blind reaches the player.
+    WaitForBigBlind,
+}
+
+/// Keeps a cash game stack topped up between hands from the
+/// user's allowance.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct AutoTopUp {
+    /// Top up when the stack drops below this many big blinds.
+    pub threshold_big_blinds: u16,
+    /// The stack in big blinds to top up to.
+    pub target_big_blinds: u16,
+    /// Whether to buy back in to the target after busting.
+    pub auto_rebuy: bool,
+}
+
+/// An action a user queues before their turn, taken as soon as the
+/// action reaches them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub enum PreAction {
+    /// Check, dropped if there is a bet to call.
+    Check,
+    /// Check, or fold to a bet.
+    CheckFold,
+    /// Call any bet, or check if there is nothing to call.
+    CallAny,
+    /// Call the given amount, dropped if the bet to call changes.
+    Call(u64),
+    /// Raise to the given total, dropped if the bet to call changes.
+    RaiseTo(u64),
+    /// Bet or raise the size of the pot, dropped if the bet to call changes.
+    BetPot,
+}
+
+/// A pre action and the situation it was queued in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct QueuedPreAction {
+    pub pre_action: PreAction,
+    /// The highest bet of the round when the pre action was queued.
+    pub highest_bet: u64,
+}
+
+/// The preset strategies a bot can play with.
+/// Bots only ever sit at play money tables.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub enum BotPersonality {
+    /// Plays few hands and rarely raises.
+    TightPassive,
+    /// Plays many hands and bets them hard.
+    LooseAggressive,
 }
 
 /// Data for a card request.
@@ -132,6 +236,15 @@ impl UserTableData {
             seated_out_turns: 0,
             experience_points: 0,
             auto_check_fold: false,
+            transport_public_key: None,
+            encrypted_cards: None,
+            straddle_next_hand: None,
+            time_bank: None,
+            missed_blinds: None,
+            blind_choice: None,
+            auto_top_up: None,
+            awaiting_rebuy: None,
+            pre_action: None,
         }
     }
 
@@ -144,6 +257,8 @@ impl UserTableData {
         self.show_card_requests.clear();
         self.experience_points = 0;
         self.auto_check_fold = false;
+        self.encrypted_cards = None;
+        self.pre_action = None;
     }
 }
 
@@ -194,6 +309,11 @@ pub struct Notification {
 #[derive(Debug, Clone, Serialize, Deserialize, CandidType, PartialEq, Eq)]
 pub enum NotificationMessage {
     UserTurnStarted,
+    /// A seat was reserved for the user coming off the waitlist.
+    SeatAvailable {
+        seat_index: u8,
+        expires_at: u64,
+    },
 }
 
 impl Notification {
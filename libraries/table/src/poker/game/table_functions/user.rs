@@ -182,14 +182,17 @@ impl Table {
             ))));
         }
 
-        // Check if user is already at the table
-        if self.is_user_in_table(user.principal_id) {
+        // Check if user is already at the table, a seat held for them doesn't count
+        let reserved_seat = self.reserved_seat(user.principal_id);
+        if self.is_user_in_table(user.principal_id) && reserved_seat != Some(seat_index) {
             return Err(trace_err!(TracedError::new(GameError::UserAlreadyInGame)));
         }
 
-        // Verify the seat is available
+        // Verify the seat is available or held for the user
+        let is_seat_free = self.seats[seat_index as usize] == SeatStatus::Empty
+            || reserved_seat == Some(seat_index);
         match self.seats[seat_index as usize] {
-            SeatStatus::Empty => {
+            SeatStatus::Empty | SeatStatus::Reserved { .. } if is_seat_free => {
                 // Set appropriate seat status based on game state
                 if self.is_game_ongoing() {
                     self.seats[seat_index as usize] = SeatStatus::QueuedForNextRound(
@@ -201,8 +204,12 @@ impl Table {
                     self.seats[seat_index as usize] = SeatStatus::Occupied(user.principal_id);
 
                     // Initialize user data
+                    let user_table_data = UserTableData {
+                        time_bank: self.starting_time_bank(),
+                        ..UserTableData::new()
+                    };
                     self.user_table_data
-                        .insert(user.principal_id, UserTableData::new());
+                        .insert(user.principal_id, user_table_data);
                     if player_sitting_out {
                         self.get_user_table_data_mut(user.principal_id)?
                             .player_action = PlayerAction::SittingOut;
@@ -210,6 +217,7 @@ impl Table {
                         self.get_user_table_data_mut(user.principal_id)?
                             .player_action = PlayerAction::Joining;
                     }
+                    self.mark_new_player_blinds(user.principal_id);
 
                     // Add user and log action
                     self.users.add_user(user.clone())?;
@@ -285,6 +293,7 @@ impl Table {
             self.log_action(Some(user_principal), action_type);
             self.user_table_data.remove(&user_principal);
             self.users.remove_user(user_principal);
+            self.bots.remove(&user_principal);
         }
 
         Ok(())
@@ -482,6 +491,59 @@ impl Table {
         Ok(user.balance)
     }
 
+    /// Adds the `amount` to the stack of the user by `user_principal`.
+    /// A deposit made during a hand is counted into the hand's chip audit.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user to deposit to.
+    /// - `amount` - The amount to deposit.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user is not found.
+    pub fn deposit_to_stack(
+        &mut self,
+        user_principal: Principal,
+        amount: u64,
+    ) -> Result<(), TracedError<GameError>> {
+        self.users
+            .get_mut(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?
+            .deposit(amount);
+        self.audit_deposit(user_principal, amount);
+        Ok(())
+    }
+
+    /// Takes the `amount` from the stack of the user by `user_principal`.
+    /// A withdrawal made during a hand is taken out of the hand's chip audit.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The principal of the user to withdraw from.
+    /// - `amount` - The amount to withdraw.
+    ///
+    /// # Errors
+    ///
+    /// - [`GameError::PlayerNotFound`] if the user is not found.
+    /// - [`GameError::InsufficientFunds`] if the stack is smaller than the amount.
+    pub fn withdraw_from_stack(
+        &mut self,
+        user_principal: Principal,
+        amount: u64,
+    ) -> Result<(), TracedError<GameError>> {
+        let user = self
+            .users
+            .get_mut(&user_principal)
+            .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound)))?;
+        if user.balance < amount {
+            return Err(trace_err!(TracedError::new(GameError::InsufficientFunds)));
+        }
+        user.withdraw(amount);
+        self.audit_withdrawal(user_principal, amount);
+        Ok(())
+    }
+
     /// Returns the current total bet of the user by `user_principal`
     ///
     /// # Parameters
@@ -150,6 +150,14 @@ impl Table {
             }
         }
 
+        // The pre action continues the hand from the player after them
+        if self
+            .execute_pre_action()
+            .map_err(|e| trace_err!(e, "Failed to execute pre action."))?
+        {
+            return Ok(());
+        }
+
         #[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
         {
             let current_player = self
@@ -231,6 +239,13 @@ impl Table {
             ))));
         }
 
+        if let Some(starting_player_index) = self.calculate_straddle_starting_player_index() {
+            return Ok(starting_player_index);
+        }
+        if let Some(starting_player_index) = self.calculate_dead_button_starting_player_index() {
+            return Ok(starting_player_index);
+        }
+
         if num_players == 2 {
             // Heads-up play: find the small blind
             return self.calculate_small_blind_user_index();
@@ -385,6 +400,7 @@ impl Table {
                     Some(table_data) => {
                         table_data.total_bet += table_data.current_total_bet;
                         table_data.current_total_bet = 0;
+                        table_data.pre_action = None;
                         if table_data.player_action != PlayerAction::Folded
                             && table_data.player_action != PlayerAction::AllIn
                             && table_data.player_action != PlayerAction::SittingOut
@@ -529,12 +545,14 @@ impl Table {
         }
     }
 
-    /// Sets the last timer started timestamp.
+    /// Sets the last timer started timestamp. Any time bank
+    /// used since the previous timer started is deducted first.
     ///
     /// # Parameters
     ///
     /// - `timestamp` - The timestamp to set
     pub fn set_last_timer_started_timestamp(&mut self, timestamp: u64) {
+        self.settle_time_bank(timestamp);
         self.last_timer_started_timestamp = timestamp;
     }
 
@@ -627,8 +645,12 @@ impl Table {
             self.seats[i] = SeatStatus::Occupied(*principal);
 
             // Initialize user data
+            let user_table_data = UserTableData {
+                time_bank: self.starting_time_bank(),
+                ..UserTableData::new()
+            };
             self.user_table_data
-                .insert(user.principal_id, UserTableData::new());
+                .insert(user.principal_id, user_table_data);
             if *sitting_out {
                 self.get_user_table_data_mut(user.principal_id)
                     .map_err(|e| trace_err!(e, "Error getting user table data to initialise it."))?
@@ -638,6 +660,7 @@ impl Table {
                     .map_err(|e| trace_err!(e, "Error getting user table data to initialise it."))?
                     .player_action = PlayerAction::Joining;
             }
+            self.mark_new_player_blinds(user.principal_id);
 
             // Add user and log action
             self.users.add_user(*user).map_err(|e| {
@@ -690,6 +713,7 @@ impl Table {
         while self.deal_stage != DealStage::Showdown {
             self.deal_cards(true)
                 .map_err(|e| trace_err!(e, "Error dealing cards in cycle to showdown."))?;
+            self.annotate_all_in_equities();
         }
         self.showdown().map_err(|e| trace_err!(e, ""))?;
         Ok(())
@@ -700,6 +724,39 @@ impl Table {
         self.queue.push(item);
     }
 
+    /// Handles the deposits in the queue and leaves the other items
+    /// for the next hand, so deposits land between hands.
+    pub fn handle_queued_deposits(&mut self) {
+        let (deposits, queue): (Vec<QueueItem>, Vec<QueueItem>) = std::mem::take(&mut self.queue)
+            .into_iter()
+            .partition(|item| matches!(item, QueueItem::Deposit(..)));
+        self.queue = queue;
+        for item in deposits {
+            if let QueueItem::Deposit(user_id, users_canister_id, amount) = item {
+                self.spawn_queued_deposit(user_id, users_canister_id, amount);
+            }
+        }
+    }
+
+    fn spawn_queued_deposit(&self, user_id: Principal, users_canister_id: Principal, amount: u64) {
+        if !self.user_table_data.contains_key(&user_id) {
+            ic_cdk::println!("Warning: User data not found for {}", user_id);
+            return;
+        }
+        let table_principal = self.id;
+        ic_cdk::futures::spawn(async move {
+            match deposit_to_table(table_principal, users_canister_id, user_id, amount, true).await
+            {
+                Ok(res) => {
+                    ic_cdk::println!("Deposit successful: {:?}", res);
+                }
+                Err(err) => {
+                    ic_cdk::println!("Error depositing to table: {:?}", err);
+                }
+            }
+        })
+    }
+
     /// Handles the items in the queue.
     #[allow(dependency_on_unit_never_type_fallback)]
     pub fn handle_queue_items(&mut self) -> Result<(), TracedError<GameError>> {
@@ -723,29 +780,7 @@ impl Table {
                     }
                 }
                 QueueItem::Deposit(user_id, users_canister_id, amount) => {
-                    if !self.user_table_data.contains_key(&user_id) {
-                        ic_cdk::println!("Warning: User data not found for {}", user_id);
-                        continue;
-                    }
-                    let table_principal = self.id;
-                    ic_cdk::futures::spawn(async move {
-                        match deposit_to_table(
-                            table_principal,
-                            users_canister_id,
-                            user_id,
-                            amount,
-                            true,
-                        )
-                        .await
-                        {
-                            Ok(res) => {
-                                ic_cdk::println!("Deposit successful: {:?}", res);
-                            }
-                            Err(err) => {
-                                ic_cdk::println!("Error depositing to table: {:?}", err);
-                            }
-                        }
-                    })
+                    self.spawn_queued_deposit(user_id, users_canister_id, amount);
                 }
                 QueueItem::RemoveUser(user_principal, action_type) => {
                     // Find and clear the user's seat
@@ -876,14 +911,21 @@ impl Table {
         Ok(())
     }
 
-    // Removes all the cards from the user table data apart from the user specified by the principal.
+    // Removes all the cards from the user table data apart from the user specified by the principal
+    // and the players whose hands are face up.
     pub fn hide_cards(&mut self, user_id: Principal) -> Result<(), TracedError<GameError>> {
+        let face_up: Vec<Principal> = self
+            .user_table_data
+            .keys()
+            .filter(|user_principal| self.is_hand_face_up(**user_principal))
+            .copied()
+            .collect();
         for (user_principal, table_data) in self.user_table_data.iter_mut() {
             let user = self
                 .users
                 .get(user_principal)
                 .ok_or_else(|| trace_err!(TracedError::new(GameError::PlayerNotFound), ""))?;
-            if user.principal_id != user_id {
+            if user.principal_id != user_id && !face_up.contains(user_principal) {
                 table_data.cards.clear();
             }
         }
@@ -11,9 +11,12 @@ use super::{
     table_functions::{
         action_log::{ActionLog, ActionType},
         ante::AnteType,
+        board_runs::BoardRunsOffer,
+        buy_in::TableExit,
+        provably_fair::HandVerification,
         side_pot::SidePot,
         table::{Table, TableConfig},
-        types::{DealStage, Notifications, SeatStatus, UserTableData},
+        types::{BotPersonality, DealStage, Notifications, SeatStatus, UserTableData},
     },
     users::Users,
 };
@@ -54,6 +57,55 @@ pub enum GameType {
     SpreadLimit(u64, u64), // (min_bet, max_bet)
     // Players can bet up to the amount in the pot.
     PotLimit(u64),
+    /// Pot-limit Omaha with four hole cards.
+    PotLimitOmaha4(u64),
+    /// Pot-limit Omaha with five hole cards.
+    PotLimitOmaha5(u64),
+}
+
+impl GameType {
+    /// The small and big blind.
+    pub fn blinds(&self) -> (u64, u64) {
+        match *self {
+            GameType::NoLimit(small_blind) => (small_blind, small_blind * 2),
+            GameType::SpreadLimit(min, _) => (min / 2, min),
+            GameType::FixedLimit(small, _) => (small / 2, small),
+            GameType::PotLimit(small)
+            | GameType::PotLimitOmaha4(small)
+            | GameType::PotLimitOmaha5(small) => (small, small * 2),
+        }
+    }
+
+    /// The number of hole cards dealt to each player.
+    pub fn hole_cards_count(&self) -> usize {
+        match self {
+            GameType::PotLimitOmaha4(_) => 4,
+            GameType::PotLimitOmaha5(_) => 5,
+            _ => 2,
+        }
+    }
+
+    /// Whether bets are capped at the size of the pot.
+    pub fn is_pot_limit(&self) -> bool {
+        matches!(
+            self,
+            GameType::PotLimit(_) | GameType::PotLimitOmaha4(_) | GameType::PotLimitOmaha5(_)
+        )
+    }
+
+    /// Whether bets come in fixed or spread limit sizes.
+    pub fn is_limit(&self) -> bool {
+        matches!(self, GameType::FixedLimit(..) | GameType::SpreadLimit(..))
+    }
+
+    /// Whether hands must be made with exactly two hole cards
+    /// and three community cards.
+    pub fn is_omaha(&self) -> bool {
+        matches!(
+            self,
+            GameType::PotLimitOmaha4(_) | GameType::PotLimitOmaha5(_)
+        )
+    }
 }
 
 /// A struct that holds a user's hand and rank,
@@ -105,6 +157,12 @@ pub struct PublicTable {
     pub last_timer_started_timestamp: u64,
     pub users: Users,
     pub queue: Vec<QueueItem>,
+    pub deck_commitment: Option<String>,
+    pub extra_boards: Vec<Vec<Card>>,
+    pub board_runs_offer: Option<BoardRunsOffer>,
+    pub time_bank_user_principal: Option<Principal>,
+    pub bots: HashMap<Principal, BotPersonality>,
+    pub all_in_equities: Option<Vec<(Principal, u16)>>,
 }
 
 impl PublicTable {
@@ -191,12 +249,19 @@ impl Default for PublicTable {
             last_timer_started_timestamp: 0,
             users: Users::new(),
             queue: Vec::new(),
+            deck_commitment: None,
+            extra_boards: Vec::new(),
+            board_runs_offer: None,
+            time_bank_user_principal: None,
+            bots: HashMap::new(),
+            all_in_equities: None,
         }
     }
 }
 
 impl From<Table> for PublicTable {
     fn from(table: Table) -> PublicTable {
+        let user_table_data = table.public_user_table_data();
         PublicTable {
             id: table.id,
             config: table.config,
@@ -213,13 +278,19 @@ impl From<Table> for PublicTable {
             winners: table.winners.clone(),
             sorted_users: table.sorted_users.clone(),
             action_logs: table.action_logs.clone(),
-            user_table_data: table.user_table_data.clone(),
+            user_table_data,
             highest_bet: table.highest_bet,
             last_raise: table.last_raise,
             round_ticker: table.round_ticker,
             last_timer_started_timestamp: table.last_timer_started_timestamp,
             users: table.users,
             queue: table.queue,
+            deck_commitment: table.deck_commitment.clone(),
+            extra_boards: table.extra_boards.clone(),
+            board_runs_offer: table.board_runs_offer.clone(),
+            time_bank_user_principal: table.time_bank_user_principal,
+            bots: table.bots.clone(),
+            all_in_equities: table.all_in_equities.clone(),
         }
     }
 }
@@ -242,13 +313,19 @@ impl From<&Table> for PublicTable {
             winners: table.winners.clone(),
             sorted_users: table.sorted_users.clone(),
             action_logs: table.action_logs.clone(),
-            user_table_data: table.user_table_data.clone(),
+            user_table_data: table.public_user_table_data(),
             highest_bet: table.highest_bet,
             last_raise: table.last_raise,
             round_ticker: table.round_ticker,
             last_timer_started_timestamp: table.last_timer_started_timestamp,
             users: table.users.clone(),
             queue: table.queue.clone(),
+            deck_commitment: table.deck_commitment.clone(),
+            extra_boards: table.extra_boards.clone(),
+            board_runs_offer: table.board_runs_offer.clone(),
+            time_bank_user_principal: table.time_bank_user_principal,
+            bots: table.bots.clone(),
+            all_in_equities: table.all_in_equities.clone(),
         }
     }
 }
@@ -271,13 +348,19 @@ impl From<&mut Table> for PublicTable {
             winners: table.winners.clone(),
             sorted_users: table.sorted_users.clone(),
             action_logs: table.action_logs.clone(),
-            user_table_data: table.user_table_data.clone(),
+            user_table_data: table.public_user_table_data(),
             highest_bet: table.highest_bet,
             last_raise: table.last_raise,
             round_ticker: table.round_ticker,
             last_timer_started_timestamp: table.last_timer_started_timestamp,
             users: table.users.clone(),
             queue: table.queue.clone(),
+            deck_commitment: table.deck_commitment.clone(),
+            extra_boards: table.extra_boards.clone(),
+            board_runs_offer: table.board_runs_offer.clone(),
+            time_bank_user_principal: table.time_bank_user_principal,
+            bots: table.bots.clone(),
+            all_in_equities: table.all_in_equities.clone(),
         }
     }
 }
@@ -316,6 +399,21 @@ pub struct StorableTable {
     pub last_timer_started_timestamp: u64,
     pub users: Users,
     pub queue: Vec<QueueItem>,
+    pub deck_commitment: Option<String>,
+    pub deck_seed: Option<Vec<u8>>,
+    pub deck_hand_id: Option<u64>,
+    pub revealed_hands: Option<Vec<HandVerification>>,
+    pub card_master_key: Option<Vec<u8>>,
+    pub straddle_user_principal: Option<Principal>,
+    pub extra_boards: Option<Vec<Vec<Card>>>,
+    pub board_runs_offer: Option<BoardRunsOffer>,
+    pub time_bank_user_principal: Option<Principal>,
+    pub hand_start_chips: Option<u64>,
+    pub blind_seats: Option<(usize, usize)>,
+    pub recent_exits: Option<HashMap<Principal, TableExit>>,
+    pub bots: Option<HashMap<Principal, BotPersonality>>,
+    pub all_in_equities: Option<Vec<(Principal, u16)>>,
+    pub eliminations: Option<HashMap<Principal, Vec<(Principal, u64)>>>,
 }
 
 impl Default for StorableTable {
@@ -349,6 +447,21 @@ impl Default for StorableTable {
             last_timer_started_timestamp: 0,
             users: Users::new(),
             queue: Vec::new(),
+            deck_commitment: None,
+            deck_seed: None,
+            deck_hand_id: None,
+            revealed_hands: None,
+            card_master_key: None,
+            straddle_user_principal: None,
+            extra_boards: None,
+            board_runs_offer: None,
+            time_bank_user_principal: None,
+            hand_start_chips: None,
+            blind_seats: None,
+            recent_exits: None,
+            bots: None,
+            all_in_equities: None,
+            eliminations: None,
         }
     }
 }
@@ -388,6 +501,21 @@ impl From<StorableTable> for Table {
             queue: storable_table.queue,
             rake_config: None,
             rake_total: None,
+            deck_commitment: storable_table.deck_commitment,
+            deck_seed: storable_table.deck_seed,
+            deck_hand_id: storable_table.deck_hand_id,
+            revealed_hands: storable_table.revealed_hands.unwrap_or_default(),
+            card_master_key: storable_table.card_master_key,
+            straddle_user_principal: storable_table.straddle_user_principal,
+            extra_boards: storable_table.extra_boards.unwrap_or_default(),
+            board_runs_offer: storable_table.board_runs_offer,
+            time_bank_user_principal: storable_table.time_bank_user_principal,
+            hand_start_chips: storable_table.hand_start_chips,
+            blind_seats: storable_table.blind_seats,
+            recent_exits: storable_table.recent_exits.unwrap_or_default(),
+            bots: storable_table.bots.unwrap_or_default(),
+            all_in_equities: storable_table.all_in_equities,
+            eliminations: storable_table.eliminations.unwrap_or_default(),
         }
     }
 }
@@ -423,6 +551,21 @@ impl From<Table> for StorableTable {
             last_timer_started_timestamp: table.last_timer_started_timestamp,
             users: table.users,
             queue: table.queue,
+            deck_commitment: table.deck_commitment,
+            deck_seed: table.deck_seed,
+            deck_hand_id: table.deck_hand_id,
+            revealed_hands: Some(table.revealed_hands),
+            card_master_key: table.card_master_key,
+            straddle_user_principal: table.straddle_user_principal,
+            extra_boards: Some(table.extra_boards),
+            board_runs_offer: table.board_runs_offer,
+            time_bank_user_principal: table.time_bank_user_principal,
+            hand_start_chips: table.hand_start_chips,
+            blind_seats: table.blind_seats,
+            recent_exits: Some(table.recent_exits),
+            bots: Some(table.bots),
+            all_in_equities: table.all_in_equities,
+            eliminations: Some(table.eliminations),
         }
     }
 }
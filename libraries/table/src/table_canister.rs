@@ -1,5 +1,8 @@
 use crate::{
-    poker::game::{table_functions::table::TableConfig, types::PublicTable},
+    poker::game::{
+        table_functions::{table::TableConfig, types::BotPersonality},
+        types::PublicTable,
+    },
     types::ReturnResult,
 };
 use candid::Principal;
@@ -527,13 +530,84 @@ pub async fn handle_timer_expiration_wrapper(
     }
 }
 
+pub async fn resolve_board_runs_wrapper(table_principal: Principal) -> Result<(), TableError> {
+    let call_result =
+        ic_cdk::call::Call::unbounded_wait(table_principal, "resolve_board_runs").await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error resolving board runs: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode resolve_board_runs response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in resolve_board_runs call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn reserve_seat_wrapper(
+    table_id: Principal,
+    user_id: Principal,
+) -> Result<u8, TableError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "reserve_seat")
+        .with_arg(user_id)
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error reserving seat: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode reserve_seat response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in reserve_seat call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn release_expired_seats_wrapper(table_principal: Principal) -> Result<(), TableError> {
+    let call_result =
+        ic_cdk::call::Call::unbounded_wait(table_principal, "release_expired_seats").await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error releasing expired seats: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode release_expired_seats response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in release_expired_seats call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
 pub async fn handle_user_losing_wrapper(
     tournament_id: Principal,
     user_principal: Principal,
     id: Principal,
+    eliminators: Vec<(Principal, u64)>,
 ) -> Result<(), TournamentError> {
     let call_result = ic_cdk::call::Call::unbounded_wait(tournament_id, "handle_user_losing")
-        .with_args(&(user_principal, id))
+        .with_args(&(user_principal, id, eliminators))
         .await;
 
     match call_result {
@@ -576,3 +650,81 @@ pub async fn withdraw_rake_wrapper(table_id: Principal, rake_total: u64) -> Resu
         }
     }
 }
+
+pub async fn play_bot_turn_wrapper(
+    table_id: Principal,
+    user_id: Principal,
+) -> Result<(), TableError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "play_bot_turn")
+        .with_arg(user_id)
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error playing bot turn: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode play_bot_turn response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in play_bot_turn call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn add_bot_wrapper(
+    table_id: Principal,
+    personality: BotPersonality,
+) -> Result<Principal, TableError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "add_bot")
+        .with_arg(personality)
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error adding bot: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode add_bot response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in add_bot call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
+
+pub async fn remove_bot_wrapper(
+    table_id: Principal,
+    user_id: Principal,
+) -> Result<PublicTable, TableError> {
+    let call_result = ic_cdk::call::Call::unbounded_wait(table_id, "remove_bot")
+        .with_arg(user_id)
+        .await;
+
+    match call_result {
+        Ok(res) => match res.candid() {
+            Ok(res) => res,
+            Err(err) => {
+                ic_cdk::println!("Error removing bot: {:?}", err);
+                Err(TableError::CanisterCallError(format!(
+                    "Failed to decode remove_bot response: {:?}",
+                    err
+                )))
+            }
+        },
+        Err(err) => {
+            ic_cdk::println!("Error in remove_bot call: {:?}", err);
+            Err(TableError::CanisterCallError(format!("{:?}", err)))
+        }
+    }
+}
@@ -39,6 +39,8 @@ pub struct BlindLevel {
     pub big_blind: u64,
     pub ante_type: AnteType,
     pub duration_ns: u64,
+    /// Seconds added to every player's time bank when the level starts.
+    pub time_bank_seconds: Option<u16>,
 }
 
 fn generate_blind_levels(
@@ -70,6 +72,7 @@ fn generate_blind_levels(
             big_blind,
             ante_type,
             duration_ns: level_duration_ns,
+            time_bank_seconds: None,
         });
 
         current_small_blind = (current_small_blind as f64 * blind_multiplier).round() as u64;
@@ -0,0 +1,161 @@
+use candid::{CandidType, Principal};
+use errors::tournament_error::TournamentError;
+use serde::{Deserialize, Serialize};
+
+use super::types::TournamentData;
+
+/// How the bounty of a knocked out player is paid out.
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BountyType {
+    /// The eliminator is paid the whole bounty.
+    Fixed,
+    /// Progressive knockout. The eliminator is paid half of the bounty
+    /// and the other half is added to the bounty on their own head.
+    Progressive,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BountyOptions {
+    pub bounty_type: BountyType,
+    /// The percentage of every buy-in that goes on the player's head
+    /// instead of into the prize pool.
+    pub bounty_percentage: u8,
+}
+
+impl BountyOptions {
+    /// Splits a buy-in into the part for the prize pool and the bounty.
+    pub fn split_buy_in(&self, amount: u64) -> (u64, u64) {
+        let bounty = (amount as u128 * self.bounty_percentage.min(100) as u128 / 100) as u64;
+        (amount - bounty, bounty)
+    }
+}
+
+/// What was paid out for a knocked out player.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BountyCollection {
+    /// The amount paid to each eliminator.
+    pub payouts: Vec<(Principal, u64)>,
+    /// The bounty nobody won, when no one at the table knocked the
+    /// player out.
+    pub unclaimed: u64,
+}
+
+/// Splits a bounty between the eliminators by the chips each of them
+/// won from the knocked out player. Chips that don't split evenly go
+/// one at a time to the first eliminators.
+pub fn split_bounty(bounty: u64, eliminators: &[(Principal, u64)]) -> Vec<(Principal, u64)> {
+    let total: u128 = eliminators.iter().map(|(_, chips)| *chips as u128).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut shares: Vec<(Principal, u64)> = eliminators
+        .iter()
+        .map(|(eliminator, chips)| {
+            (
+                *eliminator,
+                (bounty as u128 * *chips as u128 / total) as u64,
+            )
+        })
+        .collect();
+    let mut odd_chips = bounty - shares.iter().map(|(_, share)| share).sum::<u64>();
+    for (_, share) in shares.iter_mut() {
+        if odd_chips == 0 {
+            break;
+        }
+        *share += 1;
+        odd_chips -= 1;
+    }
+    shares
+}
+
+impl TournamentData {
+    /// The bounty options if this is a bounty tournament.
+    pub fn bounty_options(&self) -> Option<&BountyOptions> {
+        self.tournament_type.buy_in_options().bounty.as_ref()
+    }
+
+    /// Adds a buy-in, re-entry or rebuy of the user. In bounty tournaments
+    /// part of it goes on the user's head.
+    ///
+    /// # Returns
+    ///
+    /// The part of the buy-in that goes into the prize pool.
+    ///
+    /// # Errors
+    ///
+    /// - [`TournamentError::Other`] if the user isn't in the tournament
+    pub fn add_buy_in(
+        &mut self,
+        user_principal: &Principal,
+        amount: u64,
+    ) -> Result<u64, TournamentError> {
+        let Some(options) = self.bounty_options().cloned() else {
+            return Ok(amount);
+        };
+        let (prize_pool, bounty) = options.split_buy_in(amount);
+        let user_data = self.get_user_tournament_data_mut(user_principal)?;
+        user_data.bounty = Some(user_data.bounty.unwrap_or(0) + bounty);
+        Ok(prize_pool)
+    }
+
+    /// Takes the bounty of a knocked out player and hands it to the
+    /// players who knocked them out, split by the chips each won from
+    /// them. Fixed bounties are paid out in full, progressive ones half
+    /// paid out and half added to the eliminator's own bounty.
+    ///
+    /// # Parameters
+    ///
+    /// - `user_principal` - The knocked out player.
+    /// - `eliminators` - The players who won chips from the knocked out
+    ///   player in their last hand and how many.
+    ///
+    /// # Errors
+    ///
+    /// - [`TournamentError::Other`] if the player isn't in the tournament
+    pub fn collect_bounty(
+        &mut self,
+        user_principal: &Principal,
+        eliminators: &[(Principal, u64)],
+    ) -> Result<BountyCollection, TournamentError> {
+        let Some(options) = self.bounty_options().cloned() else {
+            return Ok(BountyCollection::default());
+        };
+        let bounty = self
+            .get_user_tournament_data_mut(user_principal)?
+            .bounty
+            .replace(0)
+            .unwrap_or(0);
+
+        let eliminators: Vec<(Principal, u64)> = eliminators
+            .iter()
+            .filter(|(eliminator, _)| {
+                eliminator != user_principal && self.current_players.contains_key(eliminator)
+            })
+            .copied()
+            .collect();
+        let shares = split_bounty(bounty, &eliminators);
+        if shares.is_empty() {
+            return Ok(BountyCollection {
+                payouts: Vec::new(),
+                unclaimed: bounty,
+            });
+        }
+
+        let mut payouts = Vec::new();
+        for (eliminator, share) in shares {
+            let paid = match options.bounty_type {
+                BountyType::Fixed => share,
+                BountyType::Progressive => share / 2,
+            };
+            let user_data = self.get_user_tournament_data_mut(&eliminator)?;
+            user_data.bounty = Some(user_data.bounty.unwrap_or(0) + share - paid);
+            user_data.bounties_won = Some(user_data.bounties_won.unwrap_or(0) + paid);
+            payouts.push((eliminator, paid));
+        }
+        Ok(BountyCollection {
+            payouts,
+            unclaimed: 0,
+        })
+    }
+}
@@ -0,0 +1,241 @@
+use candid::{CandidType, Principal};
+use errors::tournament_error::TournamentError;
+use serde::{Deserialize, Serialize};
+
+use super::types::PayoutPercentage;
+
+/// How long the players have to accept a deal once it's proposed.
+pub const DEAL_TIMEOUT_NS: u64 = 120_000_000_000; // 2 minutes
+
+/// ICM has to go through every finishing order, which grows too fast
+/// beyond a full final table.
+pub const MAX_DEAL_PLAYERS: usize = 10;
+
+/// How the remaining prize money is split in a deal.
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DealType {
+    /// Every player gets their expected winnings under the
+    /// Malmuth-Harville independent chip model.
+    Icm,
+    /// Every player gets the prize for the last remaining place and the
+    /// rest is split by chip count.
+    ChipChop,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DealProposal {
+    pub proposer: Principal,
+    pub deal_type: DealType,
+    /// The amount kept out of the split for the winner. The tournament
+    /// ends with the deal, so the chip leader is paid it.
+    pub left_for_winner: u64,
+    /// The stacks of the players the deal was worked out from, biggest
+    /// stack first.
+    pub stacks: Vec<(Principal, u64)>,
+    /// What each player is paid, in the same order as the stacks.
+    pub payouts: Vec<(Principal, u64)>,
+    pub accepted: Vec<Principal>,
+    pub expires_at: u64,
+}
+
+impl DealProposal {
+    /// Works out a deal between the remaining players. The proposer
+    /// accepts it by proposing it.
+    ///
+    /// # Parameters
+    ///
+    /// - `stacks` - The chips of every remaining player.
+    /// - `prizes` - The prizes still to be paid, first place first.
+    /// - `left_for_winner` - The part of the first prize that isn't split.
+    /// - `now` - The current time in nanoseconds.
+    ///
+    /// # Errors
+    ///
+    /// - [`TournamentError::InvalidState`] with fewer than two or more
+    ///   than [`MAX_DEAL_PLAYERS`] players
+    /// - [`TournamentError::NotRegistered`] if the proposer isn't one of
+    ///   the players
+    /// - [`TournamentError::InvalidConfiguration`] if more is left for
+    ///   the winner than first place pays over second
+    pub fn new(
+        proposer: Principal,
+        deal_type: DealType,
+        mut stacks: Vec<(Principal, u64)>,
+        prizes: &[u64],
+        left_for_winner: u64,
+        now: u64,
+    ) -> Result<Self, TournamentError> {
+        if stacks.len() < 2 || stacks.len() > MAX_DEAL_PLAYERS {
+            return Err(TournamentError::InvalidState(format!(
+                "A deal needs between 2 and {} players",
+                MAX_DEAL_PLAYERS
+            )));
+        }
+        if !stacks.iter().any(|(user, _)| *user == proposer) {
+            return Err(TournamentError::NotRegistered);
+        }
+        stacks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut prizes: Vec<u64> = (0..stacks.len())
+            .map(|place| prizes.get(place).copied().unwrap_or(0))
+            .collect();
+        if left_for_winner > prizes[0].saturating_sub(prizes[1]) {
+            return Err(TournamentError::InvalidConfiguration(
+                "Can't leave more for the winner than first place pays over second".to_string(),
+            ));
+        }
+        prizes[0] -= left_for_winner;
+
+        let chips: Vec<u64> = stacks.iter().map(|(_, chips)| *chips).collect();
+        let mut amounts = match deal_type {
+            DealType::Icm => icm_split(&chips, &prizes),
+            DealType::ChipChop => chip_chop_split(&chips, &prizes),
+        };
+        amounts[0] += left_for_winner;
+
+        Ok(Self {
+            proposer,
+            deal_type,
+            left_for_winner,
+            payouts: stacks
+                .iter()
+                .zip(amounts)
+                .map(|((user, _), amount)| (*user, amount))
+                .collect(),
+            stacks,
+            accepted: vec![proposer],
+            expires_at: now.saturating_add(DEAL_TIMEOUT_NS),
+        })
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether every player in the deal accepted it.
+    pub fn is_unanimous(&self) -> bool {
+        self.stacks
+            .iter()
+            .all(|(user, _)| self.accepted.contains(user))
+    }
+
+    /// Accepts the deal for the user.
+    ///
+    /// # Returns
+    ///
+    /// Whether every player has now accepted the deal.
+    ///
+    /// # Errors
+    ///
+    /// - [`TournamentError::InvalidState`] if the deal has expired
+    /// - [`TournamentError::NotRegistered`] if the user isn't in the deal
+    pub fn accept(&mut self, user_principal: Principal, now: u64) -> Result<bool, TournamentError> {
+        if self.is_expired(now) {
+            return Err(TournamentError::InvalidState(
+                "The deal has expired".to_string(),
+            ));
+        }
+        if !self.stacks.iter().any(|(user, _)| *user == user_principal) {
+            return Err(TournamentError::NotRegistered);
+        }
+        if !self.accepted.contains(&user_principal) {
+            self.accepted.push(user_principal);
+        }
+        Ok(self.is_unanimous())
+    }
+}
+
+/// The prizes for the places the remaining players can still finish in,
+/// first place first.
+pub fn remaining_prizes(
+    total_prize: u64,
+    payout_structure: &[PayoutPercentage],
+    remaining_players: usize,
+) -> Vec<u64> {
+    (0..remaining_players)
+        .map(|place| {
+            payout_structure.get(place).map_or(0, |payout| {
+                (total_prize as u128 * payout.percentage as u128 / 100) as u64
+            })
+        })
+        .collect()
+}
+
+/// The expected winnings of every stack under the Malmuth-Harville model,
+/// where the chance to finish in the next place is the share of the
+/// chips still in play.
+pub fn icm_equities(stacks: &[u64], prizes: &[u64]) -> Vec<f64> {
+    let players = stacks.len();
+    let mut equities = vec![0.0; players];
+    let total: u64 = stacks.iter().sum();
+    if players == 0 || total == 0 {
+        return equities;
+    }
+
+    // The chance that exactly the players in the mask took the first places
+    let mut finished = vec![0.0; 1 << players];
+    finished[0] = 1.0;
+    for mask in 0..(1usize << players) {
+        let chance = finished[mask];
+        let place = mask.count_ones() as usize;
+        if chance == 0.0 || place == players {
+            continue;
+        }
+        let finished_chips: u64 = (0..players)
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| stacks[i])
+            .sum();
+        let remaining_chips = (total - finished_chips) as f64;
+        if remaining_chips == 0.0 {
+            continue;
+        }
+
+        let prize = prizes.get(place).copied().unwrap_or(0) as f64;
+        for i in (0..players).filter(|i| mask & (1 << i) == 0) {
+            let chance_next = chance * stacks[i] as f64 / remaining_chips;
+            equities[i] += chance_next * prize;
+            finished[mask | (1 << i)] += chance_next;
+        }
+    }
+    equities
+}
+
+/// Rounds the amounts down and hands the odd chips one at a time to the
+/// first players, so the split adds up to exactly the total.
+fn round_split(amounts: &[f64], total: u64) -> Vec<u64> {
+    let mut split: Vec<u64> = amounts.iter().map(|amount| *amount as u64).collect();
+    let mut odd_chips = total.saturating_sub(split.iter().sum());
+    let players = split.len();
+    let mut i = 0;
+    while odd_chips > 0 && players > 0 {
+        split[i % players] += 1;
+        odd_chips -= 1;
+        i += 1;
+    }
+    split
+}
+
+/// Splits the prizes by ICM.
+pub fn icm_split(stacks: &[u64], prizes: &[u64]) -> Vec<u64> {
+    let total: u64 = prizes.iter().take(stacks.len()).sum();
+    round_split(&icm_equities(stacks, prizes), total)
+}
+
+/// Splits the prizes by chip count after every player is paid the prize
+/// for the last remaining place.
+pub fn chip_chop_split(stacks: &[u64], prizes: &[u64]) -> Vec<u64> {
+    let players = stacks.len();
+    let total: u64 = prizes.iter().take(players).sum();
+    let chips: u64 = stacks.iter().sum();
+    if players == 0 || chips == 0 {
+        return vec![0; players];
+    }
+
+    let guaranteed = prizes.get(players - 1).copied().unwrap_or(0);
+    let rest = total.saturating_sub(guaranteed * players as u64);
+    let amounts: Vec<f64> = stacks
+        .iter()
+        .map(|stack| guaranteed as f64 + (rest as u128 * *stack as u128 / chips as u128) as f64)
+        .collect();
+    round_split(&amounts, total)
+}
@@ -2,6 +2,10 @@
 pub mod tests;
 
 pub mod blind_level;
+pub mod bounty;
+pub mod deal;
+pub mod satellite;
+pub mod schedule;
 pub mod spin_and_go;
 pub mod storable;
 pub mod table_balancing;
@@ -0,0 +1,81 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use super::types::{TournamentData, TournamentState};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SatelliteOptions {
+    /// The tournament the tickets are for.
+    pub target_tournament: Principal,
+    /// What a ticket is worth, the buy-in of the target tournament.
+    pub ticket_value: u64,
+}
+
+/// What a satellite pays out when it ends.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SatellitePayouts {
+    /// The players who won a ticket to the target tournament.
+    pub tickets: Vec<Principal>,
+    /// The part of the prize pool that doesn't buy a whole ticket, paid
+    /// in cash to the first player who missed out on one.
+    pub cash: Option<(Principal, u64)>,
+}
+
+impl SatelliteOptions {
+    /// The number of tickets the prize pool pays for and the cash left over.
+    pub fn seats(&self, prize_pool: u64) -> (usize, u64) {
+        if self.ticket_value == 0 {
+            return (0, prize_pool);
+        }
+        (
+            (prize_pool / self.ticket_value) as usize,
+            prize_pool % self.ticket_value,
+        )
+    }
+
+    /// Works out who wins a ticket and who is paid the leftover cash.
+    ///
+    /// # Parameters
+    ///
+    /// - `positions` - The players in the order they finished, winner first.
+    /// - `prize_pool` - The prize pool of the satellite.
+    pub fn payouts(&self, positions: &[Principal], prize_pool: u64) -> SatellitePayouts {
+        let (seats, _) = self.seats(prize_pool);
+        let seats = seats.min(positions.len());
+        let leftover = prize_pool - seats as u64 * self.ticket_value;
+
+        // With more tickets than players the winner takes what's left
+        let cash = positions
+            .get(seats)
+            .or(positions.first())
+            .filter(|_| leftover > 0)
+            .map(|user| (*user, leftover));
+        SatellitePayouts {
+            tickets: positions[..seats].to_vec(),
+            cash,
+        }
+    }
+}
+
+impl TournamentData {
+    /// The satellite options if this is a satellite.
+    pub fn satellite_options(&self) -> Option<&SatelliteOptions> {
+        self.tournament_type.buy_in_options().satellite.as_ref()
+    }
+
+    /// Whether so many players are out of a satellite that every player
+    /// left wins a ticket, so there's nothing left to play for.
+    pub fn is_seat_bubble_burst(&self, prize_pool: u64) -> bool {
+        let Some(options) = self.satellite_options() else {
+            return false;
+        };
+        if matches!(
+            self.state,
+            TournamentState::Registration | TournamentState::LateRegistration
+        ) {
+            return false;
+        }
+        let (seats, _) = options.seats(prize_pool);
+        seats > 0 && self.current_players.len() <= seats
+    }
+}
@@ -0,0 +1,131 @@
+use candid::{CandidType, Principal};
+use errors::tournament_error::TournamentError;
+use serde::{Deserialize, Serialize};
+use table::poker::game::table_functions::table::TableConfig;
+
+use super::types::NewTournament;
+
+const NS_PER_SECOND: u64 = 1_000_000_000;
+const NS_PER_HOUR: u64 = 60 * 60 * NS_PER_SECOND;
+const NS_PER_DAY: u64 = 24 * NS_PER_HOUR;
+
+/// How often a scheduled tournament is run. Times are in UTC.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    /// Every day at the given time.
+    Daily { time_of_day_secs: u32 },
+    /// Every week on the given day, 0 being Sunday, at the given time.
+    Weekly {
+        day_of_week: u8,
+        time_of_day_secs: u32,
+    },
+    /// Every few hours, counting from the first start.
+    EveryHours { hours: u32, first_start: u64 },
+}
+
+impl Recurrence {
+    pub fn validate(&self) -> Result<(), TournamentError> {
+        let time_of_day_secs = match self {
+            Recurrence::Daily { time_of_day_secs } => *time_of_day_secs,
+            Recurrence::Weekly {
+                day_of_week,
+                time_of_day_secs,
+            } => {
+                if *day_of_week > 6 {
+                    return Err(TournamentError::InvalidConfiguration(
+                        "Day of week must be between 0 and 6".to_string(),
+                    ));
+                }
+                *time_of_day_secs
+            }
+            Recurrence::EveryHours { hours, .. } => {
+                if *hours == 0 {
+                    return Err(TournamentError::InvalidConfiguration(
+                        "Tournaments must be at least an hour apart".to_string(),
+                    ));
+                }
+                return Ok(());
+            }
+        };
+        if time_of_day_secs as u64 * NS_PER_SECOND >= NS_PER_DAY {
+            return Err(TournamentError::InvalidConfiguration(
+                "Time of day must be less than 24 hours".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The first start time strictly after `after`, in nanoseconds.
+    pub fn next_after(&self, after: u64) -> u64 {
+        let day_start = after - after % NS_PER_DAY;
+        match *self {
+            Recurrence::Daily { time_of_day_secs } => {
+                let start = day_start + time_of_day_secs as u64 * NS_PER_SECOND;
+                if start > after {
+                    start
+                } else {
+                    start + NS_PER_DAY
+                }
+            }
+            Recurrence::Weekly {
+                day_of_week,
+                time_of_day_secs,
+            } => {
+                // The epoch was on a Thursday
+                let today = (after / NS_PER_DAY + 4) % 7;
+                let days_ahead = (day_of_week as u64 + 7 - today) % 7;
+                let start =
+                    day_start + days_ahead * NS_PER_DAY + time_of_day_secs as u64 * NS_PER_SECOND;
+                if start > after {
+                    start
+                } else {
+                    start + 7 * NS_PER_DAY
+                }
+            }
+            Recurrence::EveryHours { hours, first_start } => {
+                if after < first_start {
+                    return first_start;
+                }
+                let period = hours.max(1) as u64 * NS_PER_HOUR;
+                first_start + ((after - first_start) / period + 1) * period
+            }
+        }
+    }
+}
+
+/// A template the tournament index creates tournaments from on a schedule.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TournamentSchedule {
+    pub id: u64,
+    pub template: NewTournament,
+    pub table_config: TableConfig,
+    pub recurrence: Recurrence,
+    /// How long before the start a tournament is created, so players can
+    /// register for it.
+    pub lead_time_ns: u64,
+    pub paused: bool,
+    /// The start time of the last tournament created from the schedule.
+    pub last_start_time: Option<u64>,
+    pub last_tournament: Option<Principal>,
+}
+
+impl TournamentSchedule {
+    /// The start time of the next tournament that hasn't been created yet.
+    pub fn next_start_time(&self, now: u64) -> u64 {
+        let after = self.last_start_time.map_or(now, |last| last.max(now));
+        self.recurrence.next_after(after)
+    }
+
+    /// Whether the next tournament should be created now.
+    ///
+    /// # Returns
+    ///
+    /// The start time of the tournament to create.
+    pub fn due(&self, now: u64) -> Option<u64> {
+        if self.paused {
+            return None;
+        }
+        let start_time = self.next_start_time(now);
+        (start_time.saturating_sub(self.lead_time_ns) <= now).then_some(start_time)
+    }
+}
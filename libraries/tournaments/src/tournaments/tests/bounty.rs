@@ -0,0 +1,185 @@
+use candid::Principal;
+
+use crate::tournaments::{
+    blind_level::SpeedType,
+    bounty::{split_bounty, BountyOptions, BountyType},
+    tournament_type::{BuyInOptions, TournamentSizeType, TournamentType},
+    types::{PayoutPercentage, TournamentData, UserTournamentData},
+};
+
+fn create_test_principal(id: &str) -> Principal {
+    Principal::self_authenticating(id)
+}
+
+fn create_bounty_tournament(bounty_type: BountyType, players: usize) -> TournamentData {
+    let mut tournament = TournamentData {
+        buy_in: 1_000,
+        tournament_type: TournamentType::BuyIn(TournamentSizeType::SingleTable(
+            BuyInOptions::new_freezout().with_bounty(BountyOptions {
+                bounty_type,
+                bounty_percentage: 50,
+            }),
+        )),
+        ..Default::default()
+    };
+    for i in 0..players {
+        let user_principal = create_test_principal(&format!("user{}", i));
+        tournament
+            .current_players
+            .insert(user_principal, UserTournamentData::default());
+        assert_eq!(tournament.add_buy_in(&user_principal, 1_000), Ok(500));
+    }
+    tournament
+}
+
+fn bounty_of(tournament: &TournamentData, id: &str) -> Option<u64> {
+    tournament
+        .get_user_tournament_data(&create_test_principal(id))
+        .unwrap()
+        .bounty
+}
+
+#[test]
+fn test_split_buy_in() {
+    let options = BountyOptions {
+        bounty_type: BountyType::Fixed,
+        bounty_percentage: 25,
+    };
+    assert_eq!(options.split_buy_in(1_000), (750, 250));
+    assert_eq!(options.split_buy_in(3), (3, 0));
+}
+
+#[test]
+fn test_split_bounty_odd_chips() {
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+    let user3 = create_test_principal("user3");
+
+    let shares = split_bounty(100, &[(user1, 10), (user2, 10), (user3, 10)]);
+    assert_eq!(shares, vec![(user1, 34), (user2, 33), (user3, 33)]);
+
+    let shares = split_bounty(100, &[(user1, 300), (user2, 100)]);
+    assert_eq!(shares, vec![(user1, 75), (user2, 25)]);
+
+    assert!(split_bounty(100, &[]).is_empty());
+}
+
+#[test]
+fn test_no_bounty_without_options() {
+    let mut tournament = TournamentData::default();
+    let user_principal = create_test_principal("user0");
+    tournament
+        .current_players
+        .insert(user_principal, UserTournamentData::default());
+
+    assert_eq!(tournament.add_buy_in(&user_principal, 1_000), Ok(1_000));
+    let collection = tournament
+        .collect_bounty(&user_principal, &[(create_test_principal("user1"), 10)])
+        .unwrap();
+    assert!(collection.payouts.is_empty());
+    assert_eq!(collection.unclaimed, 0);
+}
+
+#[test]
+fn test_collect_fixed_bounty() {
+    let mut tournament = create_bounty_tournament(BountyType::Fixed, 2);
+    let loser = create_test_principal("user0");
+    let winner = create_test_principal("user1");
+
+    let collection = tournament
+        .collect_bounty(&loser, &[(winner, 1_000)])
+        .unwrap();
+    assert_eq!(collection.payouts, vec![(winner, 500)]);
+    assert_eq!(collection.unclaimed, 0);
+    assert_eq!(bounty_of(&tournament, "user0"), Some(0));
+    assert_eq!(bounty_of(&tournament, "user1"), Some(500));
+    assert_eq!(
+        tournament
+            .get_user_tournament_data(&winner)
+            .unwrap()
+            .bounties_won,
+        Some(500)
+    );
+}
+
+#[test]
+fn test_collect_progressive_bounty() {
+    let mut tournament = create_bounty_tournament(BountyType::Progressive, 2);
+    let loser = create_test_principal("user0");
+    let winner = create_test_principal("user1");
+
+    let collection = tournament
+        .collect_bounty(&loser, &[(winner, 1_000)])
+        .unwrap();
+    assert_eq!(collection.payouts, vec![(winner, 250)]);
+    assert_eq!(bounty_of(&tournament, "user0"), Some(0));
+    assert_eq!(bounty_of(&tournament, "user1"), Some(750));
+
+    // Collecting twice pays nothing more
+    let collection = tournament
+        .collect_bounty(&loser, &[(winner, 1_000)])
+        .unwrap();
+    assert_eq!(collection.payouts, vec![(winner, 0)]);
+    assert_eq!(bounty_of(&tournament, "user1"), Some(750));
+}
+
+#[test]
+fn test_collect_split_bounty() {
+    let mut tournament = create_bounty_tournament(BountyType::Fixed, 3);
+    let loser = create_test_principal("user0");
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+
+    // Both won part of the pots the loser was in
+    let collection = tournament
+        .collect_bounty(&loser, &[(user1, 600), (user2, 400)])
+        .unwrap();
+    assert_eq!(collection.payouts, vec![(user1, 300), (user2, 200)]);
+}
+
+#[test]
+fn test_unclaimed_bounty() {
+    let mut tournament = create_bounty_tournament(BountyType::Fixed, 2);
+    let loser = create_test_principal("user0");
+
+    // Eliminators no longer in the tournament don't count
+    let collection = tournament
+        .collect_bounty(&loser, &[(create_test_principal("gone"), 1_000)])
+        .unwrap();
+    assert!(collection.payouts.is_empty());
+    assert_eq!(collection.unclaimed, 500);
+    assert_eq!(bounty_of(&tournament, "user0"), Some(0));
+}
+
+#[test]
+fn test_validate_bounty_options() {
+    let mut tournament = create_bounty_tournament(BountyType::Fixed, 0);
+    tournament.speed_type = SpeedType::new_default(1_000, 10);
+    tournament.max_players = 6;
+    tournament.payout_structure = vec![PayoutPercentage {
+        position: 1,
+        percentage: 100,
+    }];
+    assert!(tournament.validate().is_ok());
+
+    let invalid_bounty = |bounty_percentage| {
+        TournamentType::BuyIn(TournamentSizeType::SingleTable(
+            BuyInOptions::new_freezout().with_bounty(BountyOptions {
+                bounty_type: BountyType::Progressive,
+                bounty_percentage,
+            }),
+        ))
+    };
+    tournament.tournament_type = invalid_bounty(0);
+    assert!(tournament.validate().is_err());
+    tournament.tournament_type = invalid_bounty(100);
+    assert!(tournament.validate().is_err());
+
+    tournament.tournament_type = TournamentType::Freeroll(TournamentSizeType::SingleTable(
+        BuyInOptions::new_freezout().with_bounty(BountyOptions {
+            bounty_type: BountyType::Fixed,
+            bounty_percentage: 50,
+        }),
+    ));
+    assert!(tournament.validate().is_err());
+}
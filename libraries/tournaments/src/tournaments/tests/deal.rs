@@ -0,0 +1,157 @@
+use candid::Principal;
+use errors::tournament_error::TournamentError;
+
+use crate::tournaments::{
+    deal::{
+        chip_chop_split, icm_equities, icm_split, remaining_prizes, DealProposal, DealType,
+        DEAL_TIMEOUT_NS,
+    },
+    types::PayoutPercentage,
+};
+
+fn create_test_principal(id: &str) -> Principal {
+    Principal::self_authenticating(id)
+}
+
+#[test]
+fn test_remaining_prizes() {
+    let payout_structure = vec![
+        PayoutPercentage {
+            position: 1,
+            percentage: 50,
+        },
+        PayoutPercentage {
+            position: 2,
+            percentage: 30,
+        },
+        PayoutPercentage {
+            position: 3,
+            percentage: 20,
+        },
+    ];
+    assert_eq!(
+        remaining_prizes(1_000, &payout_structure, 2),
+        vec![500, 300]
+    );
+    assert_eq!(
+        remaining_prizes(1_000, &payout_structure, 4),
+        vec![500, 300, 200, 0]
+    );
+}
+
+#[test]
+fn test_icm_equities() {
+    let equities = icm_equities(&[50, 30, 20], &[50, 30, 20]);
+    // First place 0.5, second 0.3 * 5/7 + 0.2 * 5/8, third whatever is left
+    assert!((equities[0] - 38.392857).abs() < 1e-4);
+    assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    // The short stack gets more than its share of chips
+    assert!(equities[2] > 20.0);
+
+    let equities = icm_equities(&[100, 100, 100], &[60, 30, 0]);
+    for equity in equities {
+        assert!((equity - 30.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_icm_split_adds_up() {
+    let split = icm_split(&[5_000, 3_000, 2_000], &[5_003, 3_001, 2_000]);
+    assert_eq!(split.iter().sum::<u64>(), 10_004);
+    assert!(split[0] > split[1] && split[1] > split[2]);
+}
+
+#[test]
+fn test_chip_chop_split() {
+    // Both get the second prize, the other 40 is split 60/40
+    assert_eq!(chip_chop_split(&[60, 40], &[70, 30]), vec![54, 46]);
+    assert_eq!(chip_chop_split(&[1, 1, 1], &[50, 30, 20]), vec![34, 33, 33]);
+}
+
+#[test]
+fn test_propose_deal() {
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+
+    let deal = DealProposal::new(
+        user2,
+        DealType::ChipChop,
+        vec![(user2, 40), (user1, 60)],
+        &[70, 30, 10],
+        0,
+        1_000,
+    )
+    .unwrap();
+    assert_eq!(deal.stacks, vec![(user1, 60), (user2, 40)]);
+    assert_eq!(deal.payouts, vec![(user1, 54), (user2, 46)]);
+    assert_eq!(deal.accepted, vec![user2]);
+    assert_eq!(deal.expires_at, 1_000 + DEAL_TIMEOUT_NS);
+    assert!(!deal.is_unanimous());
+}
+
+#[test]
+fn test_leave_for_winner() {
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+    let stacks = vec![(user1, 60), (user2, 40)];
+
+    // 60 is chopped, the chip leader is paid the 10 on top
+    let deal =
+        DealProposal::new(user1, DealType::ChipChop, stacks.clone(), &[70, 30], 10, 0).unwrap();
+    assert_eq!(deal.payouts, vec![(user1, 58), (user2, 42)]);
+
+    assert!(matches!(
+        DealProposal::new(user1, DealType::Icm, stacks, &[70, 30], 41, 0),
+        Err(TournamentError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_invalid_deals() {
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+
+    assert!(matches!(
+        DealProposal::new(user1, DealType::Icm, vec![(user1, 100)], &[100], 0, 0),
+        Err(TournamentError::InvalidState(_))
+    ));
+    assert_eq!(
+        DealProposal::new(
+            create_test_principal("user3"),
+            DealType::Icm,
+            vec![(user1, 60), (user2, 40)],
+            &[70, 30],
+            0,
+            0,
+        ),
+        Err(TournamentError::NotRegistered)
+    );
+}
+
+#[test]
+fn test_accept_deal() {
+    let user1 = create_test_principal("user1");
+    let user2 = create_test_principal("user2");
+    let user3 = create_test_principal("user3");
+    let mut deal = DealProposal::new(
+        user1,
+        DealType::Icm,
+        vec![(user1, 50), (user2, 30), (user3, 20)],
+        &[50, 30, 20],
+        0,
+        0,
+    )
+    .unwrap();
+
+    assert_eq!(
+        deal.accept(create_test_principal("user4"), 0),
+        Err(TournamentError::NotRegistered)
+    );
+    assert_eq!(deal.accept(user2, 0), Ok(false));
+    assert_eq!(deal.accept(user2, 0), Ok(false));
+    assert!(matches!(
+        deal.accept(user3, DEAL_TIMEOUT_NS),
+        Err(TournamentError::InvalidState(_))
+    ));
+    assert_eq!(deal.accept(user3, DEAL_TIMEOUT_NS - 1), Ok(true));
+}
@@ -0,0 +1,47 @@
+use candid::Principal;
+
+use crate::tournaments::types::{TournamentData, UserTournamentData};
+
+fn create_test_principal(id: &str) -> Principal {
+    Principal::self_authenticating(id)
+}
+
+fn create_tournament(players: &[Principal]) -> TournamentData {
+    let mut tournament = TournamentData::default();
+    for player in players {
+        tournament
+            .current_players
+            .insert(*player, UserTournamentData::default());
+    }
+    tournament
+}
+
+#[test]
+fn test_two_players_remaining_finish_by_stack() {
+    let big_stack = create_test_principal("big_stack");
+    let short_stack = create_test_principal("short_stack");
+    let eliminated = create_test_principal("eliminated");
+    let tournament = create_tournament(&[big_stack, short_stack]);
+    let mut leaderboard = vec![eliminated];
+
+    let positions = tournament.finishing_positions(
+        &mut leaderboard,
+        vec![(short_stack, 500), (big_stack, 2_500)],
+    );
+
+    assert_eq!(positions, vec![big_stack, short_stack, eliminated]);
+    assert_eq!(leaderboard, vec![eliminated, short_stack, big_stack]);
+}
+
+#[test]
+fn test_players_at_other_tables_finish_behind_final_table() {
+    let winner = create_test_principal("winner");
+    let other_table = create_test_principal("other_table");
+    let eliminated = create_test_principal("eliminated");
+    let tournament = create_tournament(&[winner, other_table]);
+    let mut leaderboard = vec![eliminated];
+
+    let positions = tournament.finishing_positions(&mut leaderboard, vec![(winner, 3_000)]);
+
+    assert_eq!(positions, vec![winner, other_table, eliminated]);
+}
@@ -1,2 +1,7 @@
 pub mod balance_moves;
+pub mod bounty;
 pub mod calculate_players_per_table;
+pub mod deal;
+pub mod finishing_positions;
+pub mod satellite;
+pub mod schedule;
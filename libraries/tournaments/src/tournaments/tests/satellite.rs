@@ -0,0 +1,118 @@
+use candid::Principal;
+
+use crate::tournaments::{
+    blind_level::SpeedType,
+    satellite::{SatelliteOptions, SatellitePayouts},
+    tournament_type::{BuyInOptions, TournamentSizeType, TournamentType},
+    types::{PayoutPercentage, TournamentData, TournamentState, UserTournamentData},
+};
+
+fn create_test_principal(id: &str) -> Principal {
+    Principal::self_authenticating(id)
+}
+
+fn satellite_options() -> SatelliteOptions {
+    SatelliteOptions {
+        target_tournament: create_test_principal("target"),
+        ticket_value: 1_000,
+    }
+}
+
+fn create_satellite(players: usize) -> TournamentData {
+    let mut tournament = TournamentData {
+        tournament_type: TournamentType::BuyIn(TournamentSizeType::SingleTable(
+            BuyInOptions::new_freezout().with_satellite(satellite_options()),
+        )),
+        state: TournamentState::Running,
+        ..Default::default()
+    };
+    for i in 0..players {
+        tournament.current_players.insert(
+            create_test_principal(&format!("user{}", i)),
+            UserTournamentData::default(),
+        );
+    }
+    tournament
+}
+
+#[test]
+fn test_seats() {
+    let options = satellite_options();
+    assert_eq!(options.seats(4_500), (4, 500));
+    assert_eq!(options.seats(999), (0, 999));
+    assert_eq!(options.seats(3_000), (3, 0));
+}
+
+#[test]
+fn test_payouts() {
+    let options = satellite_options();
+    let positions: Vec<Principal> = (0..10)
+        .map(|i| create_test_principal(&format!("user{}", i)))
+        .collect();
+
+    assert_eq!(
+        options.payouts(&positions, 3_250),
+        SatellitePayouts {
+            tickets: positions[..3].to_vec(),
+            cash: Some((positions[3], 250)),
+        }
+    );
+    assert_eq!(
+        options.payouts(&positions, 2_000),
+        SatellitePayouts {
+            tickets: positions[..2].to_vec(),
+            cash: None,
+        }
+    );
+
+    // More tickets than players, the winner is paid what's left
+    assert_eq!(
+        options.payouts(&positions[..2], 3_250),
+        SatellitePayouts {
+            tickets: positions[..2].to_vec(),
+            cash: Some((positions[0], 1_250)),
+        }
+    );
+}
+
+#[test]
+fn test_seat_bubble() {
+    let mut tournament = create_satellite(4);
+    assert!(!tournament.is_seat_bubble_burst(3_500));
+    assert!(tournament.is_seat_bubble_burst(4_000));
+
+    // Late registration can still add players
+    tournament.state = TournamentState::LateRegistration;
+    assert!(!tournament.is_seat_bubble_burst(4_000));
+
+    // Not a satellite
+    let tournament = TournamentData::default();
+    assert!(!tournament.is_seat_bubble_burst(4_000));
+}
+
+#[test]
+fn test_validate_satellite() {
+    let mut tournament = create_satellite(0);
+    tournament.speed_type = SpeedType::new_default(1_000, 10);
+    tournament.max_players = 6;
+    tournament.payout_structure = vec![PayoutPercentage {
+        position: 1,
+        percentage: 100,
+    }];
+    assert!(tournament.validate().is_ok());
+
+    tournament.tournament_type = TournamentType::BuyIn(TournamentSizeType::SingleTable(
+        BuyInOptions::new_freezout().with_satellite(SatelliteOptions {
+            target_tournament: create_test_principal("target"),
+            ticket_value: 0,
+        }),
+    ));
+    assert!(tournament.validate().is_err());
+
+    // A satellite for itself
+    tournament.id = create_test_principal("target");
+    tournament.tournament_type = TournamentType::BuyIn(TournamentSizeType::SingleTable(
+        BuyInOptions::new_freezout().with_satellite(satellite_options()),
+    ));
+    assert!(tournament.validate().is_err());
+}
@@ -0,0 +1,140 @@
+use table::poker::game::table_functions::{table::TableConfig, types::CurrencyType};
+
+use crate::tournaments::{
+    schedule::{Recurrence, TournamentSchedule},
+    tournament_type::{BuyInOptions, TournamentSizeType, TournamentType},
+    types::{NewTournament, NewTournamentSpeedType, PayoutPercentage},
+};
+
+const SECOND: u64 = 1_000_000_000;
+const HOUR: u64 = 60 * 60 * SECOND;
+const DAY: u64 = 24 * HOUR;
+/// Monday 1 January 2024, 00:00 UTC.
+const MONDAY: u64 = 1_704_067_200 * SECOND;
+
+fn create_schedule(recurrence: Recurrence, lead_time_ns: u64) -> TournamentSchedule {
+    TournamentSchedule {
+        id: 0,
+        template: NewTournament {
+            name: "Daily".to_string(),
+            description: String::new(),
+            hero_picture: String::new(),
+            currency: CurrencyType::Fake,
+            buy_in: 1_000,
+            starting_chips: 10_000,
+            speed_type: NewTournamentSpeedType::Regular(10),
+            min_players: 2,
+            max_players: 9,
+            late_registration_duration_ns: 0,
+            payout_structure: vec![PayoutPercentage {
+                position: 1,
+                percentage: 100,
+            }],
+            tournament_type: TournamentType::BuyIn(TournamentSizeType::SingleTable(
+                BuyInOptions::new_freezout(),
+            )),
+            start_time: 0,
+            require_proof_of_humanity: false,
+        },
+        table_config: TableConfig::default(),
+        recurrence,
+        lead_time_ns,
+        paused: false,
+        last_start_time: None,
+        last_tournament: None,
+    }
+}
+
+#[test]
+fn test_daily() {
+    let daily = Recurrence::Daily {
+        time_of_day_secs: 20 * 60 * 60,
+    };
+    assert_eq!(daily.next_after(MONDAY + 10 * HOUR), MONDAY + 20 * HOUR);
+    assert_eq!(
+        daily.next_after(MONDAY + 20 * HOUR),
+        MONDAY + DAY + 20 * HOUR
+    );
+}
+
+#[test]
+fn test_weekly() {
+    let sunday = Recurrence::Weekly {
+        day_of_week: 0,
+        time_of_day_secs: 20 * 60 * 60,
+    };
+    assert_eq!(sunday.next_after(MONDAY), MONDAY + 6 * DAY + 20 * HOUR);
+
+    let monday = Recurrence::Weekly {
+        day_of_week: 1,
+        time_of_day_secs: 20 * 60 * 60,
+    };
+    assert_eq!(monday.next_after(MONDAY), MONDAY + 20 * HOUR);
+    assert_eq!(
+        monday.next_after(MONDAY + 21 * HOUR),
+        MONDAY + 7 * DAY + 20 * HOUR
+    );
+}
+
+#[test]
+fn test_every_hours() {
+    let every_six_hours = Recurrence::EveryHours {
+        hours: 6,
+        first_start: MONDAY,
+    };
+    assert_eq!(every_six_hours.next_after(MONDAY - 1), MONDAY);
+    assert_eq!(every_six_hours.next_after(MONDAY), MONDAY + 6 * HOUR);
+    assert_eq!(
+        every_six_hours.next_after(MONDAY + 7 * HOUR),
+        MONDAY + 12 * HOUR
+    );
+}
+
+#[test]
+fn test_invalid_recurrence() {
+    assert!(Recurrence::Daily {
+        time_of_day_secs: 24 * 60 * 60
+    }
+    .validate()
+    .is_err());
+    assert!(Recurrence::Weekly {
+        day_of_week: 7,
+        time_of_day_secs: 0
+    }
+    .validate()
+    .is_err());
+    assert!(Recurrence::EveryHours {
+        hours: 0,
+        first_start: 0
+    }
+    .validate()
+    .is_err());
+    assert!(Recurrence::Daily {
+        time_of_day_secs: 0
+    }
+    .validate()
+    .is_ok());
+}
+
+#[test]
+fn test_schedule_due() {
+    let mut schedule = create_schedule(
+        Recurrence::Daily {
+            time_of_day_secs: 20 * 60 * 60,
+        },
+        2 * HOUR,
+    );
+    assert_eq!(schedule.due(MONDAY + 17 * HOUR), None);
+    assert_eq!(schedule.due(MONDAY + 18 * HOUR), Some(MONDAY + 20 * HOUR));
+
+    // Once created, the next one is due the day after
+    schedule.last_start_time = Some(MONDAY + 20 * HOUR);
+    assert_eq!(schedule.due(MONDAY + 19 * HOUR), None);
+    assert_eq!(
+        schedule.due(MONDAY + DAY + 18 * HOUR),
+        Some(MONDAY + DAY + 20 * HOUR)
+    );
+
+    schedule.paused = true;
+    assert_eq!(schedule.due(MONDAY + DAY + 18 * HOUR), None);
+}
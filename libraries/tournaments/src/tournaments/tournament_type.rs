@@ -2,6 +2,8 @@ use candid::CandidType;
 use errors::tournament_error::TournamentError;
 use serde::{Deserialize, Serialize};
 
+use super::bounty::BountyOptions;
+use super::satellite::SatelliteOptions;
 use super::spin_and_go::SpinGoMultiplier;
 
 use super::table_balancing::TableBalancer;
@@ -23,6 +25,19 @@ impl TournamentType {
             TournamentType::SpinAndGo(_, _) => 3,
         }
     }
+
+    pub fn buy_in_options(&self) -> &BuyInOptions {
+        let size_type = match self {
+            TournamentType::BuyIn(size_type)
+            | TournamentType::SitAndGo(size_type)
+            | TournamentType::Freeroll(size_type)
+            | TournamentType::SpinAndGo(size_type, _) => size_type,
+        };
+        match size_type {
+            TournamentSizeType::SingleTable(options)
+            | TournamentSizeType::MultiTable(options, _) => options,
+        }
+    }
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -37,6 +52,10 @@ pub struct BuyInOptions {
     pub reentry: ReentryOptions,
     pub rebuy: RebuyOptions,
     pub addon: AddonOptions,
+    /// Knockout tournaments put part of every buy-in on the player's head.
+    pub bounty: Option<BountyOptions>,
+    /// Satellites pay out entry tickets to another tournament.
+    pub satellite: Option<SatelliteOptions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, CandidType)]
@@ -123,9 +142,23 @@ impl BuyInOptions {
                 rebuy_chips: 0,
                 min_chips_for_rebuy: 0,
             },
+            bounty: None,
+            satellite: None,
         }
     }
 
+    /// Turns the tournament into a knockout tournament.
+    pub fn with_bounty(mut self, bounty: BountyOptions) -> Self {
+        self.bounty = Some(bounty);
+        self
+    }
+
+    /// Turns the tournament into a satellite for another tournament.
+    pub fn with_satellite(mut self, satellite: SatelliteOptions) -> Self {
+        self.satellite = Some(satellite);
+        self
+    }
+
     pub fn new_reentry(
         new_tournament_options: NewTournamentOptions,
     ) -> Result<Self, TournamentError> {
@@ -214,6 +247,8 @@ impl BuyInOptions {
             reentry,
             addon,
             rebuy,
+            bounty: None,
+            satellite: None,
         })
     }
 
@@ -47,6 +47,9 @@ pub struct TournamentData {
     pub sorted_users: Option<Vec<Principal>>,
 
     pub require_proof_of_humanity: bool,
+
+    /// The bounties that failed to be paid out, retried until they are.
+    pub unpaid_bounties: Option<Vec<(Principal, u64)>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -96,6 +99,7 @@ impl Default for TournamentData {
             tables: HashMap::new(),
             sorted_users: None,
             require_proof_of_humanity: false,
+            unpaid_bounties: None,
         }
     }
 }
@@ -108,6 +112,13 @@ pub struct UserTournamentData {
     pub reentries: u32,
     pub addons: u32,
     pub rebuys: u32,
+    /// The bounty on the player's head in knockout tournaments.
+    pub bounty: Option<u64>,
+    /// The bounties the player was paid for knocking out other players.
+    pub bounties_won: Option<u64>,
+    /// The ticket from the player's inventory in the users canister that
+    /// paid the buy-in.
+    pub ticket_id: Option<u64>,
 }
 
 impl UserTournamentData {
@@ -119,6 +130,9 @@ impl UserTournamentData {
             reentries: 0,
             addons: 0,
             rebuys: 0,
+            bounty: None,
+            bounties_won: None,
+            ticket_id: None,
         }
     }
 }
@@ -132,6 +146,9 @@ impl Default for UserTournamentData {
             reentries: 0,
             addons: 0,
             rebuys: 0,
+            bounty: None,
+            bounties_won: None,
+            ticket_id: None,
         }
     }
 }
@@ -314,6 +331,7 @@ impl TournamentData {
             tables: HashMap::new(),
             sorted_users: None,
             require_proof_of_humanity: new_tournament_data.require_proof_of_humanity,
+            unpaid_bounties: None,
         };
 
         Ok(tournament)
@@ -444,6 +462,32 @@ impl TournamentData {
             }
             _ => {}
         }
+
+        if let Some(bounty) = self.bounty_options() {
+            if matches!(self.tournament_type, TournamentType::Freeroll(_)) {
+                return Err(TournamentError::InvalidConfiguration(
+                    "Freerolls can't have bounties".to_string(),
+                ));
+            }
+            if bounty.bounty_percentage == 0 || bounty.bounty_percentage >= 100 {
+                return Err(TournamentError::InvalidConfiguration(
+                    "Bounty percentage must be between 1 and 99".to_string(),
+                ));
+            }
+        }
+
+        if let Some(satellite) = self.satellite_options() {
+            if satellite.ticket_value == 0 {
+                return Err(TournamentError::InvalidConfiguration(
+                    "Satellite tickets must have a value".to_string(),
+                ));
+            }
+            if satellite.target_tournament == self.id {
+                return Err(TournamentError::InvalidConfiguration(
+                    "A satellite can't award tickets to itself".to_string(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -486,6 +530,32 @@ impl TournamentData {
                 )),
         }
     }
+
+    /// Adds the players still in the tournament to the leaderboard, which
+    /// lists players in the order they were knocked out, and returns the
+    /// finishing positions from first to last.
+    ///
+    /// The players at the final table finish by their stacks. A satellite can
+    /// stop with players left at other tables, they finish behind the final table.
+    pub fn finishing_positions(
+        &self,
+        leaderboard: &mut Vec<Principal>,
+        mut final_table: Vec<(Principal, u64)>,
+    ) -> Vec<Principal> {
+        // Shortest stack first, as it would have been knocked out first
+        final_table.sort_by_key(|(_, balance)| *balance);
+
+        for principal in self.current_players.keys() {
+            if !final_table.iter().any(|(user, _)| user == principal)
+                && !leaderboard.contains(principal)
+            {
+                leaderboard.push(*principal);
+            }
+        }
+        leaderboard.extend(final_table.into_iter().map(|(principal, _)| principal));
+
+        leaderboard.iter().rev().copied().collect()
+    }
 }
 
 /// Calculates the current blind level at a specific timestamp
@@ -0,0 +1,134 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::user::User;
+
+/// What a ticket can be used to enter.
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub enum TicketScope {
+    /// A single tournament.
+    Tournament(Principal),
+    /// Any tournament with this buy-in.
+    BuyInTier(u64),
+}
+
+/// A ticket that pays the buy-in of a tournament instead of currency.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize, CandidType, PartialEq, Eq)]
+pub struct Ticket {
+    pub id: u64,
+    /// Who gave out the ticket.
+    pub issuer: Principal,
+    pub scope: TicketScope,
+    /// The amount of currency the ticket is worth.
+    pub value: u64,
+    /// When the ticket can no longer be used, in nanoseconds.
+    pub expires_at: Option<u64>,
+    /// The tournament the ticket was used to enter.
+    pub redeemed_in: Option<Principal>,
+}
+
+impl Ticket {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Whether the ticket can be used to enter a tournament.
+    ///
+    /// # Parameters
+    ///
+    /// - `tournament_id` - The principal of the tournament.
+    /// - `buy_in` - The buy-in of the tournament.
+    /// - `now` - The current time in nanoseconds.
+    pub fn is_valid_for(&self, tournament_id: Principal, buy_in: u64, now: u64) -> bool {
+        let in_scope = match self.scope {
+            TicketScope::Tournament(id) => id == tournament_id,
+            TicketScope::BuyInTier(tier) => tier == buy_in,
+        };
+        in_scope && self.redeemed_in.is_none() && !self.is_expired(now) && self.value >= buy_in
+    }
+}
+
+impl User {
+    /// Gives the user a new ticket and drops the ones that expired unused.
+    ///
+    /// # Returns
+    ///
+    /// The ticket that was issued.
+    pub fn issue_ticket(
+        &mut self,
+        issuer: Principal,
+        scope: TicketScope,
+        value: u64,
+        expires_at: Option<u64>,
+        now: u64,
+    ) -> Ticket {
+        let tickets = self.tickets.get_or_insert_with(Vec::new);
+        tickets.retain(|ticket| ticket.redeemed_in.is_some() || !ticket.is_expired(now));
+        let ticket = Ticket {
+            id: tickets
+                .iter()
+                .map(|ticket| ticket.id + 1)
+                .max()
+                .unwrap_or(0),
+            issuer,
+            scope,
+            value,
+            expires_at,
+            redeemed_in: None,
+        };
+        tickets.push(ticket.clone());
+        ticket
+    }
+
+    /// The tickets the user can still use.
+    pub fn get_tickets(&self, now: u64) -> Vec<Ticket> {
+        self.tickets
+            .iter()
+            .flatten()
+            .filter(|ticket| ticket.redeemed_in.is_none() && !ticket.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Uses up a ticket to enter a tournament. Tickets for the tournament
+    /// itself are used before buy-in tier tickets, and the ones closest to
+    /// expiring first.
+    ///
+    /// # Returns
+    ///
+    /// The ticket that was used, if the user had a valid one.
+    pub fn redeem_ticket(
+        &mut self,
+        tournament_id: Principal,
+        buy_in: u64,
+        now: u64,
+    ) -> Option<Ticket> {
+        let ticket = self
+            .tickets
+            .iter_mut()
+            .flatten()
+            .filter(|ticket| ticket.is_valid_for(tournament_id, buy_in, now))
+            .min_by_key(|ticket| {
+                (
+                    !matches!(ticket.scope, TicketScope::Tournament(_)),
+                    ticket.expires_at.unwrap_or(u64::MAX),
+                )
+            })?;
+        ticket.redeemed_in = Some(tournament_id);
+        Some(ticket.clone())
+    }
+
+    /// Hands back a ticket that was used to enter a tournament.
+    ///
+    /// # Returns
+    ///
+    /// The refunded ticket, if it was used to enter the tournament.
+    pub fn refund_ticket(&mut self, ticket_id: u64, tournament_id: Principal) -> Option<Ticket> {
+        let ticket =
+            self.tickets.iter_mut().flatten().find(|ticket| {
+                ticket.id == ticket_id && ticket.redeemed_in == Some(tournament_id)
+            })?;
+        ticket.redeemed_in = None;
+        Some(ticket.clone())
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use ic_stable_structures::{storable::Bound, Storable};
 use std::{borrow::Cow, collections::HashMap};
 
+use crate::ticket::Ticket;
+
 const MAX_VALUE_SIZE: u32 = 200_000_000;
 pub const REFERRAL_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
 
@@ -48,6 +50,12 @@ pub struct User {
     pub referrer: Option<Principal>,
     pub referred_users: Option<HashMap<Principal, u64>>,
     pub referral_start_date: Option<u64>, // Timestamp when user was referred
+
+    /// Whether the user is a bot seated by the table index.
+    pub is_bot: Option<bool>,
+
+    /// Tickets that pay the buy-in of a tournament.
+    pub tickets: Option<Vec<Ticket>>,
 }
 
 impl User {
@@ -80,6 +88,8 @@ impl User {
             referrer,
             referred_users: Some(HashMap::new()),
             referral_start_date,
+            is_bot: None,
+            tickets: Some(Vec::new()),
         }
     }
 
@@ -295,6 +305,8 @@ impl Storable for User {
                 referrer: None,
                 referred_users: Some(HashMap::new()),
                 referral_start_date: None,
+                is_bot: None,
+                tickets: Some(Vec::new()),
             }
         })
     }
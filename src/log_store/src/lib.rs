@@ -2,14 +2,17 @@ use std::cell::RefCell;
 
 use authentication::validate_caller;
 use candid::{Nat, Principal};
-use errors::log_store_error::LogStoreError;
+use errors::{game_error::GameError, log_store_error::LogStoreError};
 use ic_cdk::management_canister::{canister_status, CanisterStatusArgs};
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
     DefaultMemoryImpl, StableBTreeMap,
 };
 use serde_cbor::{from_slice, to_vec};
-use table::poker::game::table_functions::action_log::ActionLog;
+use table::poker::game::{
+    hand_history::{export_hand, HandHistoryFormat},
+    table_functions::action_log::ActionLog,
+};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -187,6 +190,24 @@ fn get_action_logs(
     })
 }
 
+#[ic_cdk::query]
+fn get_hand_history(
+    table_principal: Principal,
+    hand_id: u64,
+    format: HandHistoryFormat,
+) -> Result<String, LogStoreError> {
+    MAP.with(|p| {
+        let map = p.borrow();
+        let logs = map.get(&table_principal).unwrap_or_default();
+        let logs = deserialize_from_bytes(&logs, None, None)?;
+
+        export_hand(table_principal, &logs, hand_id, format).map_err(|e| match e.into_inner() {
+            GameError::HandNotFound => LogStoreError::HandNotFound,
+            e => LogStoreError::SerializationError(e.to_string()),
+        })
+    })
+}
+
 #[ic_cdk::update]
 async fn get_canister_status_formatted() -> Result<String, LogStoreError> {
     // Validate caller is a controller
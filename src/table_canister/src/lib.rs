@@ -9,6 +9,7 @@ use errors::{chat_error::ChatError, game_error::GameError, table_error::TableErr
 use ic_cdk::management_canister::{canister_status, CanisterStatusArgs, DepositCyclesArgs};
 use intercanister_call_wrappers::{
     log_store::log_actions_wrapper,
+    table_index::return_to_fast_fold_pool_wrapper,
     users_canister::{
         add_users_active_table, get_user_wrapper, get_users_canister_principal_by_id_wrapper,
         remove_users_active_table,
@@ -17,13 +18,21 @@ use intercanister_call_wrappers::{
 use lazy_static::lazy_static;
 use table::{
     poker::{
-        core::{Card, Rank},
+        core::{Card, Equities, EquityRules, Rank},
         game::{
+            hand_history::{export_hand, HandHistoryFormat},
             table_functions::{
                 action_log::ActionType,
                 ante::AnteType,
+                card_encryption::EncryptedCardKey,
+                equity::equities_for,
+                legal_actions::LegalActions,
+                provably_fair::HandVerification,
                 table::{Table, TableConfig, TableType},
-                types::{BetType, CurrencyType, DealStage, Notification, PlayerAction, SeatStatus},
+                types::{
+                    AutoTopUp, BetType, BlindChoice, BotPersonality, CurrencyType, DealStage,
+                    Notification, PlayerAction, PreAction, SeatStatus,
+                },
             },
             types::{PublicTable, QueueItem, TableStatus},
             utils::rank_hand,
@@ -40,8 +49,6 @@ use utils::{
     handle_table_validity_check, update_player_count_tournament, update_table_player_count,
 };
 
-use crate::utils::reshuffle_bytes_hash;
-
 mod memory;
 pub mod utils;
 
@@ -74,6 +81,44 @@ fn init() {
 
 #[ic_cdk::update]
 async fn create_table(config: TableConfig, bytes: Vec<u8>) -> Result<PublicTable, TableError> {
+    // Hole cards for every seat plus five community cards and three burns
+    // have to fit in a single deck.
+    if config.seats as usize * config.game_type.hole_cards_count() + 8 > config.deck_size() {
+        return Err(TableError::InvalidRequest(
+            "Too many seats for this game type".to_string(),
+        ));
+    }
+    if config.uses_short_deck() && (config.game_type.is_omaha() || config.is_hi_lo.unwrap_or(false))
+    {
+        return Err(TableError::InvalidRequest(
+            "Short deck is only supported for Hold'em high".to_string(),
+        ));
+    }
+    if config.max_board_runs.unwrap_or(1) > 1
+        && !matches!(config.table_type, None | Some(TableType::Cash))
+    {
+        return Err(TableError::InvalidRequest(
+            "The board can only be run more than once at cash tables".to_string(),
+        ));
+    }
+    if config.straddle_type.is_some()
+        && (!matches!(config.table_type, None | Some(TableType::Cash))
+            || config.game_type.is_limit())
+    {
+        return Err(TableError::InvalidRequest(
+            "Straddles are only supported at no limit and pot limit cash tables".to_string(),
+        ));
+    }
+    if let (Some(min_buy_in), Some(max_buy_in)) =
+        (config.min_buy_in_big_blinds, config.max_buy_in_big_blinds)
+    {
+        if min_buy_in > max_buy_in {
+            return Err(TableError::InvalidRequest(
+                "The min buy in can't be more than the max buy in".to_string(),
+            ));
+        }
+    }
+
     let table = {
         let mut backend_principal = BACKEND_PRINCIPAL
             .lock()
@@ -157,14 +202,30 @@ async fn join_table(
         table.as_ref().ok_or(TableError::TableNotFound)?.clone()
     };
 
+    // Players are seated at fast-fold tables by the pool
+    if table.is_fast_fold() {
+        let backend_principal = BACKEND_PRINCIPAL
+            .lock()
+            .map_err(|_| TableError::LockError)?
+            .ok_or(TableError::CanisterCallError(
+                "Backend principal not found.".to_string(),
+            ))?;
+        validate_caller(vec![backend_principal]);
+    }
+
     if table.big_blind > deposit_amount {
         return Err(TableError::InsufficientFunds);
     }
+    table
+        .validate_buy_in(user_id, deposit_amount, ic_cdk::api::time())
+        .map_err(|e| e.into_inner())?;
 
+    // A player coming off the waitlist takes the seat held for them
     let seat_index = match seat_index {
         Some(seat_index) => seat_index as u8,
         None => table
-            .get_free_seat_index()
+            .reserved_seat(user_id)
+            .or_else(|| table.get_free_seat_index())
             .ok_or(TableError::InvalidRequest("Table is full.".to_string()))?,
     };
 
@@ -204,7 +265,9 @@ async fn join_table(
     let mut user = user?;
     user.balance = deposit_amount;
 
-    if table.users.users.contains_key(&user_id) || table.is_user_in_table(user_id) {
+    if table.users.users.contains_key(&user_id)
+        || (table.is_user_in_table(user_id) && table.reserved_seat(user_id) != Some(seat_index))
+    {
         return Err(TableError::UserAlreadyInGame);
     }
 
@@ -376,6 +439,7 @@ async fn kick_player(
     {
         let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
         let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+        table_state.record_exit(user_id, balance, ic_cdk::api::time());
         table_state
             .remove_user(user_id, ActionType::Leave)
             .map_err(|e| e.into_inner())?;
@@ -488,6 +552,7 @@ async fn leave_table(
     {
         let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
         let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+        table_state.record_exit(user_id, balance, ic_cdk::api::time());
         table_state
             .remove_user(user_id, ActionType::Leave)
             .map_err(|e| e.into_inner())?;
@@ -611,14 +676,9 @@ async fn withdraw_from_table(user_id: Principal, amount: u64) -> Result<(), Tabl
             .into());
         }
 
-        let user = table
-            .users
-            .get_mut(&user_id)
-            .ok_or(TableError::UserNotFound)?;
-        if user.balance < amount {
-            return Err(GameError::InsufficientFunds.into());
-        }
-        user.withdraw(amount);
+        table
+            .withdraw_from_stack(user_id, amount)
+            .map_err(|e| e.into_inner())?;
         table.clone()
     };
 
@@ -661,6 +721,9 @@ async fn deposit_to_table(
             let table = TABLE.lock().map_err(|_| TableError::LockError)?;
             table.as_ref().ok_or(TableError::TableNotFound)?.clone()
         };
+        table
+            .validate_top_up(user_id, amount)
+            .map_err(|e| e.into_inner())?;
         match table.config.currency_type {
             CurrencyType::Real(currency) => {
                 let currency_manager = {
@@ -676,6 +739,14 @@ async fn deposit_to_table(
                     .validate_allowance(&currency, user_id, amount)
                     .await
                 {
+                    if is_queued {
+                        let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+                        table
+                            .as_mut()
+                            .ok_or(TableError::TableNotFound)?
+                            .handle_failed_top_up(user_id)
+                            .map_err(|e| e.into_inner())?;
+                    }
                     return Err(e.into());
                 }
             }
@@ -683,7 +754,7 @@ async fn deposit_to_table(
         }
     }
 
-    let table = {
+    let (table, is_rebuy) = {
         let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
         let table = table.as_mut().ok_or(TableError::TableNotFound)?;
 
@@ -699,12 +770,11 @@ async fn deposit_to_table(
             return Ok(ReturnResult::DepositQueued);
         }
 
-        let user = table
-            .users
-            .get_mut(&user_id)
-            .ok_or(TableError::UserNotFound)?;
-        user.deposit(amount);
-        table.clone()
+        table
+            .deposit_to_stack(user_id, amount)
+            .map_err(|e| e.into_inner())?;
+        let is_rebuy = table.finish_rebuy(user_id);
+        (table.clone(), is_rebuy)
     };
 
     match table.config.currency_type {
@@ -738,17 +808,32 @@ async fn deposit_to_table(
                     ic_cdk::println!("Error depositing: {:?}", e);
                     let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
                     let table = table.as_mut().ok_or(TableError::TableNotFound)?;
-                    let user = table
-                        .users
-                        .get_mut(&user_id)
-                        .ok_or(TableError::UserNotFound)?;
-                    user.withdraw(amount);
+                    table
+                        .withdraw_from_stack(user_id, amount)
+                        .map_err(|e| e.into_inner())?;
+                    if is_queued {
+                        table
+                            .handle_failed_top_up(user_id)
+                            .map_err(|e| e.into_inner())?;
+                    }
                     return Err(e.into());
                 }
             }
         }
         CurrencyType::Fake => {}
     }
+
+    // A busted player who sat out waiting for their rebuy may have
+    // been the one the table needed to keep dealing.
+    if is_rebuy
+        && table.number_of_players() >= 2
+        && table.number_of_active_players() >= 2
+        && !table.is_game_ongoing()
+    {
+        if let Err(e) = start_new_betting_round_wrapper(ic_cdk::api::canister_self()).await {
+            ic_cdk::println!("Error starting new betting round: {:?}", e);
+        }
+    }
     Ok(ReturnResult::DepositSuccessful)
 }
 
@@ -778,6 +863,133 @@ fn set_player_action(
     Ok(())
 }
 
+#[ic_cdk::update]
+fn set_transport_public_key(
+    user_principal: Principal,
+    transport_public_key: Vec<u8>,
+) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_transport_public_key(user_principal, transport_public_key)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_straddle_next_hand(user_principal: Principal, enabled: bool) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_straddle_next_hand(user_principal, enabled)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_blind_choice(
+    user_principal: Principal,
+    blind_choice: BlindChoice,
+) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_blind_choice(user_principal, blind_choice)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_auto_top_up(
+    user_principal: Principal,
+    auto_top_up: Option<AutoTopUp>,
+) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_auto_top_up(user_principal, auto_top_up)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn set_pre_action(
+    user_principal: Principal,
+    pre_action: Option<PreAction>,
+) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_pre_action(user_principal, pre_action)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
 // #[ic_cdk::update]
 // fn set_auto_check_fold(user_principal: Principal, enabled: bool) -> Result<(), TableError> {
 //     handle_cycle_check();
@@ -914,6 +1126,9 @@ async fn player_sitting_in(
         }
         .into());
     }
+    table_state
+        .check_blind_choice(user_id)
+        .map_err(|e| e.into_inner())?;
 
     if table_state.is_game_ongoing() {
         let is_game_paused = table_state
@@ -985,31 +1200,85 @@ fn place_bet(user_principal: Principal, bet_type: BetType) -> Result<(), TableEr
 fn fold(user_principal: Principal, is_pre_fold: bool) -> Result<(), TableError> {
     handle_cycle_check();
 
-    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
-    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
-    let user = table_state
-        .users
-        .get(&user_principal)
-        .ok_or(TableError::UserNotFound)?;
+    let is_fast_fold = {
+        let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+        let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+        let user = table_state
+            .users
+            .get(&user_principal)
+            .ok_or(TableError::UserNotFound)?;
+        let backend_principal = BACKEND_PRINCIPAL
+            .lock()
+            .map_err(|_| TableError::LockError)?
+            .ok_or(TableError::CanisterCallError(
+                "Backend principal not found.".to_string(),
+            ))?;
+        validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+        if is_pre_fold {
+            table_state
+                .user_pre_fold(user_principal)
+                .map_err(|e| e.into_inner())?;
+        } else {
+            table_state
+                .user_fold(user_principal, false)
+                .map_err(|e| e.into_inner())?;
+        }
+        table_state.is_fast_fold()
+    };
+
+    if is_fast_fold {
+        ic_cdk::futures::spawn(async move {
+            if let Err(e) = move_to_fast_fold_pool(user_principal).await {
+                ic_cdk::println!("Error moving user to the fast-fold pool: {:?}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Sends a player who folded at a fast-fold table back to the pool with
+/// the rest of their stack, so the index can deal them into the next
+/// hand at another table.
+async fn move_to_fast_fold_pool(user_id: Principal) -> Result<(), TableError> {
+    let (stack, users_canister_id, currency_type) = {
+        let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+        let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+        let users_canister_id = table
+            .users
+            .get(&user_id)
+            .ok_or(TableError::UserNotFound)?
+            .users_canister_id;
+        let stack = table.fast_fold_user(user_id).map_err(|e| e.into_inner())?;
+        (stack, users_canister_id, table.config.currency_type)
+    };
+
+    if let CurrencyType::Real(currency) = currency_type {
+        if stack > 0 {
+            let currency_manager = {
+                let currency_manager =
+                    CURRENCY_MANAGER.lock().map_err(|_| TableError::LockError)?;
+                currency_manager
+                    .as_ref()
+                    .ok_or(TableError::StateNotInitialized)?
+                    .clone()
+            };
+            currency_manager.withdraw(&currency, user_id, stack).await?;
+        }
+    }
+
+    remove_users_active_table(users_canister_id, user_id, ic_cdk::api::canister_self()).await?;
+
     let backend_principal = BACKEND_PRINCIPAL
         .lock()
         .map_err(|_| TableError::LockError)?
         .ok_or(TableError::CanisterCallError(
             "Backend principal not found.".to_string(),
         ))?;
-    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
-
-    if is_pre_fold {
-        table_state
-            .user_pre_fold(user_principal)
-            .map_err(|e| e.into_inner())?;
-    } else {
-        table_state
-            .user_fold(user_principal, false)
-            .map_err(|e| e.into_inner())?;
-    }
-
-    Ok(())
+    return_to_fast_fold_pool_wrapper(backend_principal, users_canister_id, user_id, stack)
+        .await
+        .map_err(|e| TableError::CanisterCallError(format!("{:?}", e)))
 }
 
 #[ic_cdk::update]
@@ -1073,6 +1342,10 @@ fn handle_timer_expiration(user_id: Principal) -> Result<(), TableError> {
     valid_callers.push(table_state.id);
     validate_caller(valid_callers);
 
+    if table_state.start_time_bank_timer(user_id) {
+        return Ok(());
+    }
+
     let res = if table_state.is_users_current_total_bet_equal_to_highest_bet(user_id) {
         table_state
             .user_check(user_id, true)
@@ -1086,21 +1359,67 @@ fn handle_timer_expiration(user_id: Principal) -> Result<(), TableError> {
     Ok(())
 }
 
+#[ic_cdk::update]
+fn set_board_runs(user_principal: Principal, runs: u8) -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let user = table_state
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![user_principal, user.principal_id, backend_principal]);
+
+    table_state
+        .set_board_runs(user_principal, runs)
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn resolve_board_runs() -> Result<(), TableError> {
+    handle_cycle_check();
+    let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    let mut valid_callers = (*CONTROLLER_PRINCIPALS).clone();
+    valid_callers.push(backend_principal);
+    valid_callers.push(table_state.id);
+    validate_caller(valid_callers);
+
+    // Every player may have chosen before the timer ran out.
+    if table_state.board_runs_offer.is_none() {
+        return Ok(());
+    }
+
+    table_state
+        .resolve_board_runs()
+        .map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
 #[ic_cdk::update]
 async fn start_new_betting_round() -> Result<(), TableError> {
     handle_cycle_check();
 
+    // The VRF output seeds the shuffle, the table commits to the deck
+    // before dealing and reveals the seed after showdown.
     let raw_bytes = ic_cdk::management_canister::raw_rand().await;
-    let mut raw_bytes = raw_bytes.map_err(|e| {
+    let raw_bytes = raw_bytes.map_err(|e| {
         TableError::CanisterCallError(format!("Failed to generate random bytes: {:?}", e))
     })?;
 
-    // Get current time in nanoseconds as seed
-    let time_seed = ic_cdk::api::time();
-    
-    // Reshuffle the bytes using time as seed
-    reshuffle_bytes_hash(&mut raw_bytes, time_seed);
-
     let (kicked_players, action_logs, table_id, total_users, seated_out_kicked_players, users) = {
         let mut table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
         let table_state = table_state.as_mut().ok_or(TableError::TableNotFound)?;
@@ -1418,6 +1737,65 @@ fn rank_cards(hand: Vec<Card>) -> Result<Rank, TableError> {
     Ok(rank_hand(hand))
 }
 
+/// Works out the equities of hands against a partial board,
+/// for hand analysis tools.
+#[ic_cdk::query]
+fn calculate_equities(
+    hands: Vec<Vec<Card>>,
+    board: Vec<Card>,
+    rules: EquityRules,
+) -> Result<Equities, TableError> {
+    equities_for(&hands, &board, rules).map_err(|e| e.into_inner().into())
+}
+
+/// Reveals the shuffle seed of a finished hand together with the
+/// commitment that was published before its cards were dealt.
+#[ic_cdk::query]
+fn verify_hand(hand_id: u64) -> Result<HandVerification, TableError> {
+    let table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_ref().ok_or(TableError::TableNotFound)?;
+
+    table
+        .verify_hand(hand_id)
+        .map_err(|e| e.into_inner().into())
+}
+
+/// Exports the hand still in the table's logs, usually the
+/// last one played, as a hand history file.
+#[ic_cdk::query]
+fn get_hand_history(hand_id: u64, format: HandHistoryFormat) -> Result<String, TableError> {
+    let table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_ref().ok_or(TableError::TableNotFound)?;
+
+    export_hand(table.id, &table.action_logs, hand_id, format).map_err(|e| e.into_inner().into())
+}
+
+#[ic_cdk::query]
+fn get_encrypted_card_key(user_principal: Principal) -> Result<EncryptedCardKey, TableError> {
+    let table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_ref().ok_or(TableError::TableNotFound)?;
+    let user = table
+        .users
+        .get(&user_principal)
+        .ok_or(TableError::UserNotFound)?;
+    // Only the player may fetch their own card key.
+    validate_caller(vec![user_principal, user.principal_id]);
+
+    table
+        .get_encrypted_card_key(user_principal)
+        .map_err(|e| e.into_inner().into())
+}
+
+#[ic_cdk::query]
+fn get_legal_actions(user_principal: Principal) -> Result<LegalActions, TableError> {
+    let table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_ref().ok_or(TableError::TableNotFound)?;
+
+    table
+        .legal_actions(user_principal)
+        .map_err(|e| e.into_inner().into())
+}
+
 #[ic_cdk::query]
 fn get_notifications() -> Result<Vec<Notification>, TableError> {
     let table_state = TABLE.lock().map_err(|_| TableError::LockError)?;
@@ -1497,7 +1875,12 @@ async fn transfer_cycles_to_table_index(cycles_amount: u128) -> Result<(), Table
 }
 
 #[ic_cdk::update]
-async fn update_blinds(small_blind: u64, big_blind: u64, ante: AnteType) -> Result<(), TableError> {
+async fn update_blinds(
+    small_blind: u64,
+    big_blind: u64,
+    ante: AnteType,
+    time_bank_seconds: Option<u16>,
+) -> Result<(), TableError> {
     handle_cycle_check();
 
     let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
@@ -1517,6 +1900,11 @@ async fn update_blinds(small_blind: u64, big_blind: u64, ante: AnteType) -> Resu
         None
     };
 
+    // Bank time for the new level can be added straight away.
+    if let Some(time_bank_seconds) = time_bank_seconds {
+        table.add_time_bank(time_bank_seconds as u64);
+    }
+
     // Only allow blind updates between hands
     if table.is_game_ongoing() {
         table.append_to_queue(QueueItem::UpdateBlinds(small_blind, big_blind, ante));
@@ -1541,6 +1929,127 @@ async fn get_free_seat_index() -> Result<Option<u8>, TableError> {
     Ok(table.get_free_seat_index())
 }
 
+#[ic_cdk::update]
+fn reserve_seat(user_principal: Principal) -> Result<u8, TableError> {
+    handle_cycle_check();
+
+    let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+
+    validate_caller(vec![backend_principal]);
+
+    if !matches!(table.config.table_type, Some(TableType::Cash) | None) {
+        return Err(TableError::InvalidRequest(
+            "Table is not a cash table".to_string(),
+        ));
+    }
+
+    let seat_index = table
+        .reserve_seat(user_principal, ic_cdk::api::time())
+        .map_err(|e| e.into_inner())?;
+    table.start_seat_reservation_timer();
+    Ok(seat_index)
+}
+
+#[ic_cdk::update]
+fn release_expired_seats() -> Result<(), TableError> {
+    handle_cycle_check();
+
+    let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+
+    validate_caller(vec![backend_principal, table.id]);
+
+    let released = table.release_expired_reservations(ic_cdk::api::time());
+    if !released.is_empty() {
+        // Lets the table index offer the seats to the next players in line
+        update_table_player_count(table.users.len())?;
+    }
+    Ok(())
+}
+
+#[ic_cdk::update]
+async fn add_bot(personality: BotPersonality) -> Result<Principal, TableError> {
+    handle_cycle_check();
+
+    let (bot_principal, should_start) = {
+        let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+        let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+        let backend_principal = BACKEND_PRINCIPAL
+            .lock()
+            .map_err(|_| TableError::LockError)?
+            .ok_or(TableError::CanisterCallError(
+                "Backend principal not found.".to_string(),
+            ))?;
+        validate_caller(vec![backend_principal]);
+
+        // Bots sit down with a full stack of play money
+        let bot_principal = table
+            .add_bot(personality, table.big_blind * 100)
+            .map_err(|e| e.into_inner())?;
+        let should_start = table.number_of_players() >= 2
+            && !table.is_game_ongoing()
+            && !table.config.is_paused.unwrap_or(false);
+        (bot_principal, should_start)
+    };
+
+    if should_start {
+        if let Err(e) = start_new_betting_round_wrapper(ic_cdk::api::canister_self()).await {
+            ic_cdk::println!("Error starting new betting round: {:?}", e);
+        }
+    }
+    Ok(bot_principal)
+}
+
+#[ic_cdk::update]
+fn remove_bot(user_id: Principal) -> Result<PublicTable, TableError> {
+    handle_cycle_check();
+
+    let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+    let backend_principal = BACKEND_PRINCIPAL
+        .lock()
+        .map_err(|_| TableError::LockError)?
+        .ok_or(TableError::CanisterCallError(
+            "Backend principal not found.".to_string(),
+        ))?;
+    validate_caller(vec![backend_principal]);
+
+    if !table.is_bot(user_id) {
+        return Err(TableError::InvalidRequest("User is not a bot".to_string()));
+    }
+    table
+        .remove_user(user_id, ActionType::Leave)
+        .map_err(|e| e.into_inner())?;
+    Ok(table.clone().into())
+}
+
+#[ic_cdk::update]
+fn play_bot_turn(user_id: Principal) -> Result<(), TableError> {
+    handle_cycle_check();
+
+    let mut table = TABLE.lock().map_err(|_| TableError::LockError)?;
+    let table = table.as_mut().ok_or(TableError::TableNotFound)?;
+    let mut valid_callers = (*CONTROLLER_PRINCIPALS).clone();
+    valid_callers.push(table.id);
+    validate_caller(valid_callers);
+
+    table.play_bot_turn(user_id).map_err(|e| e.into_inner())?;
+    Ok(())
+}
+
 #[ic_cdk::update]
 async fn clear_table() -> Result<(), TableError> {
     handle_cycle_check();
@@ -0,0 +1,191 @@
+use std::collections::{HashMap, VecDeque};
+
+use candid::{CandidType, Principal};
+use errors::table_index_error::TableIndexError;
+use serde::Deserialize;
+use table::poker::game::table_functions::table::TableConfig;
+
+/// A player waiting in a fast-fold pool to be dealt into the next hand.
+#[derive(Debug, Clone, PartialEq, CandidType, Deserialize)]
+pub struct PooledPlayer {
+    pub user_principal: Principal,
+    pub users_canister_principal: Principal,
+    /// The stack the player brings to the next table.
+    pub stack: u64,
+}
+
+/// The table canisters playing one stake of fast-fold and the players
+/// waiting for a seat at one of them.
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct FastFoldPool {
+    pub config: TableConfig,
+    pub tables: Vec<Principal>,
+    waiting: VecDeque<PooledPlayer>,
+}
+
+/// A fast-fold pool as shown in the lobby.
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct FastFoldPoolInfo {
+    pub id: u64,
+    pub config: TableConfig,
+    pub tables: Vec<Principal>,
+    pub waiting_players: u64,
+}
+
+/// The fast-fold pools managed by the index.
+#[derive(Debug, Clone, Default, CandidType, Deserialize)]
+pub struct FastFoldPools {
+    pools: HashMap<u64, FastFoldPool>,
+    next_id: u64,
+}
+
+impl FastFoldPools {
+    /// Adds a pool of tables.
+    ///
+    /// # Returns
+    ///
+    /// The id of the pool.
+    pub fn add_pool(&mut self, config: TableConfig, tables: Vec<Principal>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pools.insert(
+            id,
+            FastFoldPool {
+                config,
+                tables,
+                waiting: VecDeque::new(),
+            },
+        );
+        id
+    }
+
+    /// Gets a pool by its id.
+    pub fn get(&self, pool_id: u64) -> Result<&FastFoldPool, TableIndexError> {
+        self.pools
+            .get(&pool_id)
+            .ok_or(TableIndexError::InvalidRequest(
+                "Fast-fold pool not found".to_string(),
+            ))
+    }
+
+    fn get_mut(&mut self, pool_id: u64) -> Result<&mut FastFoldPool, TableIndexError> {
+        self.pools
+            .get_mut(&pool_id)
+            .ok_or(TableIndexError::InvalidRequest(
+                "Fast-fold pool not found".to_string(),
+            ))
+    }
+
+    /// Gets the pool a table belongs to.
+    pub fn pool_of_table(&self, table_id: Principal) -> Option<u64> {
+        self.pools
+            .iter()
+            .find(|(_, pool)| pool.tables.contains(&table_id))
+            .map(|(id, _)| *id)
+    }
+
+    /// Lists the pools for the lobby.
+    pub fn info(&self) -> Vec<FastFoldPoolInfo> {
+        self.pools
+            .iter()
+            .map(|(id, pool)| FastFoldPoolInfo {
+                id: *id,
+                config: pool.config.clone(),
+                tables: pool.tables.clone(),
+                waiting_players: pool.waiting.len() as u64,
+            })
+            .collect()
+    }
+
+    /// Puts a player at the back of the queue of a pool.
+    ///
+    /// # Returns
+    ///
+    /// The position of the player in the queue, starting at 1.
+    pub fn enqueue(
+        &mut self,
+        pool_id: u64,
+        player: PooledPlayer,
+    ) -> Result<usize, TableIndexError> {
+        let is_waiting = self.pools.values().any(|pool| {
+            pool.waiting
+                .iter()
+                .any(|waiting| waiting.user_principal == player.user_principal)
+        });
+        if is_waiting {
+            return Err(TableIndexError::InvalidRequest(
+                "User is already waiting in a fast-fold pool".to_string(),
+            ));
+        }
+        let pool = self.get_mut(pool_id)?;
+        pool.waiting.push_back(player);
+        Ok(pool.waiting.len())
+    }
+
+    /// Puts a player back at the head of the queue when no table could
+    /// take them.
+    pub fn push_front(
+        &mut self,
+        pool_id: u64,
+        player: PooledPlayer,
+    ) -> Result<(), TableIndexError> {
+        self.get_mut(pool_id)?.waiting.push_front(player);
+        Ok(())
+    }
+
+    /// Takes a waiting player out of a pool.
+    pub fn leave(
+        &mut self,
+        pool_id: u64,
+        user_principal: Principal,
+    ) -> Result<PooledPlayer, TableIndexError> {
+        let pool = self.get_mut(pool_id)?;
+        let index = pool
+            .waiting
+            .iter()
+            .position(|player| player.user_principal == user_principal)
+            .ok_or(TableIndexError::UserNotFound)?;
+        pool.waiting
+            .remove(index)
+            .ok_or(TableIndexError::UserNotFound)
+    }
+
+    /// Takes the player who has been waiting the longest along with
+    /// the tables of the pool that have a free seat. The fullest tables
+    /// come first so new hands get going as soon as possible.
+    ///
+    /// # Parameters
+    ///
+    /// - `pool_id` - The id of the pool.
+    /// - `player_counts` - The number of players at each table.
+    pub fn next_seating(
+        &mut self,
+        pool_id: u64,
+        player_counts: &HashMap<Principal, usize>,
+    ) -> Result<Option<(PooledPlayer, Vec<Principal>)>, TableIndexError> {
+        let pool = self.get_mut(pool_id)?;
+        let seats = pool.config.seats as usize;
+        let mut tables: Vec<(Principal, usize)> = pool
+            .tables
+            .iter()
+            .map(|id| (*id, player_counts.get(id).copied().unwrap_or(0)))
+            .filter(|(_, count)| *count < seats)
+            .collect();
+        if tables.is_empty() {
+            return Ok(None);
+        }
+        tables.sort_by(|a, b| b.1.cmp(&a.1));
+
+        Ok(pool
+            .waiting
+            .pop_front()
+            .map(|player| (player, tables.into_iter().map(|(id, _)| id).collect())))
+    }
+
+    /// Drops a table that was deleted from its pool.
+    pub fn remove_table(&mut self, table_id: Principal) {
+        for pool in self.pools.values_mut() {
+            pool.tables.retain(|id| *id != table_id);
+        }
+    }
+}
@@ -9,32 +9,43 @@ use canister_functions::{
 };
 use currency::{state::TransactionState, types::currency_manager::CurrencyManager, Currency};
 use errors::{
-    canister_management_error::CanisterManagementError, table_error::TableError,
-    table_index_error::TableIndexError,
+    canister_management_error::CanisterManagementError, game_error::GameError,
+    table_error::TableError, table_index_error::TableIndexError,
 };
+use fast_fold::{FastFoldPoolInfo, FastFoldPools, PooledPlayer};
 use futures::future::join_all;
 use ic_cdk::management_canister::{canister_status, CanisterStatusArgs};
 use intercanister_call_wrappers::table_index::get_rake_stats;
 use lazy_static::lazy_static;
 use std::{cmp::Ordering, collections::HashMap, sync::Mutex};
 use table::poker::game::{
-    table_functions::{rake::Rake, table::TableConfig, types::CurrencyType},
-    types::{GameType, PublicTable},
+    table_functions::{
+        rake::Rake,
+        table::{TableConfig, TableType},
+        types::{BotPersonality, CurrencyType, SeatStatus},
+    },
+    types::{GameType, PublicTable, QueueItem},
 };
 use table::table_canister::{
-    clear_table, create_table_wrapper, get_table_wrapper, is_game_ongoing_wrapper, join_table,
-    return_all_cycles_to_index,
+    add_bot_wrapper, clear_table, create_table_wrapper, get_table_wrapper, is_game_ongoing_wrapper,
+    join_table, remove_bot_wrapper, reserve_seat_wrapper, return_all_cycles_to_index,
 };
 use table_index::{PrivateTableIndex, PublicTableIndex};
 use table_index_types::filter::FilterOptions;
 use utils::{get_canister_state, is_table_full};
+use waitlist::{WaitlistTarget, Waitlists};
 
+pub mod fast_fold;
 mod memory;
 pub mod table_index;
 pub mod utils;
+pub mod waitlist;
 
 const MINIMUM_CYCLE_THRESHOLD: u128 = 2_000_000_000_000;
 
+/// The most bots kept at a play money table waiting for humans.
+const MAX_BOTS_PER_TABLE: usize = 2;
+
 async fn handle_cycle_check() -> Result<(), TableIndexError> {
     let id = ic_cdk::api::canister_self();
     let cycle_dispenser_canister_id =
@@ -58,6 +69,8 @@ lazy_static! {
     static ref PRIVATE_TABLE_INDEX_STATE: Mutex<PrivateTableIndex> =
         Mutex::new(PrivateTableIndex::new());
     static ref TABLE_PLAYER_COUNTS: Mutex<HashMap<Principal, usize>> = Mutex::new(HashMap::new());
+    static ref WAITLISTS: Mutex<Waitlists> = Mutex::new(Waitlists::default());
+    static ref FAST_FOLD_POOLS: Mutex<FastFoldPools> = Mutex::new(FastFoldPools::default());
     static ref CYCLE_DISPENSER_CANISTER_PROD: Principal =
         Principal::from_text("zuv6g-yaaaa-aaaam-qbeza-cai").unwrap();
     static ref CYCLE_DISPENSER_CANISTER_TEST: Principal =
@@ -195,6 +208,9 @@ async fn create_table(
             .map_err(|_| TableIndexError::LockError)?
             .tables
             .insert(table.id, config);
+        if let Err(e) = manage_bots(table.id).await {
+            ic_cdk::println!("Failed to seat bots at {}: {:?}", table.id.to_text(), e);
+        }
     }
     Ok(table)
 }
@@ -213,6 +229,109 @@ async fn update_table_player_count(
 ) -> Result<(), TableIndexError> {
     handle_cycle_check().await?;
 
+    // Only a table of this index reports its own player count
+    if ic_cdk::api::msg_caller() != table_id {
+        return Err(TableIndexError::AuthorizationError);
+    }
+    let fast_fold_pool = FAST_FOLD_POOLS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .pool_of_table(table_id);
+    let is_indexed_table = PUBLIC_TABLE_INDEX_STATE
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .tables
+        .contains_key(&table_id)
+        || PRIVATE_TABLE_INDEX_STATE
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .tables
+            .contains_key(&table_id);
+    if fast_fold_pool.is_none() && !is_indexed_table {
+        return Err(TableIndexError::AuthorizationError);
+    }
+
+    TABLE_PLAYER_COUNTS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .insert(table_id, count);
+
+    match fast_fold_pool {
+        Some(pool_id) => seat_fast_fold_players(pool_id).await,
+        None => {
+            offer_free_seats(table_id, count).await?;
+            manage_bots(table_id).await
+        }
+    }
+}
+
+/// Holds the free seats of a public cash table for the players who
+/// have been waiting the longest. The table lets them know their seat
+/// is ready.
+async fn offer_free_seats(table_id: Principal, mut count: usize) -> Result<(), TableIndexError> {
+    let config = PUBLIC_TABLE_INDEX_STATE
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .tables
+        .get(&table_id)
+        .cloned();
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if !matches!(config.table_type, Some(TableType::Cash) | None)
+        || config.is_paused.unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    while count < config.seats as usize {
+        let next = WAITLISTS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .pop_next(table_id, &config);
+        let Some((user_principal, target)) = next else {
+            break;
+        };
+
+        match reserve_seat_wrapper(table_id, user_principal).await {
+            Ok(seat_index) => {
+                ic_cdk::println!(
+                    "Reserved seat {} at table {} for {}",
+                    seat_index,
+                    table_id.to_text(),
+                    user_principal.to_text()
+                );
+                WAITLISTS
+                    .lock()
+                    .map_err(|_| TableIndexError::LockError)?
+                    .remove_user(user_principal);
+                count += 1;
+            }
+            Err(TableError::Game(GameError::GameFull)) => {
+                // Someone sat down in the meantime
+                WAITLISTS
+                    .lock()
+                    .map_err(|_| TableIndexError::LockError)?
+                    .push_front(user_principal, target);
+                break;
+            }
+            Err(e) => {
+                ic_cdk::println!(
+                    "Failed to reserve a seat for {}: {:?}",
+                    user_principal.to_text(),
+                    e
+                );
+                // Keep their place and try again on the next update
+                WAITLISTS
+                    .lock()
+                    .map_err(|_| TableIndexError::LockError)?
+                    .push_front(user_principal, target);
+                break;
+            }
+        }
+    }
+
+    // Held seats count as taken so quick join doesn't send anyone else there
     TABLE_PLAYER_COUNTS
         .lock()
         .map_err(|_| TableIndexError::LockError)?
@@ -220,6 +339,355 @@ async fn update_table_player_count(
     Ok(())
 }
 
+/// Keeps bots seated at a public play money cash table so players
+/// arriving at an empty table get a game. Every human who sits down
+/// takes the place of a bot and there is always a seat left for the
+/// next human.
+async fn manage_bots(table_id: Principal) -> Result<(), TableIndexError> {
+    let config = PUBLIC_TABLE_INDEX_STATE
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .tables
+        .get(&table_id)
+        .cloned();
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if config.currency_type != CurrencyType::Fake
+        || !matches!(config.table_type, Some(TableType::Cash) | None)
+    {
+        return Ok(());
+    }
+
+    let table = get_table_wrapper(table_id).await?;
+    // Held seats count as humans, they are on their way
+    let humans = table
+        .seats
+        .iter()
+        .filter(|seat| match seat {
+            SeatStatus::Occupied(principal)
+            | SeatStatus::QueuedForNextRound(principal, _, _)
+            | SeatStatus::Reserved { principal, .. } => !table.bots.contains_key(principal),
+            SeatStatus::Empty => false,
+        })
+        .count();
+    // Bots leaving after the current hand are already gone
+    let bots: Vec<Principal> = table
+        .bots
+        .keys()
+        .copied()
+        .filter(|bot| {
+            !table
+                .queue
+                .iter()
+                .any(|item| matches!(item, QueueItem::RemoveUser(user, _) if user == bot))
+        })
+        .collect();
+    let target = MAX_BOTS_PER_TABLE
+        .saturating_sub(humans)
+        .min((config.seats as usize).saturating_sub(humans + 1));
+
+    for i in bots.len()..target {
+        let personality = if i % 2 == 0 {
+            BotPersonality::LooseAggressive
+        } else {
+            BotPersonality::TightPassive
+        };
+        if let Err(e) = add_bot_wrapper(table_id, personality).await {
+            ic_cdk::println!("Failed to add a bot to {}: {:?}", table_id.to_text(), e);
+            break;
+        }
+    }
+    for bot in bots.iter().skip(target) {
+        if let Err(e) = remove_bot_wrapper(table_id, *bot).await {
+            ic_cdk::println!("Failed to remove bot {}: {:?}", bot.to_text(), e);
+        }
+    }
+    Ok(())
+}
+
+#[ic_cdk::update]
+async fn join_waitlist(
+    user_principal: Principal,
+    target: WaitlistTarget,
+) -> Result<usize, TableIndexError> {
+    handle_cycle_check().await?;
+    validate_caller(vec![user_principal]);
+
+    if let WaitlistTarget::Table(table_id) = target {
+        let is_public_table = PUBLIC_TABLE_INDEX_STATE
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .tables
+            .contains_key(&table_id);
+        if !is_public_table {
+            return Err(TableIndexError::TableNotFound);
+        }
+    }
+
+    WAITLISTS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .join(user_principal, target)
+}
+
+#[ic_cdk::update]
+fn leave_waitlist(
+    user_principal: Principal,
+    target: WaitlistTarget,
+) -> Result<(), TableIndexError> {
+    validate_caller(vec![user_principal]);
+    WAITLISTS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .leave(user_principal, &target)
+}
+
+#[ic_cdk::query]
+fn get_waitlist_position(
+    user_principal: Principal,
+    target: WaitlistTarget,
+) -> Result<Option<usize>, TableIndexError> {
+    Ok(WAITLISTS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .position(user_principal, &target))
+}
+
+/// Sets up a fast-fold pool: a set of private cash tables playing the
+/// same stake. Players join the pool rather than a table and are moved
+/// to another table of the pool as soon as they fold.
+#[ic_cdk::update]
+async fn create_fast_fold_pool(
+    config: TableConfig,
+    table_count: u8,
+) -> Result<u64, TableIndexError> {
+    handle_cycle_check().await?;
+    let controllers = (*CONTROLLER_PRINCIPALS).clone();
+    validate_caller(controllers);
+
+    if table_count == 0 {
+        return Err(TableIndexError::InvalidRequest(
+            "A fast-fold pool needs at least one table".to_string(),
+        ));
+    }
+
+    // Pool tables are private so players only ever reach them through the pool
+    let config = TableConfig {
+        table_type: Some(TableType::Cash),
+        is_private: Some(true),
+        is_shared_rake: None,
+        is_fast_fold: Some(true),
+        ..config
+    };
+    let mut tables = Vec::new();
+    for _ in 0..table_count {
+        let table = create_table(config.clone(), None).await?;
+        tables.push(table.id);
+    }
+
+    Ok(FAST_FOLD_POOLS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .add_pool(config, tables))
+}
+
+#[ic_cdk::query]
+fn get_fast_fold_pools() -> Result<Vec<FastFoldPoolInfo>, TableIndexError> {
+    Ok(FAST_FOLD_POOLS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .info())
+}
+
+/// Puts a player in the queue of a fast-fold pool. For real currency
+/// pools the stack goes through the player's wallet between tables, so
+/// the player has to allow the pool tables to take it.
+///
+/// # Returns
+///
+/// The position of the player in the queue, starting at 1.
+#[ic_cdk::update]
+async fn join_fast_fold_pool(
+    pool_id: u64,
+    users_canister_principal: Principal,
+    user_principal: Principal,
+    buy_in: u64,
+) -> Result<usize, TableIndexError> {
+    handle_cycle_check().await?;
+    validate_caller(vec![user_principal]);
+
+    let position = {
+        let mut pools = FAST_FOLD_POOLS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?;
+        pools
+            .get(pool_id)?
+            .config
+            .validate_fast_fold_buy_in(buy_in)
+            .map_err(|e| TableError::from(e.into_inner()))?;
+        pools.enqueue(
+            pool_id,
+            PooledPlayer {
+                user_principal,
+                users_canister_principal,
+                stack: buy_in,
+            },
+        )?
+    };
+
+    seat_fast_fold_players(pool_id).await?;
+    Ok(position)
+}
+
+/// Takes a player out of the queue of a fast-fold pool. A player who is
+/// seated leaves their table as usual.
+#[ic_cdk::update]
+fn leave_fast_fold_pool(pool_id: u64, user_principal: Principal) -> Result<(), TableIndexError> {
+    validate_caller(vec![user_principal]);
+    FAST_FOLD_POOLS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?
+        .leave(pool_id, user_principal)?;
+    Ok(())
+}
+
+/// Called by a fast-fold table when a player folds, so they are dealt
+/// into the next hand at another table of the pool with their stack.
+#[ic_cdk::update]
+async fn return_to_fast_fold_pool(
+    users_canister_principal: Principal,
+    user_principal: Principal,
+    stack: u64,
+) -> Result<(), TableIndexError> {
+    handle_cycle_check().await?;
+    let table_id = ic_cdk::api::msg_caller();
+
+    let pool_id = {
+        let mut pools = FAST_FOLD_POOLS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?;
+        let pool_id = pools
+            .pool_of_table(table_id)
+            .ok_or(TableIndexError::AuthorizationError)?;
+
+        // The player's seat is freed before the table deals again
+        if let Some(count) = TABLE_PLAYER_COUNTS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .get_mut(&table_id)
+        {
+            *count = count.saturating_sub(1);
+        }
+
+        let (_, big_blind) = pools.get(pool_id)?.config.game_type.blinds();
+        if stack < big_blind {
+            ic_cdk::println!(
+                "{} busted out of fast-fold pool {}",
+                user_principal.to_text(),
+                pool_id
+            );
+            return Ok(());
+        }
+        pools.enqueue(
+            pool_id,
+            PooledPlayer {
+                user_principal,
+                users_canister_principal,
+                stack,
+            },
+        )?;
+        pool_id
+    };
+
+    seat_fast_fold_players(pool_id).await
+}
+
+/// Deals the players waiting in a fast-fold pool into the tables of the
+/// pool with a free seat, longest waiting first. A player no table could
+/// take stays at the head of the queue until a seat frees up, without
+/// holding up the players behind them when the tables turned them away
+/// for another reason.
+async fn seat_fast_fold_players(pool_id: u64) -> Result<(), TableIndexError> {
+    let mut skipped = Vec::new();
+    let result = seat_next_fast_fold_players(pool_id, &mut skipped).await;
+
+    let mut pools = FAST_FOLD_POOLS
+        .lock()
+        .map_err(|_| TableIndexError::LockError)?;
+    for player in skipped.into_iter().rev() {
+        pools.push_front(pool_id, player)?;
+    }
+    result
+}
+
+async fn seat_next_fast_fold_players(
+    pool_id: u64,
+    skipped: &mut Vec<PooledPlayer>,
+) -> Result<(), TableIndexError> {
+    loop {
+        let next = {
+            let player_counts = TABLE_PLAYER_COUNTS
+                .lock()
+                .map_err(|_| TableIndexError::LockError)?
+                .clone();
+            FAST_FOLD_POOLS
+                .lock()
+                .map_err(|_| TableIndexError::LockError)?
+                .next_seating(pool_id, &player_counts)?
+        };
+        let Some((player, tables)) = next else {
+            return Ok(());
+        };
+
+        let mut is_seated = false;
+        let mut is_pool_full = true;
+        for table_id in tables {
+            match join_table(
+                table_id,
+                player.users_canister_principal,
+                player.user_principal,
+                None,
+                player.stack,
+                false,
+            )
+            .await
+            {
+                Ok(_) => {
+                    *TABLE_PLAYER_COUNTS
+                        .lock()
+                        .map_err(|_| TableIndexError::LockError)?
+                        .entry(table_id)
+                        .or_insert(0) += 1;
+                    is_seated = true;
+                    break;
+                }
+                Err(e) => {
+                    ic_cdk::println!(
+                        "Failed to seat {} at fast-fold table {}: {:?}",
+                        player.user_principal.to_text(),
+                        table_id.to_text(),
+                        e
+                    );
+                    is_pool_full &= matches!(e, TableError::Game(GameError::GameFull));
+                }
+            }
+        }
+
+        if !is_seated {
+            if !is_pool_full {
+                skipped.push(player);
+                continue;
+            }
+            FAST_FOLD_POOLS
+                .lock()
+                .map_err(|_| TableIndexError::LockError)?
+                .push_front(pool_id, player)?;
+            return Ok(());
+        }
+    }
+}
+
 #[ic_cdk::update]
 async fn get_tables(
     filter_options: Option<FilterOptions>,
@@ -416,6 +884,14 @@ async fn monitor_and_top_up_table_canisters() -> Result<(), TableIndexError> {
 async fn delete_table(table_principal: Principal) -> Result<(), TableIndexError> {
     handle_cycle_check().await?;
     {
+        WAITLISTS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .remove_table(table_principal);
+        FAST_FOLD_POOLS
+            .lock()
+            .map_err(|_| TableIndexError::LockError)?
+            .remove_table(table_principal);
         PUBLIC_TABLE_INDEX_STATE
             .lock()
             .map_err(|_| TableIndexError::LockError)?
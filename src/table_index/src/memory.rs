@@ -6,8 +6,13 @@ use ic_stable_structures::Cell;
 use ic_stable_structures::{storable::Bound, DefaultMemoryImpl, Storable};
 use std::{borrow::Cow, cell::RefCell};
 
+use crate::fast_fold::FastFoldPools;
 use crate::table_index::{PrivateTableIndex, PublicTableIndex};
-use crate::{PRIVATE_TABLE_INDEX_STATE, PUBLIC_TABLE_INDEX_STATE, TRANSACTION_STATE};
+use crate::waitlist::Waitlists;
+use crate::{
+    FAST_FOLD_POOLS, PRIVATE_TABLE_INDEX_STATE, PUBLIC_TABLE_INDEX_STATE, TRANSACTION_STATE,
+    WAITLISTS,
+};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -65,6 +70,48 @@ impl Storable for PrivateTableIndex {
     };
 }
 
+impl Storable for Waitlists {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap_or_else(|e| {
+            ic_cdk::println!("Serialization error: {:?}", e);
+            vec![]
+        }))
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap_or_else(|e| {
+            ic_cdk::println!("Deserialization error: {:?}", e);
+            Waitlists::default()
+        })
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_VALUE_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+impl Storable for FastFoldPools {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap_or_else(|e| {
+            ic_cdk::println!("Serialization error: {:?}", e);
+            vec![]
+        }))
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap_or_else(|e| {
+            ic_cdk::println!("Deserialization error: {:?}", e);
+            FastFoldPools::default()
+        })
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: MAX_VALUE_SIZE,
+        is_fixed_size: false,
+    };
+}
+
 thread_local! {
     // The memory manager is used for simulating multiple memories. Given a `MemoryId` it can
     // return a memory that can be used by stable structures.
@@ -91,6 +138,20 @@ thread_local! {
             TransactionState::new()
         ).unwrap()
     );
+
+    static WAITLISTS_CELL: RefCell<Cell<Waitlists, Memory>> = RefCell::new(
+        Cell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(4))),
+            Waitlists::default()
+        ).unwrap()
+    );
+
+    static FAST_FOLD_POOLS_CELL: RefCell<Cell<FastFoldPools, Memory>> = RefCell::new(
+        Cell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5))),
+            FastFoldPools::default()
+        ).unwrap()
+    );
 }
 
 #[ic_cdk::pre_upgrade]
@@ -122,6 +183,24 @@ fn pre_upgrade() {
         } else {
             ic_cdk::println!("Failed to acquire TRANSACTION_STATE lock");
         }
+
+        if let Ok(waitlists) = WAITLISTS.lock() {
+            WAITLISTS_CELL.with(|p| {
+                let mut cell = p.borrow_mut();
+                let _ = cell.set(waitlists.clone());
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire WAITLISTS lock");
+        }
+
+        if let Ok(pools) = FAST_FOLD_POOLS.lock() {
+            FAST_FOLD_POOLS_CELL.with(|p| {
+                let mut cell = p.borrow_mut();
+                let _ = cell.set(pools.clone());
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire FAST_FOLD_POOLS lock");
+        }
     });
 
     if res.is_err() {
@@ -158,6 +237,24 @@ fn post_upgrade() {
         } else {
             ic_cdk::println!("Failed to acquire TRANSACTION_STATE lock");
         }
+
+        if let Ok(mut waitlists) = WAITLISTS.lock() {
+            WAITLISTS_CELL.with(|p| {
+                let cell = p.borrow();
+                waitlists.clone_from(&cell.get().clone());
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire WAITLISTS lock");
+        }
+
+        if let Ok(mut pools) = FAST_FOLD_POOLS.lock() {
+            FAST_FOLD_POOLS_CELL.with(|p| {
+                let cell = p.borrow();
+                pools.clone_from(&cell.get().clone());
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire FAST_FOLD_POOLS lock");
+        }
     });
 
     if res.is_err() {
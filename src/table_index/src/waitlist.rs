@@ -0,0 +1,117 @@
+use candid::{CandidType, Principal};
+use errors::table_index_error::TableIndexError;
+use serde::Deserialize;
+use table::poker::game::{
+    table_functions::{table::TableConfig, types::CurrencyType},
+    types::GameType,
+};
+
+/// What a player is waiting for a seat at.
+#[derive(Debug, Clone, PartialEq, CandidType, Deserialize)]
+pub enum WaitlistTarget {
+    /// A specific table.
+    Table(Principal),
+    /// Any public cash table playing the game at these stakes.
+    Stakes {
+        game_type: GameType,
+        currency_type: CurrencyType,
+    },
+}
+
+impl WaitlistTarget {
+    fn matches(&self, table_id: Principal, config: &TableConfig) -> bool {
+        match self {
+            WaitlistTarget::Table(id) => *id == table_id,
+            WaitlistTarget::Stakes {
+                game_type,
+                currency_type,
+            } => *game_type == config.game_type && *currency_type == config.currency_type,
+        }
+    }
+}
+
+/// The players waiting for a seat at full cash tables, first come
+/// first served across all tables and stakes.
+#[derive(Debug, Clone, Default, CandidType, Deserialize)]
+pub struct Waitlists {
+    entries: Vec<(Principal, WaitlistTarget)>,
+}
+
+impl Waitlists {
+    /// Puts a user at the back of a waitlist.
+    ///
+    /// # Returns
+    ///
+    /// The position of the user on the waitlist, starting at 1.
+    pub fn join(
+        &mut self,
+        user_principal: Principal,
+        target: WaitlistTarget,
+    ) -> Result<usize, TableIndexError> {
+        if self.position(user_principal, &target).is_some() {
+            return Err(TableIndexError::InvalidRequest(
+                "User is already on the waitlist".to_string(),
+            ));
+        }
+        self.entries.push((user_principal, target.clone()));
+        self.position(user_principal, &target)
+            .ok_or(TableIndexError::UserNotFound)
+    }
+
+    /// Takes a user off a waitlist.
+    pub fn leave(
+        &mut self,
+        user_principal: Principal,
+        target: &WaitlistTarget,
+    ) -> Result<(), TableIndexError> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(principal, t)| *principal == user_principal && t == target)
+            .ok_or(TableIndexError::InvalidRequest(
+                "User is not on the waitlist".to_string(),
+            ))?;
+        self.entries.remove(index);
+        Ok(())
+    }
+
+    /// Gets the position of a user on a waitlist, starting at 1.
+    pub fn position(&self, user_principal: Principal, target: &WaitlistTarget) -> Option<usize> {
+        self.entries
+            .iter()
+            .filter(|(_, t)| t == target)
+            .position(|(principal, _)| *principal == user_principal)
+            .map(|position| position + 1)
+    }
+
+    /// Takes the longest waiting player who'd sit at the table.
+    pub fn pop_next(
+        &mut self,
+        table_id: Principal,
+        config: &TableConfig,
+    ) -> Option<(Principal, WaitlistTarget)> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(_, target)| target.matches(table_id, config))?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Puts a player back at the head of the waitlist when the seat
+    /// offered to them was gone already.
+    pub fn push_front(&mut self, user_principal: Principal, target: WaitlistTarget) {
+        self.entries.insert(0, (user_principal, target));
+    }
+
+    /// Takes a user off every waitlist, once they got a seat.
+    pub fn remove_user(&mut self, user_principal: Principal) {
+        self.entries
+            .retain(|(principal, _)| *principal != user_principal);
+    }
+
+    /// Drops the waitlist of a table that was removed.
+    pub fn remove_table(&mut self, table_id: Principal) {
+        self.entries
+            .retain(|(_, target)| *target != WaitlistTarget::Table(table_id));
+    }
+}
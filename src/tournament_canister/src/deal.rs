@@ -0,0 +1,195 @@
+use std::sync::atomic::Ordering;
+
+use candid::Principal;
+use errors::tournament_error::TournamentError;
+use intercanister_call_wrappers::tournament_canister::handle_tournament_end_wrapper;
+use table::table_canister::get_table_wrapper;
+use tournaments::tournaments::{
+    deal::{remaining_prizes, DealProposal, DealType},
+    types::{TournamentData, TournamentState},
+};
+
+use crate::{
+    utils::{handle_cycle_check, is_users_canister_of},
+    DEAL, PRIZE_POOL, TOURNAMENT,
+};
+
+fn current_tournament() -> Result<TournamentData, TournamentError> {
+    let tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+    Ok(tournament
+        .as_ref()
+        .ok_or(TournamentError::TournamentNotFound)?
+        .clone())
+}
+
+fn is_deal_pending(now: u64) -> Result<bool, TournamentError> {
+    let deal = DEAL.lock().map_err(|_| TournamentError::LockError)?;
+    Ok(deal.as_ref().is_some_and(|deal| !deal.is_expired(now)))
+}
+
+/// Only the player, or the users canister the users index holds their
+/// account in, may act on a deal for them.
+async fn validate_deal_caller(
+    users_canister_id: Principal,
+    user_id: Principal,
+) -> Result<(), TournamentError> {
+    let caller = ic_cdk::api::msg_caller();
+    if caller == user_id
+        || (caller == users_canister_id && is_users_canister_of(users_canister_id, user_id).await?)
+    {
+        return Ok(());
+    }
+    Err(TournamentError::NotAuthorized)
+}
+
+/// Gets the stacks of the players left at the final table.
+async fn final_table_stacks(
+    tournament: &TournamentData,
+) -> Result<Vec<(Principal, u64)>, TournamentError> {
+    let table_id = *tournament
+        .tables
+        .keys()
+        .next()
+        .ok_or(TournamentError::TableNotFound)?;
+    let table = get_table_wrapper(table_id).await?;
+    let mut stacks: Vec<(Principal, u64)> = table
+        .users
+        .users
+        .iter()
+        .filter(|(principal, user)| {
+            user.balance > 0 && tournament.current_players.contains_key(principal)
+        })
+        .map(|(principal, user)| (*principal, user.balance))
+        .collect();
+    stacks.sort();
+    Ok(stacks)
+}
+
+#[ic_cdk::update]
+async fn propose_deal(
+    users_canister_id: Principal,
+    user_id: Principal,
+    deal_type: DealType,
+    left_for_winner: u64,
+) -> Result<DealProposal, TournamentError> {
+    handle_cycle_check();
+    let tournament = current_tournament()?;
+    validate_deal_caller(users_canister_id, user_id).await?;
+
+    if tournament.state != TournamentState::FinalTable {
+        return Err(TournamentError::InvalidState(
+            "Deals can only be made at the final table".to_string(),
+        ));
+    }
+    if tournament.satellite_options().is_some() {
+        return Err(TournamentError::InvalidState(
+            "Satellites can't make deals".to_string(),
+        ));
+    }
+    if !tournament.current_players.contains_key(&user_id) {
+        return Err(TournamentError::NotRegistered);
+    }
+    let now = ic_cdk::api::time();
+    if is_deal_pending(now)? {
+        return Err(TournamentError::InvalidState(
+            "A deal has already been proposed".to_string(),
+        ));
+    }
+
+    let stacks = final_table_stacks(&tournament).await?;
+    let prizes = remaining_prizes(
+        PRIZE_POOL.load(Ordering::SeqCst),
+        &tournament.payout_structure,
+        stacks.len(),
+    );
+    let proposal = DealProposal::new(user_id, deal_type, stacks, &prizes, left_for_winner, now)?;
+
+    // Someone else may have proposed a deal while the table was fetched
+    let mut deal = DEAL.lock().map_err(|_| TournamentError::LockError)?;
+    if deal.as_ref().is_some_and(|deal| !deal.is_expired(now)) {
+        return Err(TournamentError::InvalidState(
+            "A deal has already been proposed".to_string(),
+        ));
+    }
+    *deal = Some(proposal.clone());
+    Ok(proposal)
+}
+
+#[ic_cdk::update]
+async fn accept_deal(
+    users_canister_id: Principal,
+    user_id: Principal,
+) -> Result<(), TournamentError> {
+    handle_cycle_check();
+    let tournament = current_tournament()?;
+    validate_deal_caller(users_canister_id, user_id).await?;
+
+    // The table plays on while the players make up their minds
+    let stacks = final_table_stacks(&tournament).await?;
+    let is_unanimous = {
+        let mut deal = DEAL.lock().map_err(|_| TournamentError::LockError)?;
+        let is_knocked_out = |proposal: &DealProposal| {
+            proposal
+                .stacks
+                .iter()
+                .any(|(user, _)| !tournament.current_players.contains_key(user))
+        };
+        if deal.as_ref().is_some_and(is_knocked_out) {
+            *deal = None;
+            return Err(TournamentError::InvalidState(
+                "A player in the deal has been knocked out".to_string(),
+            ));
+        }
+        let have_stacks_changed = |proposal: &DealProposal| {
+            let mut proposed_stacks = proposal.stacks.clone();
+            proposed_stacks.sort();
+            proposed_stacks != stacks
+        };
+        if deal.as_ref().is_some_and(have_stacks_changed) {
+            *deal = None;
+            return Err(TournamentError::InvalidState(
+                "The stacks have changed since the deal was proposed".to_string(),
+            ));
+        }
+        deal.as_mut()
+            .ok_or_else(|| TournamentError::InvalidState("No deal has been proposed".to_string()))?
+            .accept(user_id, ic_cdk::api::time())?
+    };
+
+    // The deal is paid out with the rest of the winnings
+    if is_unanimous {
+        handle_tournament_end_wrapper(ic_cdk::api::canister_self()).await?;
+    }
+    Ok(())
+}
+
+#[ic_cdk::update]
+async fn reject_deal(
+    users_canister_id: Principal,
+    user_id: Principal,
+) -> Result<(), TournamentError> {
+    handle_cycle_check();
+    validate_deal_caller(users_canister_id, user_id).await?;
+
+    let mut deal = DEAL.lock().map_err(|_| TournamentError::LockError)?;
+    let is_in_deal = match deal.as_ref() {
+        Some(proposal) => proposal.stacks.iter().any(|(user, _)| *user == user_id),
+        None => {
+            return Err(TournamentError::InvalidState(
+                "No deal has been proposed".to_string(),
+            ))
+        }
+    };
+    if !is_in_deal {
+        return Err(TournamentError::NotRegistered);
+    }
+    *deal = None;
+    Ok(())
+}
+
+#[ic_cdk::query]
+fn get_deal() -> Result<Option<DealProposal>, TournamentError> {
+    let deal = DEAL.lock().map_err(|_| TournamentError::LockError)?;
+    let now = ic_cdk::api::time();
+    Ok(deal.clone().filter(|deal| !deal.is_expired(now)))
+}
@@ -7,6 +7,7 @@ use intercanister_call_wrappers::tournament_canister::{
 };
 use table::table_canister::{join_table, pause_table_for_addon_wrapper, resume_table_wrapper};
 use tournaments::tournaments::{
+    bounty::BountyCollection,
     table_balancing::calculate_players_per_table,
     tournament_type::{TournamentSizeType, TournamentType},
     types::{TableInfo, TournamentData, TournamentState},
@@ -15,8 +16,8 @@ use tournaments::tournaments::{
 use crate::{
     table_balancing::check_and_balance_tables,
     utils::{
-        create_table, handle_cycle_check_async, update_live_leaderboard, update_tournament_state,
-        LEADERBOARD_UPDATE_INTERVAL,
+        create_table, handle_cycle_check_async, pay_bounties, update_live_leaderboard,
+        update_tournament_state, LEADERBOARD_UPDATE_INTERVAL,
     },
     LAST_HEARTBEAT, LAST_LEADERBOARD_UPDATE, TOURNAMENT, TOURNAMENT_INDEX, TOURNAMENT_START_TIME,
 };
@@ -36,6 +37,10 @@ async fn heartbeat() {
 
     LAST_HEARTBEAT.store(current_time, Ordering::Relaxed);
 
+    if let Err(e) = retry_unpaid_bounties().await {
+        ic_cdk::println!("Error retrying unpaid bounties: {:?}", e);
+    }
+
     {
         let tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError);
         let tournament = match tournament {
@@ -90,6 +95,26 @@ async fn heartbeat() {
     }
 }
 
+/// Pays out the bounties that failed to pay out when they were won.
+async fn retry_unpaid_bounties() -> Result<(), TournamentError> {
+    let currency = {
+        let tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+        let tournament = tournament
+            .as_ref()
+            .ok_or(TournamentError::TournamentNotFound)?;
+        if tournament
+            .unpaid_bounties
+            .as_ref()
+            .is_none_or(|bounties| bounties.is_empty())
+        {
+            return Ok(());
+        }
+        tournament.currency
+    };
+    pay_bounties(currency, BountyCollection::default()).await;
+    Ok(())
+}
+
 async fn check_late_registration_end() -> Result<(), TournamentError> {
     let tournament = {
         let tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
@@ -12,9 +12,10 @@ use intercanister_call_wrappers::{
         handle_cancelled_tournament_wrapper, return_all_cycles_to_tournament_index_wrapper,
         user_leave_tournament_wrapper,
     },
-    users_canister::get_user_wrapper,
+    users_canister::{get_user_wrapper, redeem_ticket_wrapper, refund_ticket_wrapper},
 };
 use lazy_static::lazy_static;
+use satellite::award_satellite_prizes;
 use std::{
     collections::HashSet,
     sync::{
@@ -34,20 +35,25 @@ use table::{
 };
 use table_balancing::{check_and_balance_tables, move_player_to_table};
 use tournaments::tournaments::{
+    deal::{DealProposal, DealType},
     table_balancing::get_balance_interval,
     tournament_type::{TournamentSizeType, TournamentType},
     types::{TournamentData, TournamentState, UserTournamentAction, UserTournamentData},
     utils::calculate_rake,
 };
 use utils::{
-    add_to_tournament_prize_pool, handle_addon, handle_cycle_check, handle_cycle_check_async,
-    handle_invalid_join, handle_lost_user_rebuy_availability, handle_rebuy, handle_reentry,
-    handle_refund, handle_tournament_deposit, transfer_cycles_to_tournament_index,
-    update_live_leaderboard, update_tournament_state, LEADERBOARD_UPDATE_INTERVAL,
+    add_buy_in_to_prize_pool, add_to_tournament_prize_pool, fund_ticket, handle_addon,
+    handle_cycle_check, handle_cycle_check_async, handle_invalid_join,
+    handle_lost_user_rebuy_availability, handle_rebuy, handle_reentry, handle_refund,
+    handle_tournament_deposit, is_users_canister_of, pay_bounties, refund_entry_tickets,
+    refund_ticket, transfer_cycles_to_tournament_index, update_live_leaderboard,
+    update_tournament_state, LEADERBOARD_UPDATE_INTERVAL,
 };
 
+pub mod deal;
 pub mod heartbeat;
 pub mod memory;
+pub mod satellite;
 pub mod table_balancing;
 pub mod utils;
 
@@ -80,6 +86,8 @@ lazy_static! {
     static ref CURRENCY_MANAGER: Mutex<CurrencyManager> = Mutex::new(CurrencyManager::new());
 
     static ref DEPOSITORS: Mutex<Vec<(Principal, u64)>> = Mutex::new(Vec::new());
+
+    static ref DEAL: Mutex<Option<DealProposal>> = Mutex::new(None);
 }
 
 #[ic_cdk::init]
@@ -246,6 +254,14 @@ async fn user_join_tournament(
     user_id: Principal,
 ) -> Result<(), TournamentError> {
     handle_cycle_check_async().await;
+    let mut valid_callers = vec![user_id];
+    if let Some(tournament_index) = *TOURNAMENT_INDEX
+        .lock()
+        .map_err(|_| TournamentError::LockError)?
+    {
+        valid_callers.push(tournament_index);
+    }
+    validate_caller(valid_callers);
 
     let tournament_state = {
         let tournament_state = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
@@ -285,27 +301,69 @@ async fn user_join_tournament(
             return Err(TournamentError::AlreadyRegistered);
         }
 
-        tournament_state.current_players.insert(
-            user_id,
-            UserTournamentData::new(
-                users_canister_principal,
-                tournament_state.starting_chips,
-                tournament_state.current_players.len() as u32,
-            ),
+        let user_data = UserTournamentData::new(
+            users_canister_principal,
+            tournament_state.starting_chips,
+            tournament_state.current_players.len() as u32,
         );
+        tournament_state.current_players.insert(user_id, user_data);
 
         tournament_state.clone()
     };
+    let is_freeroll = matches!(
+        tournament_state.tournament_type,
+        TournamentType::Freeroll(_)
+    );
 
     let currency_type = match tournament_state.currency {
         CurrencyType::Real(currency) => currency.to_string(),
         CurrencyType::Fake => "Fake".to_string(),
     };
 
-    if !matches!(
-        tournament_state.tournament_type,
-        TournamentType::Freeroll(_)
-    ) {
+    // A ticket from the user's inventory, including one won in a satellite,
+    // pays the buy-in if they have one, as long as the users index vouches
+    // for the users canister holding it
+    let mut ticket_id = None;
+    let is_users_canister_vouched = !is_freeroll
+        && is_users_canister_of(users_canister_principal, user_id)
+            .await
+            .unwrap_or_else(|e| {
+                ic_cdk::println!("Error looking up the users canister: {:?}", e);
+                false
+            });
+    if is_users_canister_vouched {
+        match redeem_ticket_wrapper(
+            users_canister_principal,
+            user_id,
+            ic_cdk::api::canister_self(),
+            tournament_state.buy_in,
+        )
+        .await
+        {
+            Ok(ticket) => ticket_id = ticket.map(|ticket| ticket.id),
+            Err(e) => ic_cdk::println!("Error redeeming ticket: {:?}", e),
+        }
+    }
+    // The tournament index pays for the ticket as soon as it is used up
+    if let Some(redeemed_ticket_id) = ticket_id {
+        if let Err(e) = fund_ticket(tournament_state.buy_in).await {
+            if let Err(e) =
+                refund_ticket_wrapper(users_canister_principal, user_id, redeemed_ticket_id).await
+            {
+                ic_cdk::println!("Error refunding ticket: {:?}", e);
+            }
+            return handle_invalid_join(user_id, currency_type, false, e);
+        }
+    }
+    if let Some(ticket_id) = ticket_id {
+        let mut tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+        let tournament = tournament
+            .as_mut()
+            .ok_or(TournamentError::TournamentNotFound)?;
+        tournament.get_user_tournament_data_mut(&user_id)?.ticket_id = Some(ticket_id);
+    }
+
+    if ticket_id.is_none() && !is_freeroll {
         if let Err(e) =
             handle_tournament_deposit(tournament_state.currency, tournament_state.buy_in, user_id)
                 .await
@@ -344,21 +402,28 @@ async fn user_join_tournament(
             Ok(_) => (),
             Err(e) => {
                 ic_cdk::println!("Error joining table: {:?}", e);
+                if let Some(ticket_id) = ticket_id {
+                    refund_ticket(
+                        users_canister_principal,
+                        user_id,
+                        ticket_id,
+                        tournament_state.currency,
+                        tournament_state.buy_in,
+                    )
+                    .await;
+                }
                 handle_invalid_join(
                     user_id,
                     currency_type,
-                    true,
+                    ticket_id.is_none(),
                     TournamentError::CanisterCallError(format!("{:?}", e)),
                 )?;
             }
         }
     }
 
-    if !matches!(
-        tournament_state.tournament_type,
-        TournamentType::Freeroll(_)
-    ) {
-        add_to_tournament_prize_pool(tournament_state.buy_in)?;
+    if !is_freeroll {
+        add_buy_in_to_prize_pool(user_id, tournament_state.buy_in)?;
     }
 
     Ok(())
@@ -416,6 +481,7 @@ async fn handle_cancelled_tournament() -> Result<(), TournamentError> {
             }
         }
     }
+    refund_entry_tickets().await?;
 
     let depositors = {
         DEPOSITORS
@@ -463,38 +529,60 @@ async fn user_leave_tournament(
     user_id: Principal,
 ) -> Result<(), TournamentError> {
     handle_cycle_check();
-    let tournament = {
+    let (tournament, ticket_id) = {
         let mut tournament_state = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
         let tournament_state = tournament_state.as_mut();
 
         if let Some(tournament_state) = tournament_state {
             validate_caller(vec![tournament_state.id, users_canister_id, user_id]);
-            tournament_state.current_players.remove(&user_id);
-            tournament_state.clone()
+            let ticket_id = tournament_state
+                .current_players
+                .remove(&user_id)
+                .and_then(|user_data| user_data.ticket_id);
+            (tournament_state.clone(), ticket_id)
         } else {
             return Err(TournamentError::TournamentNotFound);
         }
     };
 
     let mut table_id = Principal::anonymous();
-    for table in tournament.tables {
+    for table in &tournament.tables {
         if table.1.players.contains(&user_id) {
-            table_id = table.0;
+            table_id = *table.0;
             break;
         }
     }
     if table_id != Principal::anonymous() {
         let _ = leave_table_wrapper(table_id, users_canister_id, user_id).await?;
     }
+    if let Some(ticket_id) = ticket_id {
+        refund_ticket(
+            users_canister_id,
+            user_id,
+            ticket_id,
+            tournament.currency,
+            tournament.buy_in,
+        )
+        .await;
+    }
 
     if !matches!(tournament.tournament_type, TournamentType::Freeroll(_)) {
         match tournament.currency {
             CurrencyType::Real(currency) => {
                 let currency_type = currency.to_string();
-                let (prize_pool, rake_amount) = calculate_rake(tournament.buy_in)?;
-
-                if let Err(e) = handle_refund(user_id, tournament.buy_in, currency_type) {
-                    ic_cdk::println!("Error refunding user: {:?}", e);
+                // The bounty part of the buy-in never went into the prize pool
+                let prize_pool_part = tournament
+                    .bounty_options()
+                    .map_or(tournament.buy_in, |bounty| {
+                        bounty.split_buy_in(tournament.buy_in).0
+                    });
+                let (prize_pool, rake_amount) = calculate_rake(prize_pool_part)?;
+
+                // A ticket's buy-in goes back to the tournament index instead
+                if ticket_id.is_none() {
+                    if let Err(e) = handle_refund(user_id, tournament.buy_in, currency_type) {
+                        ic_cdk::println!("Error refunding user: {:?}", e);
+                    }
                 }
                 PRIZE_POOL.fetch_sub(prize_pool, Ordering::SeqCst);
                 RAKE_AMOUNT.fetch_sub(rake_amount, Ordering::SeqCst);
@@ -780,32 +868,25 @@ async fn distribute_winnings(table: PublicTable) -> Result<(), TournamentError>
     };
     update_tournament_state(TournamentState::Completed).await?;
 
+    // A deal agreed at the final table replaces the prizes of the players left
+    let deal = DEAL
+        .lock()
+        .map_err(|_| TournamentError::LockError)?
+        .take()
+        .filter(|deal| deal.is_unanimous());
     let total_prize = PRIZE_POOL.load(Ordering::SeqCst);
     let positions: Vec<Principal> = {
         let mut leaderboard = LEADERBOARD.lock().map_err(|_| TournamentError::LockError)?;
 
-        // Get remaining players sorted by balance
-        let mut active_players: Vec<_> = table
+        // Get the players still at the final table
+        let active_players: Vec<_> = table
             .users
             .users
             .iter()
             .map(|(principal, user)| (*principal, user.balance))
             .collect();
 
-        // Sort by balance in descending order
-        active_players.sort_by(|a, b| b.1.cmp(&a.1));
-
-        // Combine active players with eliminated players
-        let positions = active_players
-            .into_iter()
-            .map(|(principal, _)| principal)
-            .collect::<Vec<_>>();
-
-        for position in positions {
-            leaderboard.push(position);
-        }
-
-        leaderboard.iter().rev().copied().collect()
+        tournament.finishing_positions(&mut leaderboard, active_players)
     };
 
     {
@@ -823,6 +904,11 @@ async fn distribute_winnings(table: PublicTable) -> Result<(), TournamentError>
         tournament.sorted_users = Some(positions.clone());
     }
 
+    let satellite = tournament.satellite_options();
+    if let Some(satellite) = satellite {
+        award_satellite_prizes(tournament.currency, satellite, &positions, total_prize).await?;
+    }
+
     if let CurrencyType::Real(currency) = tournament.currency {
         let currency_manager = {
             CURRENCY_MANAGER
@@ -831,8 +917,34 @@ async fn distribute_winnings(table: PublicTable) -> Result<(), TournamentError>
                 .clone()
         };
 
-        // Distribute according to payout structure
-        for (position, payout) in tournament.payout_structure.iter().enumerate() {
+        let dealt_places = match &deal {
+            Some(deal) => {
+                for (user_id, amount) in &deal.payouts {
+                    currency_manager
+                        .withdraw(&currency, *user_id, *amount)
+                        .await
+                        .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))?;
+
+                    ic_cdk::println!(
+                        "Distributed {} to user {} by deal",
+                        amount,
+                        user_id.to_text()
+                    );
+                }
+                deal.payouts.len()
+            }
+            None => 0,
+        };
+
+        // Distribute according to payout structure, satellites pay
+        // out tickets instead
+        for (position, payout) in tournament
+            .payout_structure
+            .iter()
+            .enumerate()
+            .skip(dealt_places)
+            .filter(|_| satellite.is_none())
+        {
             if position < positions.len() {
                 let user_id = positions[position];
 
@@ -847,6 +959,23 @@ async fn distribute_winnings(table: PublicTable) -> Result<(), TournamentError>
             }
         }
 
+        // The winner keeps the bounty on their own head, after a deal
+        // every player left does
+        for user_id in positions.iter().take(dealt_places.max(1)) {
+            let bounty = tournament
+                .get_user_tournament_data(user_id)
+                .ok()
+                .and_then(|user_data| user_data.bounty)
+                .unwrap_or(0);
+            if bounty > 0 {
+                currency_manager
+                    .withdraw(&currency, *user_id, bounty)
+                    .await
+                    .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))?;
+                ic_cdk::println!("Paid bounty of {} to user {}", bounty, user_id.to_text());
+            }
+        }
+
         let rake = RAKE_AMOUNT.load(Ordering::SeqCst);
         let tournament_index = match TOURNAMENT_INDEX.lock() {
             Ok(tournament_index) => match tournament_index.as_ref() {
@@ -1067,6 +1196,7 @@ async fn get_total_prize_pool() -> u64 {
 async fn handle_user_losing(
     user_principal: Principal,
     table_id: Principal,
+    eliminators: Vec<(Principal, u64)>,
 ) -> Result<(), TournamentError> {
     handle_cycle_check();
 
@@ -1078,9 +1208,28 @@ async fn handle_user_losing(
             .clone();
         let valid_callers = vec![table_id, tournament.id];
         validate_caller(valid_callers);
+        if !tournament.tables.contains_key(&table_id) {
+            return Err(TournamentError::TableNotFound);
+        }
         tournament
     };
 
+    // Pay out the bounty of the player before they can rebuy. Only the
+    // table the player lost at knows who knocked them out.
+    if ic_cdk::api::msg_caller() == table_id {
+        let bounties = {
+            let mut tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+            tournament
+                .as_mut()
+                .ok_or(TournamentError::TournamentNotFound)?
+                .collect_bounty(&user_principal, &eliminators)
+        };
+        match bounties {
+            Ok(bounties) => pay_bounties(tournament.currency, bounties).await,
+            Err(e) => ic_cdk::println!("Error collecting bounty: {:?}", e),
+        }
+    }
+
     // Check if rebuy is possible
     match &tournament.tournament_type {
         TournamentType::BuyIn(buy_in_type) => {
@@ -1,4 +1,4 @@
-use candid::Principal;
+use candid::{Decode, Encode, Principal};
 use currency::state::TransactionState;
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
@@ -6,11 +6,11 @@ use ic_stable_structures::{
 };
 use std::cell::RefCell;
 use std::sync::atomic::Ordering;
-use tournaments::tournaments::types::TournamentData;
+use tournaments::tournaments::{deal::DealProposal, types::TournamentData};
 
 use crate::{
-    LAST_BALANCE_TIMESTAMP, LAST_HEARTBEAT, LEADERBOARD, PRIZE_POOL, TOURNAMENT, TOURNAMENT_INDEX,
-    TOURNAMENT_START_TIME, TRANSACTION_STATE,
+    DEAL, LAST_BALANCE_TIMESTAMP, LAST_HEARTBEAT, LEADERBOARD, PRIZE_POOL, TOURNAMENT,
+    TOURNAMENT_INDEX, TOURNAMENT_START_TIME, TRANSACTION_STATE,
 };
 
 // Define memory type
@@ -76,6 +76,13 @@ thread_local! {
             TransactionState::new()
         ).unwrap()
     );
+
+    static DEAL_CELL: RefCell<Cell<Vec<u8>, Memory>> = RefCell::new(
+        Cell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(8))),
+            Vec::new()
+        ).unwrap()
+    );
 }
 
 #[ic_cdk::pre_upgrade]
@@ -150,6 +157,20 @@ fn pre_upgrade() {
         } else {
             ic_cdk::println!("Failed to acquire TRANSACTION_STATE lock");
         }
+
+        // Save DEAL
+        if let Ok(deal) = DEAL.lock() {
+            DEAL_CELL.with(|cell| {
+                let mut cell = cell.borrow_mut();
+                let bytes = Encode!(&*deal).unwrap_or_else(|e| {
+                    ic_cdk::println!("Deal serialization error: {:?}", e);
+                    vec![]
+                });
+                let _ = cell.set(bytes);
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire DEAL lock");
+        }
     });
 
     if res.is_err() {
@@ -252,6 +273,22 @@ fn post_upgrade() {
         } else {
             ic_cdk::println!("Failed to acquire TRANSACTION_STATE lock");
         }
+
+        // Restore DEAL
+        if let Ok(mut deal) = DEAL.lock() {
+            DEAL_CELL.with(|cell| {
+                let cell = cell.borrow();
+                let bytes = cell.get();
+                if !bytes.is_empty() {
+                    *deal = Decode!(bytes, Option<DealProposal>).unwrap_or_else(|e| {
+                        ic_cdk::println!("Deal deserialization error: {:?}", e);
+                        None
+                    });
+                }
+            });
+        } else {
+            ic_cdk::println!("Failed to acquire DEAL lock");
+        }
     });
 
     if res.is_err() {
@@ -0,0 +1,92 @@
+use candid::Principal;
+use currency::Currency;
+use errors::tournament_error::TournamentError;
+use intercanister_call_wrappers::users_canister::issue_ticket_wrapper;
+use table::poker::game::table_functions::types::CurrencyType;
+use tournaments::tournaments::satellite::SatelliteOptions;
+use user::ticket::{Ticket, TicketScope};
+
+use crate::{utils::get_users_canister_of, CURRENCY_MANAGER, TOURNAMENT_INDEX};
+
+/// Issues the tickets a satellite pays out into the winners' inventories
+/// and sends their value to the tournament index, which pays the target
+/// tournament when a ticket is used. A player who can't be issued a ticket
+/// is paid its value in cash instead.
+pub async fn award_satellite_prizes(
+    currency: CurrencyType,
+    options: &SatelliteOptions,
+    positions: &[Principal],
+    prize_pool: u64,
+) -> Result<(), TournamentError> {
+    let payouts = options.payouts(positions, prize_pool);
+    let currency = match currency {
+        CurrencyType::Real(currency) => Some(currency),
+        CurrencyType::Fake => None,
+    };
+    let tournament_index = TOURNAMENT_INDEX
+        .lock()
+        .map_err(|_| TournamentError::LockError)?
+        .ok_or_else(|| TournamentError::InvalidState("Tournament index not found".to_string()))?;
+
+    for user_id in payouts.tickets {
+        let recipient = match issue_satellite_ticket(options, user_id).await {
+            Ok(ticket) => {
+                ic_cdk::println!(
+                    "Issued satellite ticket {} to user {}",
+                    ticket.id,
+                    user_id.to_text()
+                );
+                tournament_index
+            }
+            Err(e) => {
+                ic_cdk::println!("Error issuing satellite ticket: {:?}", e);
+                user_id
+            }
+        };
+        if let Some(currency) = &currency {
+            withdraw(currency, recipient, options.ticket_value).await?;
+        }
+    }
+
+    if let (Some(currency), Some((user_id, amount))) = (&currency, payouts.cash) {
+        withdraw(currency, user_id, amount).await?;
+        ic_cdk::println!("Distributed {} to user {}", amount, user_id.to_text());
+    }
+    Ok(())
+}
+
+/// Gives the user a ticket to the target tournament in the users canister
+/// the users index has their account in.
+async fn issue_satellite_ticket(
+    options: &SatelliteOptions,
+    user_id: Principal,
+) -> Result<Ticket, TournamentError> {
+    let users_canister = get_users_canister_of(user_id).await?;
+    issue_ticket_wrapper(
+        users_canister,
+        user_id,
+        TicketScope::Tournament(options.target_tournament),
+        options.ticket_value,
+        None,
+    )
+    .await
+    .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))
+}
+
+async fn withdraw(
+    currency: &Currency,
+    recipient: Principal,
+    amount: u64,
+) -> Result<(), TournamentError> {
+    let currency_manager = {
+        CURRENCY_MANAGER
+            .lock()
+            .map_err(|_| TournamentError::LockError)?
+            .clone()
+    };
+    currency_manager
+        .withdraw(currency, recipient, amount)
+        .await
+        .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))?;
+    Ok(())
+}
@@ -12,8 +12,12 @@ use errors::{
 };
 use ic_cdk::management_canister::DepositCyclesArgs;
 use ic_ledger_types::{AccountIdentifier, Subaccount};
-use intercanister_call_wrappers::tournament_canister::{
-    handle_tournament_end_wrapper, update_tournament_state_icc_wrapper,
+use intercanister_call_wrappers::{
+    tournament_canister::{
+        fund_tournament_tickets_wrapper, handle_tournament_end_wrapper,
+        update_tournament_state_icc_wrapper,
+    },
+    users_canister::{get_users_canister_principal_by_id_wrapper, refund_ticket_wrapper},
 };
 use table::{
     poker::game::{
@@ -26,6 +30,7 @@ use table::{
     },
 };
 use tournaments::tournaments::{
+    bounty::BountyCollection,
     tournament_type::{TournamentSizeType, TournamentType},
     types::{TournamentData, TournamentState},
     utils::calculate_rake,
@@ -312,8 +317,119 @@ where
     Err(error)
 }
 
-pub async fn check_tournament_end(remaining_players: usize) -> Result<(), TournamentError> {
-    if remaining_players == 1 {
+/// Gets the users index of the environment the tournament index runs in.
+pub fn get_user_index_principal(tournament_index_principal: Principal) -> Principal {
+    if tournament_index_principal == Principal::from_text("zocwf-5qaaa-aaaam-qdfaq-cai").unwrap() {
+        Principal::from_text("lvq5c-nyaaa-aaaam-qdswa-cai").unwrap()
+    } else if tournament_index_principal
+        == Principal::from_text("u2qna-fiaaa-aaaag-at3ea-cai").unwrap()
+    {
+        Principal::from_text("m3tym-daaaa-aaaah-qqbsq-cai").unwrap()
+    } else {
+        Principal::from_text("txyno-ch777-77776-aaaaq-cai").unwrap()
+    }
+}
+
+/// Looks up the users canister holding the user's account in the users
+/// index.
+pub async fn get_users_canister_of(user_id: Principal) -> Result<Principal, TournamentError> {
+    let tournament_index = TOURNAMENT_INDEX
+        .lock()
+        .map_err(|_| TournamentError::LockError)?
+        .ok_or_else(|| TournamentError::InvalidState("Tournament index not found".to_string()))?;
+    get_users_canister_principal_by_id_wrapper(get_user_index_principal(tournament_index), user_id)
+        .await
+        .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))
+}
+
+/// Whether the users index has the user's account in the users canister,
+/// so the users canister may act for the user.
+pub async fn is_users_canister_of(
+    users_canister_id: Principal,
+    user_id: Principal,
+) -> Result<bool, TournamentError> {
+    Ok(get_users_canister_of(user_id).await? == users_canister_id)
+}
+
+/// Has the tournament index pay the buy-in of a ticket the user redeemed.
+pub async fn fund_ticket(buy_in: u64) -> Result<(), TournamentError> {
+    let tournament_index = TOURNAMENT_INDEX
+        .lock()
+        .map_err(|_| TournamentError::LockError)?
+        .ok_or_else(|| TournamentError::InvalidState("Tournament index not found".to_string()))?;
+    fund_tournament_tickets_wrapper(tournament_index, buy_in)
+        .await
+        .map_err(|e| TournamentError::CanisterCallError(format!("{:?}", e)))
+}
+
+/// Hands a ticket from the user's inventory back to them and the buy-in
+/// it was funded with back to the tournament index.
+pub async fn refund_ticket(
+    users_canister_id: Principal,
+    user_id: Principal,
+    ticket_id: u64,
+    currency: CurrencyType,
+    buy_in: u64,
+) {
+    if let Err(e) = refund_ticket_wrapper(users_canister_id, user_id, ticket_id).await {
+        ic_cdk::println!("Error refunding ticket: {:?}", e);
+    }
+
+    if let CurrencyType::Real(currency) = currency {
+        let tournament_index = match TOURNAMENT_INDEX.lock() {
+            Ok(tournament_index) => *tournament_index,
+            Err(_) => None,
+        };
+        let Some(tournament_index) = tournament_index else {
+            ic_cdk::println!("Tournament index not found, keeping the ticket funds");
+            return;
+        };
+        let currency_manager = match CURRENCY_MANAGER.lock() {
+            Ok(currency_manager) => currency_manager.clone(),
+            Err(_) => return,
+        };
+        if let Err(e) = currency_manager
+            .withdraw(&currency, tournament_index, buy_in)
+            .await
+        {
+            ic_cdk::println!("Error returning ticket funds: {:?}", e);
+        }
+    }
+}
+
+/// Hands back the tickets the players still registered entered with, for
+/// when the tournament is cancelled.
+pub async fn refund_entry_tickets() -> Result<(), TournamentError> {
+    let (tickets, currency, buy_in) = {
+        let mut tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+        let tournament = tournament
+            .as_mut()
+            .ok_or(TournamentError::TournamentNotFound)?;
+
+        let mut tickets = Vec::new();
+        for (user_principal, user_data) in tournament.current_players.iter_mut() {
+            if let Some(ticket_id) = user_data.ticket_id.take() {
+                tickets.push((
+                    user_data.users_canister_principal,
+                    *user_principal,
+                    ticket_id,
+                ));
+            }
+        }
+        (tickets, tournament.currency, tournament.buy_in)
+    };
+
+    for (users_canister_id, user_id, ticket_id) in tickets {
+        refund_ticket(users_canister_id, user_id, ticket_id, currency, buy_in).await;
+    }
+    Ok(())
+}
+
+pub async fn check_tournament_end(
+    remaining_players: usize,
+    is_seat_bubble_burst: bool,
+) -> Result<(), TournamentError> {
+    if remaining_players == 1 || is_seat_bubble_burst {
         handle_tournament_end_wrapper(ic_cdk::api::canister_self()).await?;
     }
 
@@ -393,7 +509,7 @@ pub async fn handle_reentry(
                 TournamentError::CanisterCallError(format!("{:?}", e))
             })?;
 
-            add_to_tournament_prize_pool(buy_in_options.reentry.reentry_price)?;
+            add_buy_in_to_prize_pool(user_id, buy_in_options.reentry.reentry_price)?;
 
             Ok(buy_in_options.reentry.reentry_price)
         }
@@ -457,7 +573,7 @@ pub async fn handle_rebuy(
                 TournamentError::CanisterCallError(format!("{:?}", e))
             })?;
 
-            add_to_tournament_prize_pool(buy_in_options.reentry.reentry_price)?;
+            add_buy_in_to_prize_pool(user_id, buy_in_options.reentry.reentry_price)?;
 
             Ok(buy_in_options.reentry.reentry_price)
         }
@@ -668,9 +784,11 @@ pub async fn handle_user_kick(
 
     let remaining_players = tournament.current_players.len();
     let paying_positions = tournament.payout_structure.len();
+    // Satellites stop once every player left has won a ticket
+    let is_seat_bubble_burst = tournament.is_seat_bubble_burst(PRIZE_POOL.load(Ordering::SeqCst));
 
-    if remaining_players <= paying_positions {
-        match check_tournament_end(remaining_players).await {
+    if remaining_players <= paying_positions || is_seat_bubble_burst {
+        match check_tournament_end(remaining_players, is_seat_bubble_burst).await {
             Ok(_) => {}
             Err(e) => {
                 ic_cdk::println!("Error checking tournament end: {:?}", e);
@@ -778,6 +896,86 @@ pub fn add_to_tournament_prize_pool(amount: u64) -> Result<(), TournamentError>
     Ok(())
 }
 
+/// Adds a buy-in of the user to the prize pool. In bounty tournaments
+/// part of it goes on the user's head instead.
+pub fn add_buy_in_to_prize_pool(user_id: Principal, amount: u64) -> Result<(), TournamentError> {
+    let prize_pool = {
+        let mut tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
+        tournament
+            .as_mut()
+            .ok_or(TournamentError::TournamentNotFound)?
+            .add_buy_in(&user_id, amount)?
+    };
+    add_to_tournament_prize_pool(prize_pool)
+}
+
+/// Pays the bounties collected for a knocked out player to the players
+/// who knocked them out. A bounty nobody won goes into the prize pool.
+pub async fn pay_bounties(currency: CurrencyType, bounties: BountyCollection) {
+    PRIZE_POOL.fetch_add(bounties.unclaimed, Ordering::SeqCst);
+
+    let CurrencyType::Real(currency) = currency else {
+        return;
+    };
+    // The bounties that failed to pay out before are retried first
+    let mut payouts = match TOURNAMENT.lock() {
+        Ok(mut tournament) => tournament
+            .as_mut()
+            .and_then(|tournament| tournament.unpaid_bounties.take())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    payouts.extend(bounties.payouts);
+    payouts.retain(|(_, amount)| *amount > 0);
+    if payouts.is_empty() {
+        return;
+    }
+
+    let currency_manager = match CURRENCY_MANAGER.lock() {
+        Ok(currency_manager) => currency_manager.clone(),
+        Err(_) => {
+            ic_cdk::println!("Failed to lock the currency manager to pay bounties");
+            keep_unpaid_bounties(payouts);
+            return;
+        }
+    };
+    let mut unpaid = Vec::new();
+    for (user_id, amount) in payouts {
+        match currency_manager.withdraw(&currency, user_id, amount).await {
+            Ok(_) => ic_cdk::println!("Paid bounty of {} to user {}", amount, user_id.to_text()),
+            Err(e) => {
+                ic_cdk::println!(
+                    "Failed to pay bounty of {} to user {}: {:?}",
+                    amount,
+                    user_id.to_text(),
+                    e
+                );
+                unpaid.push((user_id, amount));
+            }
+        }
+    }
+    keep_unpaid_bounties(unpaid);
+}
+
+/// Keeps the bounties that couldn't be paid out so they are paid with
+/// the next ones.
+fn keep_unpaid_bounties(unpaid: Vec<(Principal, u64)>) {
+    if unpaid.is_empty() {
+        return;
+    }
+    match TOURNAMENT.lock() {
+        Ok(mut tournament) => {
+            if let Some(tournament) = tournament.as_mut() {
+                tournament
+                    .unpaid_bounties
+                    .get_or_insert_with(Vec::new)
+                    .extend(unpaid);
+            }
+        }
+        Err(_) => ic_cdk::println!("Failed to lock the tournament to keep unpaid bounties"),
+    }
+}
+
 pub async fn update_live_leaderboard() -> Result<(), TournamentError> {
     let mut tournament = {
         let tournament = TOURNAMENT.lock().map_err(|_| TournamentError::LockError)?;
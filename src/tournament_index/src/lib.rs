@@ -29,6 +29,7 @@ use table::poker::game::types::GameType::NoLimit;
 use tournament_index::{create_spin_go_tournament, TournamentIndex};
 use tournaments::tournaments::{
     blind_level::BlindLevel,
+    schedule::{Recurrence, TournamentSchedule},
     tournament_type::TournamentType,
     types::{
         get_blind_level_at_time, NewTournament, NewTournamentSpeedType, TournamentData,
@@ -38,6 +39,7 @@ use tournaments::tournaments::{
 
 pub mod cycle;
 pub mod memory;
+pub mod schedule;
 pub mod tournament_index;
 
 const MINIMUM_CYCLE_THRESHOLD: u128 = 6_000_000_000_000;
@@ -135,6 +137,7 @@ async fn create_tournament(
                 ));
             }
         }
+        validate_satellite_target(&new_tournament)?;
         let tournament_canister = create_tournament_canister().await?;
 
         // Create tournament info
@@ -209,6 +212,46 @@ async fn create_tournament(
     Ok(tournament_canister)
 }
 
+fn validate_satellite_target(new_tournament: &NewTournament) -> Result<(), TournamentIndexError> {
+    let Some(satellite) = &new_tournament.tournament_type.buy_in_options().satellite else {
+        return Ok(());
+    };
+    let state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    let target = state
+        .tournaments
+        .get(&satellite.target_tournament)
+        .ok_or_else(|| {
+            TournamentIndexError::InvalidTournamentConfig(
+                "Satellite target tournament not found".to_string(),
+            )
+        })?;
+
+    if !matches!(
+        target.state,
+        TournamentState::Registration | TournamentState::LateRegistration
+    ) {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Satellite target tournament is not open for registration".to_string(),
+        ));
+    }
+    if matches!(target.tournament_type, TournamentType::Freeroll(_)) {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Satellites can't award tickets to a freeroll".to_string(),
+        ));
+    }
+    if target.currency != new_tournament.currency {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Satellite must use the currency of its target tournament".to_string(),
+        ));
+    }
+    if satellite.ticket_value != target.buy_in {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Satellite ticket value must match the target buy-in".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 #[ic_cdk::update]
 async fn update_tournament_state(
     tournament_id: Principal,
@@ -238,6 +281,46 @@ async fn update_tournament_state(
     Ok(())
 }
 
+/// Whether the tournament was created by this index. The users canisters
+/// only let these tournaments use up tickets.
+#[ic_cdk::query]
+fn is_tournament(tournament_id: Principal) -> Result<bool, TournamentIndexError> {
+    let state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    Ok(state.tournaments.contains_key(&tournament_id))
+}
+
+#[ic_cdk::update]
+async fn fund_tournament_tickets(amount: u64) -> Result<(), TournamentIndexError> {
+    handle_cycle_check().await?;
+    let tournament_id = ic_cdk::api::msg_caller();
+    let currency = {
+        let state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+        let valid_callers: Vec<Principal> = state.tournaments.values().map(|t| t.id).collect();
+        validate_caller(valid_callers);
+
+        state
+            .tournaments
+            .get(&tournament_id)
+            .ok_or(TournamentIndexError::TournamentNotFound)?
+            .currency
+    };
+
+    // Tickets given out from the users canister are paid for out of the rake
+    if let CurrencyType::Real(currency) = currency {
+        let currency_manager = {
+            CURRENCY_MANAGER
+                .lock()
+                .map_err(|_| TournamentIndexError::LockError)?
+                .clone()
+        };
+        currency_manager
+            .withdraw(&currency, tournament_id, amount)
+            .await
+            .map_err(|e| TournamentIndexError::CanisterCallFailed(format!("{:?}", e)))?;
+    }
+    Ok(())
+}
+
 #[ic_cdk::update]
 async fn delete_tournament(tournament_id: Principal) -> Result<(), TournamentIndexError> {
     handle_cycle_check().await?;
@@ -0,0 +1,215 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use authentication::validate_caller;
+use candid::Principal;
+use errors::tournament_index_error::TournamentIndexError;
+use table::poker::game::table_functions::table::TableConfig;
+use tournaments::tournaments::{
+    schedule::{Recurrence, TournamentSchedule},
+    tournament_type::TournamentType,
+    types::{NewTournament, TournamentData},
+};
+
+use crate::{create_tournament, CONTROLLER_PRINCIPALS, STATE};
+
+const SCHEDULE_CHECK_INTERVAL: u64 = 60_000_000_000; // 1 minute in nanoseconds
+
+// A schedule is only checked once per interval, so a shorter lead time could
+// be stepped over. The margin leaves time to create the tournament canister.
+const MIN_LEAD_TIME: u64 = SCHEDULE_CHECK_INTERVAL + 60_000_000_000;
+
+static LAST_SCHEDULE_CHECK: AtomicU64 = AtomicU64::new(0);
+
+fn validate_schedule(
+    template: &NewTournament,
+    table_config: &TableConfig,
+    recurrence: &Recurrence,
+) -> Result<(), TournamentIndexError> {
+    if matches!(
+        template.tournament_type,
+        TournamentType::SitAndGo(_) | TournamentType::SpinAndGo(_, _)
+    ) {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Only tournaments with a start time can be scheduled".to_string(),
+        ));
+    }
+    recurrence.validate()?;
+    TournamentData::new(
+        Principal::anonymous(),
+        template.clone(),
+        table_config.clone(),
+    )?
+    .validate()?;
+    Ok(())
+}
+
+fn validate_lead_time(lead_time_ns: u64) -> Result<(), TournamentIndexError> {
+    if lead_time_ns < MIN_LEAD_TIME {
+        return Err(TournamentIndexError::InvalidTournamentConfig(format!(
+            "Lead time must be at least {} seconds",
+            MIN_LEAD_TIME / 1_000_000_000
+        )));
+    }
+    Ok(())
+}
+
+#[ic_cdk::update]
+fn create_tournament_schedule(
+    template: NewTournament,
+    table_config: TableConfig,
+    recurrence: Recurrence,
+    lead_time_ns: u64,
+) -> Result<TournamentSchedule, TournamentIndexError> {
+    validate_caller(CONTROLLER_PRINCIPALS.clone());
+    validate_schedule(&template, &table_config, &recurrence)?;
+    validate_lead_time(lead_time_ns)?;
+
+    let mut state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    let schedules = state.schedules.get_or_insert_with(Vec::new);
+    let schedule = TournamentSchedule {
+        id: schedules
+            .iter()
+            .map(|schedule| schedule.id + 1)
+            .max()
+            .unwrap_or(0),
+        template,
+        table_config,
+        recurrence,
+        lead_time_ns,
+        paused: false,
+        last_start_time: None,
+        last_tournament: None,
+    };
+    schedules.push(schedule.clone());
+    Ok(schedule)
+}
+
+#[ic_cdk::query]
+fn get_tournament_schedules() -> Result<Vec<TournamentSchedule>, TournamentIndexError> {
+    let state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    Ok(state.schedules.clone().unwrap_or_default())
+}
+
+#[ic_cdk::update]
+fn update_tournament_schedule(
+    schedule_id: u64,
+    template: Option<NewTournament>,
+    table_config: Option<TableConfig>,
+    recurrence: Option<Recurrence>,
+    lead_time_ns: Option<u64>,
+) -> Result<TournamentSchedule, TournamentIndexError> {
+    validate_caller(CONTROLLER_PRINCIPALS.clone());
+    if let Some(lead_time_ns) = lead_time_ns {
+        validate_lead_time(lead_time_ns)?;
+    }
+
+    let mut state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    let schedule = get_schedule_mut(state.schedules.as_mut(), schedule_id)?;
+    let template = template.unwrap_or_else(|| schedule.template.clone());
+    let table_config = table_config.unwrap_or_else(|| schedule.table_config.clone());
+    let recurrence = recurrence.unwrap_or_else(|| schedule.recurrence.clone());
+    validate_schedule(&template, &table_config, &recurrence)?;
+
+    schedule.template = template;
+    schedule.table_config = table_config;
+    schedule.recurrence = recurrence;
+    if let Some(lead_time_ns) = lead_time_ns {
+        schedule.lead_time_ns = lead_time_ns;
+    }
+    Ok(schedule.clone())
+}
+
+#[ic_cdk::update]
+fn pause_tournament_schedule(
+    schedule_id: u64,
+    paused: bool,
+) -> Result<TournamentSchedule, TournamentIndexError> {
+    validate_caller(CONTROLLER_PRINCIPALS.clone());
+
+    let mut state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    let schedule = get_schedule_mut(state.schedules.as_mut(), schedule_id)?;
+    schedule.paused = paused;
+    Ok(schedule.clone())
+}
+
+#[ic_cdk::update]
+fn delete_tournament_schedule(schedule_id: u64) -> Result<(), TournamentIndexError> {
+    validate_caller(CONTROLLER_PRINCIPALS.clone());
+
+    let mut state = STATE.lock().map_err(|_| TournamentIndexError::LockError)?;
+    let schedules = state.schedules.get_or_insert_with(Vec::new);
+    let len = schedules.len();
+    schedules.retain(|schedule| schedule.id != schedule_id);
+    if schedules.len() == len {
+        return Err(TournamentIndexError::InvalidTournamentConfig(
+            "Schedule not found".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn get_schedule_mut(
+    schedules: Option<&mut Vec<TournamentSchedule>>,
+    schedule_id: u64,
+) -> Result<&mut TournamentSchedule, TournamentIndexError> {
+    schedules
+        .into_iter()
+        .flatten()
+        .find(|schedule| schedule.id == schedule_id)
+        .ok_or_else(|| {
+            TournamentIndexError::InvalidTournamentConfig("Schedule not found".to_string())
+        })
+}
+
+#[ic_cdk::heartbeat]
+async fn heartbeat() {
+    let now = ic_cdk::api::time();
+    if now - LAST_SCHEDULE_CHECK.load(Ordering::Relaxed) < SCHEDULE_CHECK_INTERVAL {
+        return;
+    }
+    LAST_SCHEDULE_CHECK.store(now, Ordering::Relaxed);
+
+    // The start time is recorded before the tournament is created so the
+    // next heartbeat doesn't create it again. A failed occurrence is skipped.
+    let due = {
+        let mut state = match STATE.lock() {
+            Ok(state) => state,
+            Err(e) => {
+                ic_cdk::println!("Error getting state: {:?}", e);
+                return;
+            }
+        };
+        let mut due = Vec::new();
+        for schedule in state.schedules.iter_mut().flatten() {
+            if let Some(start_time) = schedule.due(now) {
+                schedule.last_start_time = Some(start_time);
+                let mut template = schedule.template.clone();
+                template.start_time = start_time;
+                due.push((schedule.id, template, schedule.table_config.clone()));
+            }
+        }
+        due
+    };
+
+    for (schedule_id, template, table_config) in due {
+        match create_tournament(template, table_config).await {
+            Ok(tournament_id) => {
+                ic_cdk::println!(
+                    "Created tournament {} from schedule {}",
+                    tournament_id.to_text(),
+                    schedule_id
+                );
+                if let Ok(mut state) = STATE.lock() {
+                    if let Ok(schedule) = get_schedule_mut(state.schedules.as_mut(), schedule_id) {
+                        schedule.last_tournament = Some(tournament_id);
+                    }
+                }
+            }
+            Err(e) => ic_cdk::println!(
+                "Error creating tournament from schedule {}: {:?}",
+                schedule_id,
+                e
+            ),
+        }
+    }
+}
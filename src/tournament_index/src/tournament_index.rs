@@ -10,6 +10,7 @@ use intercanister_call_wrappers::tournament_canister::{
 use serde::{Deserialize, Serialize};
 use table::poker::game::table_functions::{table::TableConfig, types::CurrencyType};
 use tournaments::tournaments::{
+    schedule::TournamentSchedule,
     spin_and_go::SpinGoMultiplier,
     tournament_type::{BuyInOptions, TournamentSizeType, TournamentType},
     types::{NewTournament, NewTournamentSpeedType, PayoutPercentage, TournamentData},
@@ -26,6 +27,9 @@ pub struct TournamentIndex {
     // Add fields for Spin and Go pools
     pub spin_go_pools: HashMap<u64, Vec<(Principal, Principal)>>, // Map buy-in amount to a queue of ready tournaments
     pub spin_go_templates: HashMap<u64, SpinGoTemplate>, // Store templates for different buy-in amounts
+
+    /// Templates for tournaments that are created on a schedule.
+    pub schedules: Option<Vec<TournamentSchedule>>,
 }
 
 #[derive(Debug, CandidType, Serialize, Deserialize, Clone)]
@@ -75,6 +79,7 @@ impl TournamentIndex {
             completed_tournaments: Vec::new(),
             spin_go_pools: HashMap::new(),
             spin_go_templates,
+            schedules: None,
         }
     }
 
@@ -8,7 +8,10 @@ use ic_verifiable_credentials::{
     issuer_api::CredentialSpec, validate_ii_presentation_and_claims, VcFlowSigners,
 };
 use lazy_static::lazy_static;
-use user::user::{User, UserAvatar};
+use user::{
+    ticket::{Ticket, TicketScope},
+    user::{User, UserAvatar},
+};
 
 use std::{collections::HashMap, sync::Mutex};
 
@@ -425,6 +428,102 @@ async fn verify_credential(
     Ok(())
 }
 
+/// Gets the tournament index of the environment the users index runs in.
+fn get_tournament_index_principal() -> Result<Principal, UserError> {
+    let user_index = (*USER_INDEX_PRINCIPAL
+        .lock()
+        .map_err(|_| UserError::LockError)?)
+    .ok_or(UserError::StateNotInitialized)?;
+    let tournament_index =
+        if user_index == Principal::from_text("lvq5c-nyaaa-aaaam-qdswa-cai").unwrap() {
+            "zocwf-5qaaa-aaaam-qdfaq-cai"
+        } else if user_index == Principal::from_text("m3tym-daaaa-aaaah-qqbsq-cai").unwrap() {
+            "u2qna-fiaaa-aaaag-at3ea-cai"
+        } else {
+            "t63gs-up777-77776-aaaba-cai"
+        };
+    Ok(Principal::from_text(tournament_index).unwrap())
+}
+
+/// Checks with the tournament index that the caller is one of its
+/// tournaments.
+async fn validate_tournament_caller() -> Result<(), UserError> {
+    let call_result =
+        ic_cdk::call::Call::unbounded_wait(get_tournament_index_principal()?, "is_tournament")
+            .with_arg(ic_cdk::api::msg_caller())
+            .await
+            .map_err(|e| UserError::CanisterCallFailed(format!("{:?}", e)))?;
+    let is_tournament: Result<bool, candid::Reserved> = call_result
+        .candid()
+        .map_err(|e| UserError::CanisterCallFailed(format!("{:?}", e)))?;
+    if !is_tournament.unwrap_or(false) {
+        return Err(UserError::AuthorizationError);
+    }
+    Ok(())
+}
+
+/// Tickets
+#[ic_cdk::update]
+async fn issue_ticket(
+    user_id: Principal,
+    scope: TicketScope,
+    value: u64,
+    expires_at: Option<u64>,
+) -> Result<Ticket, UserError> {
+    handle_cycle_check().await;
+    // Satellites issue the tickets they award themselves
+    if !CONTROLLER_PRINCIPALS.contains(&ic_cdk::api::msg_caller()) {
+        validate_tournament_caller().await?;
+    }
+    if value == 0 {
+        return Err(UserError::InvalidRequest(
+            "Ticket value cannot be zero".to_string(),
+        ));
+    }
+    let now = ic_cdk::api::time();
+    if expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(UserError::InvalidRequest(
+            "Ticket expiry must be in the future".to_string(),
+        ));
+    }
+
+    let mut users = USERS.lock().map_err(|_| UserError::LockError)?;
+    let user = users.get_mut(&user_id).ok_or(UserError::UserNotFound)?;
+    Ok(user.issue_ticket(ic_cdk::api::msg_caller(), scope, value, expires_at, now))
+}
+
+#[ic_cdk::query]
+fn get_tickets(user_id: Principal) -> Result<Vec<Ticket>, UserError> {
+    let users = USERS.lock().map_err(|_| UserError::LockError)?;
+    let user = users.get(&user_id).ok_or(UserError::UserNotFound)?;
+    Ok(user.get_tickets(ic_cdk::api::time()))
+}
+
+#[ic_cdk::update]
+async fn redeem_ticket(
+    user_id: Principal,
+    tournament_id: Principal,
+    buy_in: u64,
+) -> Result<Option<Ticket>, UserError> {
+    handle_cycle_check().await;
+    // Only the tournament itself can use up a ticket to enter it
+    validate_caller(vec![tournament_id]);
+    validate_tournament_caller().await?;
+
+    let mut users = USERS.lock().map_err(|_| UserError::LockError)?;
+    let user = users.get_mut(&user_id).ok_or(UserError::UserNotFound)?;
+    Ok(user.redeem_ticket(tournament_id, buy_in, ic_cdk::api::time()))
+}
+
+#[ic_cdk::update]
+async fn refund_ticket(user_id: Principal, ticket_id: u64) -> Result<Ticket, UserError> {
+    handle_cycle_check().await;
+    let mut users = USERS.lock().map_err(|_| UserError::LockError)?;
+    let user = users.get_mut(&user_id).ok_or(UserError::UserNotFound)?;
+    user.refund_ticket(ticket_id, ic_cdk::api::msg_caller())
+        .ok_or_else(|| UserError::InvalidRequest("Ticket not found".to_string()))
+}
+
 /// Referral system
 #[ic_cdk::query]
 fn get_referred_users(user_id: Principal) -> Result<Vec<Principal>, UserError> {
@@ -30,6 +30,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_1 = test_env
@@ -58,6 +71,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_2 = test_env
@@ -86,6 +112,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_3 = test_env
@@ -112,6 +151,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_4 = test_env
@@ -138,6 +190,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_5 = test_env
@@ -164,6 +229,19 @@ fn join_table() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let public_table_6 = test_env
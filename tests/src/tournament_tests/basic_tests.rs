@@ -96,6 +96,19 @@ fn create_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
@@ -154,6 +167,19 @@ fn join_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
@@ -224,6 +250,19 @@ fn duplicate_join_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
@@ -299,6 +338,19 @@ fn invalid_join_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
@@ -368,6 +420,19 @@ fn leave_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
@@ -454,6 +519,19 @@ fn start_tournament() {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     };
 
     let id = test_env
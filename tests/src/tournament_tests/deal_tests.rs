@@ -0,0 +1,86 @@
+use candid::{decode_one, encode_args, Principal};
+use errors::tournament_error::TournamentError;
+use tournaments::tournaments::types::PayoutPercentage;
+
+use crate::TestEnv;
+
+impl TestEnv {
+    pub fn accept_deal(
+        &self,
+        tournament_id: Principal,
+        caller: Principal,
+        users_canister_id: Principal,
+        user_id: Principal,
+    ) -> Result<(), TournamentError> {
+        let result = self.pocket_ic.update_call(
+            tournament_id,
+            caller,
+            "accept_deal",
+            encode_args((users_canister_id, user_id)).unwrap(),
+        );
+
+        match result {
+            Ok(arg) => {
+                let res: Result<(), TournamentError> = decode_one(&arg).unwrap();
+                res
+            }
+            _ => panic!("Failed to accept deal"),
+        }
+    }
+}
+
+#[test]
+fn test_stranger_cannot_accept_deal_for_player() {
+    let test_env = TestEnv::new(None);
+    let (tournament_id, _) = test_env.setup_payout_tournament(vec![PayoutPercentage {
+        position: 1,
+        percentage: 100,
+    }]);
+
+    let mut users = Vec::new();
+    for i in 0..2 {
+        let user_id = test_env.pocket_ic.create_canister();
+        let user = test_env
+            .create_user(format!("User {}", i), user_id)
+            .expect("Failed to create user");
+        let amount = (1000000000 + ic_ledger_types::DEFAULT_FEE.e8s() * 2) as f64 / 1e8;
+        test_env.transfer_approve_tokens_for_testing(tournament_id, user_id, amount, true);
+        test_env
+            .join_tournament(tournament_id, user.users_canister_id, user_id)
+            .unwrap();
+        users.push(user);
+    }
+    let (player, stranger) = (&users[0], &users[1]);
+
+    // Passing their own principal as the users canister doesn't let a
+    // stranger act for another player
+    assert_eq!(
+        test_env.accept_deal(
+            tournament_id,
+            stranger.principal_id,
+            stranger.principal_id,
+            player.principal_id,
+        ),
+        Err(TournamentError::NotAuthorized)
+    );
+    assert_eq!(
+        test_env.accept_deal(
+            tournament_id,
+            stranger.principal_id,
+            stranger.users_canister_id,
+            player.principal_id,
+        ),
+        Err(TournamentError::NotAuthorized)
+    );
+
+    // The player themselves gets past the caller check
+    assert_ne!(
+        test_env.accept_deal(
+            tournament_id,
+            player.principal_id,
+            player.users_canister_id,
+            player.principal_id,
+        ),
+        Err(TournamentError::NotAuthorized)
+    );
+}
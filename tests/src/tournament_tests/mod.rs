@@ -1,6 +1,7 @@
 pub mod addon_tests;
 pub mod basic_tests;
 pub mod cycle_tests;
+pub mod deal_tests;
 pub mod late_registration_tests;
 pub mod multi_table_tests;
 pub mod multiple_blind_level_tests;
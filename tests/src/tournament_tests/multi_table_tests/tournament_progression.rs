@@ -83,6 +83,19 @@ impl TestEnv {
             is_shared_rake: None,
             require_proof_of_humanity: None,
             is_paused: None,
+            is_hi_lo: None,
+            is_short_deck: None,
+            encrypt_hole_cards: None,
+            straddle_type: None,
+            max_board_runs: None,
+            time_bank_seconds: None,
+            time_bank_refill_seconds: None,
+            time_bank_refill_hands: None,
+            enforce_missed_blinds: None,
+            min_buy_in_big_blinds: None,
+            max_buy_in_big_blinds: None,
+            rathole_window_seconds: None,
+            is_fast_fold: None,
         };
 
         let id = self
@@ -81,6 +81,8 @@ fn create_test_tournament_config() -> NewTournament {
                 min_chips_for_rebuy: 0,
             },
             freezout: false,
+            bounty: None,
+            satellite: None,
         })),
         currency: table::poker::game::table_functions::types::CurrencyType::Real(
             currency::Currency::ICP,
@@ -113,6 +115,19 @@ fn create_test_table_config() -> TableConfig {
         is_shared_rake: None,
         require_proof_of_humanity: None,
         is_paused: None,
+        is_hi_lo: None,
+        is_short_deck: None,
+        encrypt_hole_cards: None,
+        straddle_type: None,
+        max_board_runs: None,
+        time_bank_seconds: None,
+        time_bank_refill_seconds: None,
+        time_bank_refill_hands: None,
+        enforce_missed_blinds: None,
+        min_buy_in_big_blinds: None,
+        max_buy_in_big_blinds: None,
+        rathole_window_seconds: None,
+        is_fast_fold: None,
     }
 }
 
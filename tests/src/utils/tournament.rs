@@ -147,7 +147,7 @@ impl TestEnv {
             tournament_id,
             tournament_id,
             "handle_user_losing",
-            encode_args((user_principal, table_id)).unwrap(),
+            encode_args((user_principal, table_id, Vec::<(Principal, u64)>::new())).unwrap(),
         );
 
         match result {